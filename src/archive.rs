@@ -0,0 +1,174 @@
+//! Report archiving for `--archive`.
+//!
+//! Each run is written to `tokval_<fingerprint>_<timestamp>.<ext>` in the
+//! archive directory. The fingerprint identifies the valuation inputs, so runs
+//! of the same inputs sort together, and the UTC timestamp orders them. Files
+//! are never overwritten: a name already taken gets a numeric suffix.
+
+use crate::error::ModelError;
+use crate::model::ValuationInputs;
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Write `contents` into `dir` under an input-fingerprinted, timestamped name,
+/// creating the directory if needed, and return the path written
+pub fn write_archive(
+    dir: &Path,
+    inputs: &ValuationInputs,
+    extension: &str,
+    contents: &str,
+) -> Result<PathBuf, ModelError> {
+    std::fs::create_dir_all(dir).map_err(|source| ModelError::Io {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let stem = format!(
+        "tokval_{}_{}",
+        &input_fingerprint(inputs)[..8],
+        format_utc_timestamp(seconds)
+    );
+
+    for attempt in 0.. {
+        let name = match attempt {
+            0 => format!("{}.{}", stem, extension),
+            n => format!("{}-{}.{}", stem, n, extension),
+        };
+        let path = dir.join(name);
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                return file
+                    .write_all(contents.as_bytes())
+                    .map(|_| path.clone())
+                    .map_err(|source| ModelError::Io { path, source });
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(source) => return Err(ModelError::Io { path, source }),
+        }
+    }
+    unreachable!("archive name attempts are unbounded")
+}
+
+/// Stable 64-bit FNV-1a hash of the valuation inputs, as 16 hex digits.
+///
+/// The thread count is left out since it does not change any value, and the
+/// rate matrix is hashed in sorted order so its map layout cannot leak in.
+pub fn input_fingerprint(inputs: &ValuationInputs) -> String {
+    let mut canonical = format!(
+        "{:?}",
+        ValuationInputs {
+            threads: None,
+            rate_matrix: None,
+            ..inputs.clone()
+        }
+    );
+    if let Some(matrix) = &inputs.rate_matrix {
+        for payout in matrix.payouts() {
+            for volatility in crate::model::VolatilityScenario::all() {
+                canonical.push_str(&format!(
+                    "|{}/{}={:?}",
+                    payout,
+                    volatility,
+                    matrix.rate(payout, *volatility)
+                ));
+            }
+        }
+    }
+
+    let hash = canonical
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
+
+/// Seconds since the Unix epoch as a basic-format ISO 8601 UTC timestamp,
+/// e.g. `20261015T083000Z`, which is safe in file names on every platform
+fn format_utc_timestamp(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64;
+    let time = seconds % 86_400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Args;
+    use clap::Parser;
+
+    fn inputs(argv: &[&str]) -> ValuationInputs {
+        let mut full = vec!["tokval", "--forecast", "220000"];
+        full.extend_from_slice(argv);
+        Args::try_parse_from(full)
+            .unwrap()
+            .valuation_inputs()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_utc_timestamp_format() {
+        assert_eq!(format_utc_timestamp(0), "19700101T000000Z");
+        assert_eq!(format_utc_timestamp(951_782_400), "20000229T000000Z");
+        assert_eq!(format_utc_timestamp(1_791_966_645), "20261014T083045Z");
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_valuation_inputs_only() {
+        let base = input_fingerprint(&inputs(&[]));
+        assert_eq!(base.len(), 16);
+        assert_eq!(base, input_fingerprint(&inputs(&["--threads", "4"])));
+        assert_ne!(base, input_fingerprint(&inputs(&["--rpm", "16"])));
+    }
+
+    #[test]
+    fn test_archive_never_overwrites() {
+        let dir = std::env::temp_dir().join(format!("tokval_archive_test_{}", std::process::id()));
+        let inputs = inputs(&[]);
+        let first = write_archive(&dir, &inputs, "txt", "first").unwrap();
+        let second = write_archive(&dir, &inputs, "txt", "second").unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "first");
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "second");
+        let name = first.file_name().unwrap().to_string_lossy().into_owned();
+        assert!(name.starts_with(&format!("tokval_{}_", &input_fingerprint(&inputs)[..8])));
+        assert!(name.ends_with(".txt"));
+
+        // A file where the directory should be surfaces as an I/O error
+        let blocked = dir.join("not_a_dir");
+        std::fs::write(&blocked, "").unwrap();
+        assert!(matches!(
+            write_archive(&blocked, &inputs, "txt", ""),
+            Err(ModelError::Io { .. })
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,243 @@
+//! Expected-value checks for `--assert-values`.
+//!
+//! An assertions file lists one grid cell per line as
+//! `scenario, expected present value, tolerance`, where the scenario is
+//! written as in the raw report (e.g. `90 Days / Typical / Medium Lift` or
+//! `60 Days / Low Vol / Baseline`) and the tolerance is absolute, or relative
+//! to the expected value with a trailing `%`. The tolerance may be omitted
+//! for an exact-to-the-cent check. Blank lines and `#` comments are ignored.
+
+use crate::error::{FileError, ModelError};
+use crate::model::ReportData;
+use std::path::Path;
+
+/// Allowed deviation from an expected value when none is given: one cent
+const DEFAULT_TOLERANCE: f64 = 0.01;
+
+/// How far a computed value may stray from its expected value
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Tolerance {
+    /// Fixed amount in currency units
+    Absolute(f64),
+    /// Fraction of the expected value (e.g. 0.005 for 0.5%)
+    Relative(f64),
+}
+
+impl Tolerance {
+    /// Largest accepted absolute deviation from `expected`
+    fn allowance(&self, expected: f64) -> f64 {
+        match self {
+            Self::Absolute(amount) => *amount,
+            Self::Relative(fraction) => fraction * expected.abs(),
+        }
+    }
+}
+
+impl std::fmt::Display for Tolerance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Absolute(amount) => write!(f, "±{}", amount),
+            Self::Relative(fraction) => write!(f, "±{}%", fraction * 100.0),
+        }
+    }
+}
+
+/// One expected grid value
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpectedValue {
+    /// Scenario label as written in the file
+    pub scenario: String,
+    /// Expected present value
+    pub expected: f64,
+    /// Accepted deviation
+    pub tolerance: Tolerance,
+}
+
+/// An expected value the computed grid failed to meet
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    /// The expectation that failed
+    pub expectation: ExpectedValue,
+    /// Computed present value, or `None` if the scenario is not in the grid
+    pub actual: Option<f64>,
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let expectation = &self.expectation;
+        match self.actual {
+            Some(actual) => write!(
+                f,
+                "{}: expected {} ({}), got {} (off by {})",
+                expectation.scenario,
+                expectation.expected,
+                expectation.tolerance,
+                actual,
+                actual - expectation.expected
+            ),
+            None => write!(
+                f,
+                "{}: scenario not found in the valuation grid",
+                expectation.scenario
+            ),
+        }
+    }
+}
+
+/// Load expected values from an assertions file
+pub fn load_expectations(path: &Path) -> Result<Vec<ExpectedValue>, ModelError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| ModelError::InputFile(format!("{}: {}", path.display(), e)))?;
+    parse_expectations(&text).map_err(|e| e.in_file(path))
+}
+
+/// Parse expected values, locating any problem by line and field
+pub fn parse_expectations(text: &str) -> Result<Vec<ExpectedValue>, FileError> {
+    let mut expectations = Vec::new();
+    for (line_index, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let (scenario, expected, tolerance) = match fields.as_slice() {
+            [scenario, expected] => (*scenario, *expected, None),
+            [scenario, expected, tolerance] => (*scenario, *expected, Some(*tolerance)),
+            _ => {
+                return Err(FileError::at_line(
+                    line_index + 1,
+                    "expected 'scenario, value[, tolerance]'",
+                ));
+            }
+        };
+        let expected = expected.parse::<f64>().map_err(|_| {
+            FileError::at_field(
+                line_index + 1,
+                "value",
+                format!("invalid expected value '{}'", expected),
+            )
+        })?;
+        let tolerance = match tolerance {
+            None => Tolerance::Absolute(DEFAULT_TOLERANCE),
+            Some(text) => parse_tolerance(text).ok_or_else(|| {
+                FileError::at_field(
+                    line_index + 1,
+                    "tolerance",
+                    format!("invalid tolerance '{}'", text),
+                )
+            })?,
+        };
+        expectations.push(ExpectedValue {
+            scenario: scenario.to_string(),
+            expected,
+            tolerance,
+        });
+    }
+
+    if expectations.is_empty() {
+        return Err("assertions file has no expected values".into());
+    }
+    Ok(expectations)
+}
+
+fn parse_tolerance(text: &str) -> Option<Tolerance> {
+    let (number, relative) = match text.strip_suffix('%') {
+        Some(number) => (number.trim_end(), true),
+        None => (text, false),
+    };
+    let value = number.parse::<f64>().ok().filter(|v| *v >= 0.0)?;
+    Some(if relative {
+        Tolerance::Relative(value / 100.0)
+    } else {
+        Tolerance::Absolute(value)
+    })
+}
+
+/// Compare every expectation against the computed grid, returning all misses
+pub fn check_values(data: &ReportData, expectations: &[ExpectedValue]) -> Vec<Mismatch> {
+    expectations
+        .iter()
+        .filter_map(|expectation| {
+            let actual = data
+                .all_valuations
+                .iter()
+                .find(|v| {
+                    v.scenario()
+                        .to_string()
+                        .eq_ignore_ascii_case(&expectation.scenario)
+                })
+                .map(|v| v.present_value);
+            let within = actual.is_some_and(|actual| {
+                (actual - expectation.expected).abs()
+                    <= expectation.tolerance.allowance(expectation.expected)
+            });
+            (!within).then(|| Mismatch {
+                expectation: expectation.clone(),
+                actual,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Args;
+    use crate::valuation::calculate_full_valuation;
+    use clap::Parser;
+
+    #[test]
+    fn test_parse_expectations() {
+        let expectations = parse_expectations(
+            "# golden values\n\
+             90 Days / Typical / Medium Lift, 1000.5, 2\n\
+             \n\
+             60 Days / Low Vol / Baseline, 900, 0.5%  # relative\n\
+             120 Days / Extreme / High Lift, 800\n",
+        )
+        .unwrap();
+        assert_eq!(expectations.len(), 3);
+        assert_eq!(expectations[0].tolerance, Tolerance::Absolute(2.0));
+        assert_eq!(expectations[1].tolerance, Tolerance::Relative(0.005));
+        assert_eq!(expectations[2].tolerance, Tolerance::Absolute(0.01));
+
+        assert!(
+            parse_expectations("90 Days / Typical / Baseline, abc\n")
+                .unwrap_err()
+                .to_string()
+                .contains("line 1")
+        );
+        assert!(parse_expectations("# nothing\n").is_err());
+        assert_eq!(
+            parse_expectations("# header\n90 Days / Typical / Baseline, 100, 5 pct\n")
+                .unwrap_err()
+                .to_string(),
+            "line 2, field 'tolerance': invalid tolerance '5 pct'"
+        );
+    }
+
+    #[test]
+    fn test_check_values_reports_every_mismatch() {
+        let data = Args::try_parse_from(["tokval", "--forecast", "220000"])
+            .unwrap()
+            .valuation_inputs()
+            .and_then(|inputs| calculate_full_valuation(&inputs))
+            .unwrap();
+        let central = data.summary.central_estimate;
+        let text = format!(
+            "90 Days / Typical / Medium Lift, {}, 1\n\
+             90 Days / Typical / Medium Lift, {}, 1%\n\
+             90 Days / Typical / Medium Lift, {}, 0.1%\n\
+             45 Days / Typical / Baseline, 1000\n",
+            central + 0.5,
+            central * 1.005,
+            central * 1.01,
+        );
+        let mismatches = check_values(&data, &parse_expectations(&text).unwrap());
+
+        assert_eq!(mismatches.len(), 2);
+        assert!(mismatches[0].actual.is_some());
+        assert_eq!(mismatches[1].actual, None);
+        assert!(mismatches[1].to_string().contains("not found"));
+    }
+}
@@ -0,0 +1,352 @@
+//! Valid ranges and units of the numeric inputs, for `--print-bounds`.
+//!
+//! Bounds are given in the units the flag takes (percentages for rate flags),
+//! so a front end can use them directly for sliders and field validation.
+//! Input validation checks the same table, so the published bounds cannot
+//! drift from what is actually accepted.
+
+use crate::json::JsonValue;
+use crate::rate::{MAX_DECIMAL, MIN_DECIMAL};
+
+/// One end of an input's valid range
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Limit {
+    /// The value itself is allowed
+    Inclusive(f64),
+    /// Only values strictly beyond it are allowed
+    Exclusive(f64),
+}
+
+impl Limit {
+    fn value(self) -> f64 {
+        match self {
+            Self::Inclusive(value) | Self::Exclusive(value) => value,
+        }
+    }
+
+    fn is_inclusive(self) -> bool {
+        matches!(self, Self::Inclusive(_))
+    }
+}
+
+/// Valid range, units and typical values of one input
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputBound {
+    /// Argument id, as used in config files (`--risk-free-rate` is `risk_free_rate`)
+    pub name: &'static str,
+    /// Units the flag's value is given in
+    pub units: &'static str,
+    /// Lowest accepted value, if bounded below
+    pub min: Option<Limit>,
+    /// Highest accepted value, if bounded above
+    pub max: Option<Limit>,
+    /// Range most real inputs fall in, for slider defaults
+    pub typical: Option<(f64, f64)>,
+}
+
+impl InputBound {
+    /// Whether `value` (in the flag's units) lies in range; non-finite values never do
+    pub fn admits(&self, value: f64) -> bool {
+        let above_min = match self.min {
+            Some(Limit::Inclusive(min)) => value >= min,
+            Some(Limit::Exclusive(min)) => value > min,
+            None => true,
+        };
+        let below_max = match self.max {
+            Some(Limit::Inclusive(max)) => value <= max,
+            Some(Limit::Exclusive(max)) => value < max,
+            None => true,
+        };
+        value.is_finite() && above_min && below_max
+    }
+}
+
+const POSITIVE: Option<Limit> = Some(Limit::Exclusive(0.0));
+const NON_NEGATIVE: Option<Limit> = Some(Limit::Inclusive(0.0));
+const RATE_MIN: Option<Limit> = Some(Limit::Exclusive(MIN_DECIMAL * 100.0));
+const RATE_MAX: Option<Limit> = Some(Limit::Inclusive(MAX_DECIMAL * 100.0));
+
+/// Bounds of every range-checked numeric input
+pub const INPUT_BOUNDS: &[InputBound] = &[
+    InputBound {
+        name: "forecast",
+        units: "dollars per period",
+        min: POSITIVE,
+        max: None,
+        typical: None,
+    },
+    InputBound {
+        name: "risk_free_rate",
+        units: "percent per year",
+        min: RATE_MIN,
+        max: RATE_MAX,
+        typical: Some((0.0, 10.0)),
+    },
+    InputBound {
+        name: "platform_risk_premium",
+        units: "percent per year",
+        min: RATE_MIN,
+        max: RATE_MAX,
+        typical: Some((5.0, 20.0)),
+    },
+    InputBound {
+        name: "baseline_audience",
+        units: "monthly audience",
+        min: POSITIVE,
+        max: None,
+        typical: None,
+    },
+    InputBound {
+        name: "rpm",
+        units: "dollars per thousand impressions",
+        min: POSITIVE,
+        max: None,
+        typical: Some((1.0, 50.0)),
+    },
+    InputBound {
+        name: "incremental_rpm",
+        units: "dollars per thousand impressions",
+        min: NON_NEGATIVE,
+        max: None,
+        typical: Some((1.0, 50.0)),
+    },
+    InputBound {
+        name: "lift_per_investor",
+        units: "monthly audience per investor",
+        min: NON_NEGATIVE,
+        max: None,
+        typical: None,
+    },
+    InputBound {
+        name: "fixed_lift",
+        units: "dollars per period",
+        min: NON_NEGATIVE,
+        max: None,
+        typical: None,
+    },
+    InputBound {
+        name: "lift_acquisition_cost",
+        units: "dollars",
+        min: NON_NEGATIVE,
+        max: None,
+        typical: None,
+    },
+    InputBound {
+        name: "quarters",
+        units: "periods",
+        min: Some(Limit::Inclusive(1.0)),
+        max: None,
+        typical: Some((1.0, 12.0)),
+    },
+    InputBound {
+        name: "purchase_price",
+        units: "dollars",
+        min: POSITIVE,
+        max: None,
+        typical: None,
+    },
+    InputBound {
+        name: "market_price",
+        units: "dollars",
+        min: POSITIVE,
+        max: None,
+        typical: None,
+    },
+    InputBound {
+        name: "ownership_fraction",
+        units: "fraction of the pool",
+        min: POSITIVE,
+        max: Some(Limit::Inclusive(1.0)),
+        typical: None,
+    },
+    InputBound {
+        name: "fx_rate",
+        units: "display currency per US dollar",
+        min: POSITIVE,
+        max: None,
+        typical: None,
+    },
+    InputBound {
+        name: "active_fraction",
+        units: "fraction of holders",
+        min: NON_NEGATIVE,
+        max: Some(Limit::Inclusive(1.0)),
+        typical: Some((0.1, 0.5)),
+    },
+    InputBound {
+        name: "quarterly_growth_rate",
+        units: "percent per period",
+        min: Some(Limit::Exclusive(-100.0)),
+        max: None,
+        typical: Some((-5.0, 5.0)),
+    },
+    InputBound {
+        name: "stage1_quarters",
+        units: "periods",
+        min: Some(Limit::Inclusive(1.0)),
+        max: None,
+        typical: Some((1.0, 8.0)),
+    },
+    InputBound {
+        name: "stage1_growth",
+        units: "percent per period",
+        min: Some(Limit::Exclusive(-100.0)),
+        max: None,
+        typical: Some((-5.0, 10.0)),
+    },
+    InputBound {
+        name: "stage2_growth",
+        units: "percent per period",
+        min: Some(Limit::Exclusive(-100.0)),
+        max: None,
+        typical: Some((-5.0, 5.0)),
+    },
+    InputBound {
+        name: "reinvestment_rate",
+        units: "percent of lift revenue",
+        min: NON_NEGATIVE,
+        max: None,
+        typical: Some((0.0, 50.0)),
+    },
+    InputBound {
+        name: "revenue_floor",
+        units: "dollars per period",
+        min: NON_NEGATIVE,
+        max: None,
+        typical: None,
+    },
+    InputBound {
+        name: "lift_dominance_threshold",
+        units: "multiple of the adjusted baseline",
+        min: POSITIVE,
+        max: None,
+        typical: Some((0.5, 2.0)),
+    },
+    InputBound {
+        name: "tax_rate",
+        units: "percent",
+        min: NON_NEGATIVE,
+        max: Some(Limit::Exclusive(100.0)),
+        typical: Some((0.0, 40.0)),
+    },
+    InputBound {
+        name: "threads",
+        units: "threads",
+        min: Some(Limit::Inclusive(1.0)),
+        max: None,
+        typical: None,
+    },
+];
+
+/// The bounds of the input `name`
+///
+/// # Panics
+///
+/// If `name` has no entry in [`INPUT_BOUNDS`]; callers name inputs statically.
+pub fn bound(name: &str) -> &'static InputBound {
+    INPUT_BOUNDS
+        .iter()
+        .find(|bound| bound.name == name)
+        .unwrap_or_else(|| panic!("no bounds for input '{}'", name))
+}
+
+impl From<&InputBound> for JsonValue {
+    fn from(bound: &InputBound) -> Self {
+        let (typical_min, typical_max) = bound.typical.unzip();
+        JsonValue::Object(vec![
+            ("name".to_string(), bound.name.into()),
+            (
+                "flag".to_string(),
+                format!("--{}", bound.name.replace('_', "-")).into(),
+            ),
+            ("units".to_string(), bound.units.into()),
+            ("min".to_string(), bound.min.map(Limit::value).into()),
+            (
+                "min_inclusive".to_string(),
+                bound.min.map(Limit::is_inclusive).into(),
+            ),
+            ("max".to_string(), bound.max.map(Limit::value).into()),
+            (
+                "max_inclusive".to_string(),
+                bound.max.map(Limit::is_inclusive).into(),
+            ),
+            ("typical_min".to_string(), typical_min.into()),
+            ("typical_max".to_string(), typical_max.into()),
+        ])
+    }
+}
+
+/// Every input's bounds as a JSON array, for `--print-bounds`
+pub fn bounds_json() -> JsonValue {
+    JsonValue::Array(INPUT_BOUNDS.iter().map(JsonValue::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Args;
+    use clap::{CommandFactory, Parser};
+
+    #[test]
+    fn test_every_bound_names_a_flag() {
+        let command = Args::command();
+        for bound in INPUT_BOUNDS {
+            assert!(
+                command
+                    .get_arguments()
+                    .any(|arg| arg.get_id() == bound.name),
+                "{} is not an argument",
+                bound.name
+            );
+        }
+        let json = bounds_json();
+        let tax = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|b| b.get("name").and_then(JsonValue::as_str) == Some("tax_rate"))
+            .unwrap();
+        assert_eq!(
+            tax.get("flag").and_then(JsonValue::as_str),
+            Some("--tax-rate")
+        );
+        assert_eq!(tax.get("max").and_then(JsonValue::as_f64), Some(100.0));
+        assert_eq!(tax.get("max_inclusive"), Some(&JsonValue::Bool(false)));
+    }
+
+    #[test]
+    fn test_validation_follows_the_bounds() {
+        let valid = |argv: &[&str]| {
+            let mut full = vec!["tokval", "--forecast", "220000"];
+            full.extend_from_slice(argv);
+            Args::try_parse_from(full)
+                .unwrap()
+                .valuation_inputs()
+                .unwrap()
+                .validate()
+                .is_ok()
+        };
+        for name in [
+            "tax_rate",
+            "quarterly_growth_rate",
+            "rpm",
+            "purchase_price",
+            "ownership_fraction",
+            "quarters",
+            "lift_dominance_threshold",
+        ] {
+            let bound = bound(name);
+            let flag = format!("--{}", name.replace('_', "-"));
+            for limit in [bound.min, bound.max].into_iter().flatten() {
+                let value = limit.value().to_string();
+                assert_eq!(
+                    valid(&[&flag, &value]),
+                    limit.is_inclusive(),
+                    "{} at {}",
+                    flag,
+                    value
+                );
+            }
+        }
+    }
+}
@@ -1,25 +1,71 @@
 //! Command-line interface definitions for tokval.
 
-use clap::Parser;
+use crate::bounds::bound;
+use crate::config::{PartialInputs, conflicts};
+use crate::error::ModelError;
+use crate::history::{HistoryAggregation, load_history};
+use crate::model::{
+    Compounding, DayCount, DisplayCurrency, EmitFigure, ForecastConfidence, ForecastUnits,
+    HeatmapStyle, InputSources, LiftRamp, LiftScenario, OutputFormat, PayoutLadder,
+    RateComponentUnits, ReportSection, RevenuePeriod, ScenarioWeights, TwoStageGrowth,
+    ValuationInputs,
+};
+use crate::profile::{load_profile, profile_dir};
+use crate::rate::Rate;
+use crate::rate_formula::RateFormula;
+use crate::rate_matrix::load_rate_matrix;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser};
+use num_format::Locale;
+use std::ffi::OsString;
+use std::io::IsTerminal;
+use std::path::PathBuf;
 
 /// Token Valuator - Calculate fair present value of tokenized quarterly ad revenue
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
-    /// Publisher's raw quarterly revenue forecast
-    #[arg(short, long)]
-    pub forecast: f64,
+    /// Publisher's raw revenue forecast for one period (see --period)
+    #[arg(short, long, required_unless_present_any = ["portfolio", "config", "scenario_profile", "list_profiles", "print_bounds"])]
+    pub forecast: Option<f64>,
+
+    /// Scale --forecast is entered in: dollars, thousands or millions
+    #[arg(long, default_value = "dollars")]
+    pub forecast_units: ForecastUnits,
+
+    /// Revenue period the forecast, lift and horizon are counted in: weekly,
+    /// monthly or quarterly
+    #[arg(long, default_value = "quarterly")]
+    pub period: RevenuePeriod,
+
+    /// Risk-free rate (as percentage, e.g., 4.5 or 4.5% for 4.5%)
+    #[arg(short, long, default_value = "4.5", value_parser = parse_percent_rate)]
+    pub risk_free_rate: Rate,
+
+    /// Fetch the current risk-free rate from --risk-free-rate-url, falling
+    /// back to --risk-free-rate if the lookup fails
+    #[cfg(feature = "network")]
+    #[arg(long, requires = "risk_free_rate_url")]
+    pub fetch_risk_free_rate: bool,
 
-    /// Risk-free rate (as percentage, e.g., 4.5 for 4.5%)
-    #[arg(short, long, default_value = "4.5")]
-    pub risk_free_rate: f64,
+    /// HTTP endpoint returning the 3-month Treasury rate as a percentage,
+    /// either bare or as JSON with a "rate" key
+    #[cfg(feature = "network")]
+    #[arg(long, value_name = "URL")]
+    pub risk_free_rate_url: Option<String>,
 
     /// Platform risk premium (as percentage)
-    #[arg(short = 'p', long, default_value = "12.0")]
-    pub platform_risk_premium: f64,
+    #[arg(short = 'p', long, default_value = "12.0", value_parser = parse_percent_rate)]
+    pub platform_risk_premium: Rate,
 
     /// Platform adjustment factor (as percentage, negative for reduction)
-    #[arg(short = 'a', long, default_value = "-9.1")]
+    #[arg(
+        short = 'a',
+        long,
+        default_value = "-9.1",
+        value_parser = parse_percentage,
+        allow_hyphen_values = true
+    )]
     pub platform_adjustment: f64,
 
     /// Baseline monthly audience for lift model calculations
@@ -30,13 +76,873 @@ pub struct Args {
     #[arg(long, default_value = "15.0")]
     pub rpm: f64,
 
+    /// CSV of monthly history (month,audience,rpm) from which to derive the
+    /// baseline audience and RPM instead of --baseline-audience and --rpm
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["baseline_audience", "rpm"])]
+    pub history: Option<PathBuf>,
+
+    /// How --history months are combined: mean, median or last
+    #[arg(long, default_value = "mean", requires = "history")]
+    pub history_agg: HistoryAggregation,
+
+    /// RPM earned by incremental (lift) audience; defaults to --rpm
+    #[arg(long)]
+    pub incremental_rpm: Option<f64>,
+
     /// --- NEW ---
     /// Estimated number of token investors to model lift
     #[arg(long, default_value = "1000")]
     pub investor_count: u32,
 
+    /// Total token holders; with --active-fraction, the expected active
+    /// investor count replaces --investor-count
+    #[arg(long, requires = "active_fraction", conflicts_with = "investor_count")]
+    pub total_holders: Option<u32>,
+
+    /// Fraction of --total-holders expected to be active (0 to 1, e.g., 0.3)
+    #[arg(long, requires = "total_holders")]
+    pub active_fraction: Option<f64>,
+
     /// --- NEW ---
     /// Estimated new audience members generated per active investor per month
     #[arg(long, default_value = "10")]
     pub lift_per_investor: f64,
+
+    /// Value the baseline revenue only, skipping all investor lift scenarios
+    #[arg(long)]
+    pub baseline_only: bool,
+
+    /// Pay the quarter in tranches instead of a single payment, as DAYS:FRACTION
+    /// pairs (e.g., 30:0.3,60:0.4,90:0.3); fractions must sum to 1.0
+    #[arg(long)]
+    pub payout_ladder: Option<PayoutLadder>,
+
+    /// Split each quarter into three equal monthly cash flows 30 days apart,
+    /// the last on the payout date, each discounted separately
+    #[arg(long, conflicts_with = "payout_ladder")]
+    pub monthly_breakdown: bool,
+
+    /// Days until lift-driven revenue is paid, discounting it apart from the
+    /// baseline; defaults to each scenario's baseline payout timing
+    #[arg(long, value_name = "DAYS")]
+    pub lift_payout_days: Option<u32>,
+
+    /// How the lift audience builds up over the quarter: instant or linear
+    #[arg(long, default_value = "instant")]
+    pub lift_ramp: LiftRamp,
+
+    /// Fixed per-period lift in dollars at medium activation, replacing the
+    /// investor-driver lift model; other lift scenarios scale it by activation
+    #[arg(long, value_name = "AMOUNT")]
+    pub fixed_lift: Option<f64>,
+
+    /// Cost in dollars of acquiring the modeled lift audience, to report how
+    /// many periods of lift revenue pay it back
+    #[arg(long, value_name = "AMOUNT")]
+    pub lift_acquisition_cost: Option<f64>,
+
+    /// Day-count convention for payout delays: actual365, actual360 or thirty360
+    #[arg(long, default_value = "actual365")]
+    pub day_count: DayCount,
+
+    /// Compounding convention for discounting: discrete or continuous
+    #[arg(long, default_value = "discrete")]
+    pub compounding: Compounding,
+
+    /// Confidence in the forecast: high narrows the volatility premiums
+    /// around the typical premium, low widens them, medium keeps them
+    #[arg(long, default_value = "medium", conflicts_with_all = ["discount_rates", "rate_matrix"])]
+    pub forecast_confidence: ForecastConfidence,
+
+    /// Append an appendix comparing each scenario under discrete and continuous discounting
+    #[arg(long)]
+    pub compare_methods: bool,
+
+    /// Append an appendix valuing the central scenario under each day-count convention
+    #[arg(long)]
+    pub compare_payout_conventions: bool,
+
+    /// Compare the central estimate under driver-based lift and --fixed-lift
+    /// in the lift section
+    #[arg(long, requires = "fixed_lift")]
+    pub compare_lift_models: bool,
+
+    /// Number of consecutive periods of cash flow to value
+    #[arg(long, default_value = "1")]
+    pub quarters: u32,
+
+    /// Price paid for the token pool, to report fair value per dollar invested
+    #[arg(long)]
+    pub purchase_price: Option<f64>,
+
+    /// Observed market price of the token pool, to report the stress level
+    /// on the volatility scenarios that the price implies
+    #[arg(long, value_name = "PRICE")]
+    pub market_price: Option<f64>,
+
+    /// Investor's share of the token pool (0-1], scaling values to their position
+    #[arg(long, conflicts_with_all = ["tokens_held", "token_supply"])]
+    pub ownership_fraction: Option<f64>,
+
+    /// Tokens held by the investor, with --token-supply, to value their position
+    #[arg(long, requires = "token_supply")]
+    pub tokens_held: Option<f64>,
+
+    /// Total tokens in the pool, with --tokens-held
+    #[arg(long, requires = "tokens_held")]
+    pub token_supply: Option<f64>,
+
+    /// Currency to show present values and other monetary figures in (ISO
+    /// code, e.g., EUR), with --fx-rate; the valuation itself stays in USD
+    #[arg(long, value_name = "CODE", requires = "fx_rate", value_parser = parse_currency_code)]
+    pub display_currency: Option<String>,
+
+    /// Units of the display currency per US dollar (e.g., 0.92 for EUR), with
+    /// --display-currency
+    #[arg(
+        long,
+        value_name = "RATE",
+        requires = "display_currency",
+        allow_hyphen_values = true
+    )]
+    pub fx_rate: Option<f64>,
+
+    /// Period-over-period cash flow growth (as percentage) in multi-period mode
+    #[arg(long, default_value = "0", value_parser = parse_percentage, allow_hyphen_values = true)]
+    pub quarterly_growth_rate: f64,
+
+    /// Periods of stage-1 growth in a two-stage growth model, with
+    /// --stage1-growth and --stage2-growth; replaces --quarterly-growth-rate
+    #[arg(
+        long,
+        value_name = "N",
+        requires_all = ["stage1_growth", "stage2_growth"],
+        conflicts_with = "quarterly_growth_rate"
+    )]
+    pub stage1_quarters: Option<u32>,
+
+    /// Per-period growth (as percentage) for the first --stage1-quarters periods
+    #[arg(
+        long,
+        value_parser = parse_percentage,
+        allow_hyphen_values = true,
+        requires = "stage1_quarters"
+    )]
+    pub stage1_growth: Option<f64>,
+
+    /// Per-period growth (as percentage) after the first --stage1-quarters periods
+    #[arg(
+        long,
+        value_parser = parse_percentage,
+        allow_hyphen_values = true,
+        requires = "stage1_quarters"
+    )]
+    pub stage2_growth: Option<f64>,
+
+    /// Share of each period's lift revenue (as percentage) reinvested into
+    /// audience for later periods in multi-period mode
+    #[arg(long, default_value = "0", value_parser = parse_percentage)]
+    pub reinvestment_rate: f64,
+
+    /// Contractual minimum revenue per period; each scenario's per-period cash
+    /// flow is raised to this amount before growth and discounting
+    #[arg(long, value_name = "AMOUNT")]
+    pub revenue_floor: Option<f64>,
+
+    /// Tax rate (as percentage) applied to every cash flow before discounting
+    #[arg(long, default_value = "0", value_parser = parse_percentage)]
+    pub tax_rate: f64,
+
+    /// Headline pre-tax values (the default)
+    #[arg(long, conflicts_with = "after_tax")]
+    pub pre_tax: bool,
+
+    /// Headline after-tax values, net of --tax-rate
+    #[arg(long)]
+    pub after_tax: bool,
+
+    /// Fail instead of clamping when growth meets or exceeds the discount rate
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Cap each present value at its face value, warning for any capped scenario
+    #[arg(long)]
+    pub cap_at_face: bool,
+
+    /// Warn when a lift scenario's per-period revenue exceeds this multiple of
+    /// the adjusted baseline (often monthly figures entered as quarterly)
+    #[arg(long, default_value_t = 1.0)]
+    pub lift_dominance_threshold: f64,
+
+    /// Refuse to compute grids with more scenario evaluations than this
+    #[arg(long, default_value_t = 10_000)]
+    pub max_scenarios: usize,
+
+    /// Reject supplied payout delays (ladder tranches, --lift-payout-days)
+    /// longer than this many days
+    #[arg(long, default_value_t = 3650)]
+    pub max_payout_days: u32,
+
+    /// Worker threads for valuing the scenario grid; defaults to every available core
+    #[arg(long)]
+    pub threads: Option<usize>,
+
+    /// Total discount rates to use verbatim for the Low/Typical/High/Extreme
+    /// volatility scenarios, as comma-separated decimals (e.g., 0.20,0.26,0.36,0.47)
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true, value_parser = parse_decimal_rate)]
+    pub discount_rates: Option<Vec<Rate>>,
+
+    /// CSV of total discount rates per payout (rows) and volatility (columns)
+    /// scenario, used verbatim in place of the composed rates
+    #[arg(long, value_name = "FILE", conflicts_with = "discount_rates")]
+    pub rate_matrix: Option<PathBuf>,
+
+    /// Formula for each volatility scenario's total discount rate in place of
+    /// the component sum, over the decimal variables rf, vol_premium and
+    /// platform_premium (e.g., "rf + 0.5*vol_premium + platform_premium")
+    #[arg(long, value_name = "EXPR", conflicts_with_all = ["discount_rates", "rate_matrix"])]
+    pub rate_formula: Option<RateFormula>,
+
+    /// Comma-separated percentiles (0-100, ascending) of the scenario
+    /// distribution to tabulate in the report; no table when omitted
+    #[arg(long, value_delimiter = ',')]
+    pub percentiles: Vec<f64>,
+
+    /// Replace absolute currency figures with index points (adjusted baseline = 100)
+    #[arg(long)]
+    pub redact: bool,
+
+    /// Repeat the key figures in a second locale (e.g., de) as a report footnote
+    #[arg(long, value_name = "LOCALE")]
+    pub also_format_locale: Option<String>,
+
+    /// Locale for audience counts (e.g., de for 1.000.000), defaulting to the
+    /// currency locale
+    #[arg(long, value_name = "LOCALE")]
+    pub audience_locale: Option<String>,
+
+    /// Output format: text (the full report), markdown (the full report with
+    /// pipe tables), csv-long (one row per scenario), json (versioned, for
+    /// archival) or prometheus (headline metrics for monitoring)
+    #[arg(long, default_value = "text", conflicts_with = "raw_numbers")]
+    pub format: OutputFormat,
+
+    /// Indent JSON output (--format json, --explain-json) for reading; the
+    /// default on a terminal
+    #[arg(long, conflicts_with = "compact")]
+    pub pretty: bool,
+
+    /// Print JSON output on a single line; the default when piped or archived
+    #[arg(long)]
+    pub compact: bool,
+
+    /// Value of the `asset` label on Prometheus metrics; defaults to the
+    /// input fingerprint
+    #[arg(long, value_name = "ID")]
+    pub asset_id: Option<String>,
+
+    /// Print only this figure (min, max or central) as a bare number, for scripts
+    #[arg(long, conflicts_with_all = ["raw_numbers", "format", "explain_json", "assert_values"])]
+    pub emit: Option<EmitFigure>,
+
+    /// Print only the central estimate as a bare number; shorthand for --emit central
+    #[arg(long, conflicts_with_all = ["emit", "raw_numbers", "format", "explain_json", "assert_values"])]
+    pub emit_central: bool,
+
+    /// Write the report, in the requested format, to a new file in this
+    /// directory named by input fingerprint and UTC timestamp, instead of printing it
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["emit", "emit_central", "explain_json", "assert_values", "portfolio"])]
+    pub archive: Option<PathBuf>,
+
+    /// Print the calculation tree behind every scenario as JSON instead of the report
+    #[arg(long, conflicts_with_all = ["raw_numbers", "format"])]
+    pub explain_json: bool,
+
+    /// Print the step-by-step audience-to-revenue arithmetic for each lift
+    /// scenario instead of the report
+    #[arg(long, conflicts_with_all = ["raw_numbers", "format", "explain_json", "baseline_only"])]
+    pub explain_lift: bool,
+
+    /// Print each volatility scenario's discount factor from day 0 to the
+    /// longest payout as CSV instead of the report
+    #[arg(long, conflicts_with_all = ["raw_numbers", "format", "explain_json", "explain_lift", "rate_matrix"])]
+    pub export_curve: bool,
+
+    /// Days between the points of --export-curve
+    #[arg(
+        long,
+        value_name = "DAYS",
+        default_value = "5",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub curve_step: u32,
+
+    /// Print each numeric input's valid range, units and typical values as
+    /// JSON, then exit
+    #[arg(long)]
+    pub print_bounds: bool,
+
+    /// Value the lift cash flow alone across the grid, as a growth option on
+    /// top of the baseline, and add it to the report as its own matrix
+    #[arg(long, conflicts_with = "baseline_only")]
+    pub value_lift_only: bool,
+
+    /// Write the JSON report, read it back and check every serialized value
+    /// against the computed data, exiting non-zero on any mismatch
+    #[arg(long, hide = true, conflicts_with_all = ["assert_values", "explain_json"])]
+    pub round_trip_check: bool,
+
+    /// Comma-separated report sections to render, in order (e.g.,
+    /// summary,matrix,insights); defaults to every section
+    #[arg(long, value_delimiter = ',')]
+    pub sections: Option<Vec<ReportSection>>,
+
+    /// Print every figure at full precision, one labeled value per line, instead of the report
+    #[arg(long)]
+    pub raw_numbers: bool,
+
+    /// Note the change between adjacent volatility columns beneath each lift matrix
+    #[arg(long)]
+    pub show_step_deltas: bool,
+
+    /// Follow each lift-matrix value with its percentage over the matching baseline cell
+    #[arg(long)]
+    pub since_baseline: bool,
+
+    /// Note under the discount-rate, lift and valuation tables the formula they are computed with
+    #[arg(long)]
+    pub show_formula: bool,
+
+    /// List every scenario by descending present value after the valuation matrices
+    #[arg(long)]
+    pub ranked: bool,
+
+    /// List only the N highest-valued scenarios; implies --ranked
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    pub top: Option<usize>,
+
+    /// Units for the discount-rate components: percent, or bps with each
+    /// component's share of the total rate
+    #[arg(long, default_value = "percent")]
+    pub rate_components_as: RateComponentUnits,
+
+    /// Shade the valuation matrices by value, from the matrix minimum to its maximum
+    #[arg(long, conflicts_with = "format")]
+    pub heatmap: bool,
+
+    /// Shade heatmaps with block characters instead of ANSI colors (also set by NO_COLOR)
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Present only one lift scenario (low, medium or high) in the report
+    #[arg(long, conflicts_with = "baseline_only")]
+    pub only_lift: Option<LiftScenario>,
+
+    /// Comma-separated relative likelihoods of the 60/90/120-day payout scenarios
+    /// (equal when omitted); any scale, normalized to sum to one
+    #[arg(long, value_delimiter = ',')]
+    pub payout_weights: Option<Vec<f64>>,
+
+    /// Comma-separated relative likelihoods of the Low/Typical/High/Extreme
+    /// volatility scenarios (equal when omitted)
+    #[arg(long, value_delimiter = ',')]
+    pub volatility_weights: Option<Vec<f64>>,
+
+    /// Comma-separated relative likelihoods of the Low/Medium/High lift
+    /// scenarios (equal when omitted)
+    #[arg(long, value_delimiter = ',')]
+    pub lift_weights: Option<Vec<f64>>,
+
+    /// Comma-separated display labels for the 60/90/120-day payout scenarios
+    #[arg(long, value_delimiter = ',')]
+    pub payout_labels: Option<Vec<String>>,
+
+    /// Comma-separated display labels for the Low/Typical/High/Extreme volatility scenarios
+    #[arg(long, value_delimiter = ',')]
+    pub volatility_labels: Option<Vec<String>>,
+
+    /// Comma-separated display labels for the Low/Medium/High lift scenarios
+    #[arg(long, value_delimiter = ',')]
+    pub lift_labels: Option<Vec<String>>,
+
+    /// Check computed present values against a file of expected values with
+    /// tolerances, exiting non-zero if any cell deviates
+    #[arg(long, value_name = "FILE", visible_alias = "benchmark-file")]
+    pub assert_values: Option<PathBuf>,
+
+    /// Validate the inputs and exit without valuing or printing a report
+    #[arg(long, visible_alias = "validate")]
+    pub dry_run: bool,
+
+    /// Report successful validation in --dry-run mode
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Print timing of each calculation stage to stderr
+    #[arg(long)]
+    pub profile: bool,
+
+    /// Value a portfolio of token positions described in a JSON file
+    #[arg(long, value_name = "FILE")]
+    pub portfolio: Option<PathBuf>,
+
+    /// JSON file of inputs keyed by flag name; repeat to layer files, later
+    /// ones overriding earlier, with flags on the command line winning last
+    #[arg(long, value_name = "FILE", conflicts_with = "portfolio")]
+    pub config: Vec<PathBuf>,
+
+    /// Named scenario profile from the profile library, applied beneath any
+    /// --config files and command-line flags (--profile times the stages)
+    #[arg(long, value_name = "NAME", conflicts_with = "portfolio")]
+    pub scenario_profile: Option<String>,
+
+    /// Profile library directory of NAME.toml files (default: $TOKVAL_PROFILES)
+    #[arg(long, value_name = "DIR")]
+    pub profile_dir: Option<PathBuf>,
+
+    /// List the profiles in the profile library and exit
+    #[arg(long)]
+    pub list_profiles: bool,
+}
+
+/// Record every argument whose value came from the command line or environment
+pub fn input_sources(matches: &ArgMatches) -> InputSources {
+    InputSources::new(
+        matches
+            .ids()
+            .filter(|id| {
+                matches!(
+                    matches.value_source(id.as_str()),
+                    Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+                )
+            })
+            .map(|id| id.as_str().to_string())
+            .collect(),
+    )
+}
+
+impl Args {
+    /// Parse the process arguments, layering in the `--scenario-profile` and any
+    /// `--config` files, and record which inputs were set explicitly and
+    /// which came from a profile or config
+    pub fn parse_with_sources() -> Result<(Self, InputSources), ModelError> {
+        let matches = Self::command().get_matches();
+        let args = Self::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+        let sources = input_sources(&matches);
+        if args.config.is_empty() && args.scenario_profile.is_none() {
+            return Ok((args, sources));
+        }
+
+        // The profile is the lowest layer, overridden by config files
+        let profile = match &args.scenario_profile {
+            Some(name) => load_profile(&profile_dir(args.profile_dir.as_deref())?, name)?,
+            None => PartialInputs::default(),
+        };
+        let configs = PartialInputs::load_layers(&args.config)?;
+
+        // Layered values go first, and only where no flag given directly sets
+        // the same input or one it cannot be combined with
+        let command = Self::command();
+        let overridden = |id: &str| {
+            sources.is_explicit(id)
+                || matches.ids().any(|given| {
+                    sources.is_explicit(given.as_str()) && conflicts(&command, id, given.as_str())
+                })
+        };
+        let layers = profile.clone().merge(configs.clone());
+        let layered: Vec<&str> = layers.ids().filter(|id| !overridden(id)).collect();
+        let mut argv: Vec<OsString> = vec!["tokval".into()];
+        argv.extend(layers.to_args(overridden).into_iter().map(OsString::from));
+        argv.extend(std::env::args_os().skip(1));
+
+        let matches = Self::command()
+            .try_get_matches_from(argv)
+            .unwrap_or_else(|e| e.exit());
+        let args = Self::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+        let mut layered_sources = input_sources(&matches);
+        for id in configs.ids().filter(|id| layered.contains(id)) {
+            layered_sources.mark_configured(id);
+        }
+        for id in profile.ids().filter(|id| layered.contains(id)) {
+            layered_sources.mark_profiled(id);
+        }
+        Ok((args, layered_sources))
+    }
+
+    /// The figure to print bare, from --emit or --emit-central
+    pub fn emit_figure(&self) -> Option<EmitFigure> {
+        self.emit
+            .or(self.emit_central.then_some(EmitFigure::Central))
+    }
+
+    /// Rows of the ranked scenario table for `--ranked` and `--top`, `None`
+    /// when it is not requested
+    pub fn ranked_rows(&self) -> Option<usize> {
+        (self.ranked || self.top.is_some()).then(|| self.top.unwrap_or(usize::MAX))
+    }
+
+    /// Whether JSON output is indented: as `--pretty` or `--compact` say,
+    /// otherwise only when printing to a terminal
+    pub fn pretty_json(&self) -> bool {
+        if self.pretty || self.compact {
+            return self.pretty;
+        }
+        self.archive.is_none() && std::io::stdout().is_terminal()
+    }
+
+    /// Heatmap shading for `--heatmap`: ANSI background colors on a terminal,
+    /// block characters under `--no-color`, a non-empty `NO_COLOR`, `--archive`
+    /// or when output is piped
+    pub fn heatmap_style(&self) -> Option<HeatmapStyle> {
+        self.heatmap.then(|| {
+            let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+            if self.no_color
+                || no_color_env
+                || self.archive.is_some()
+                || !std::io::stdout().is_terminal()
+            {
+                HeatmapStyle::Blocks
+            } else {
+                HeatmapStyle::Color
+            }
+        })
+    }
+
+    /// Convert the percentage-based CLI arguments into model inputs
+    pub fn valuation_inputs(&self) -> Result<ValuationInputs, ModelError> {
+        let raw_forecast = self
+            .forecast
+            .ok_or_else(|| ModelError::InvalidInput("A --forecast is required".to_string()))?
+            * self.forecast_units.factor();
+
+        let (baseline_audience, rpm) = match &self.history {
+            Some(path) => {
+                let history = load_history(path)?;
+                (
+                    history.baseline_audience(self.history_agg),
+                    history.blended_rpm(self.history_agg),
+                )
+            }
+            None => (self.baseline_audience, self.rpm),
+        };
+
+        let ownership_fraction = match (self.tokens_held, self.token_supply) {
+            (Some(held), Some(supply)) => {
+                if supply <= 0.0 || held <= 0.0 || held > supply {
+                    return Err(ModelError::InvalidInput(
+                        "--tokens-held must be positive and at most --token-supply".to_string(),
+                    ));
+                }
+                Some(held / supply)
+            }
+            _ => self.ownership_fraction,
+        };
+
+        let investor_count = match (self.total_holders, self.active_fraction) {
+            (Some(holders), Some(fraction)) => {
+                if !bound("active_fraction").admits(fraction) {
+                    return Err(ModelError::InvalidInput(
+                        "--active-fraction must be between 0 and 1".to_string(),
+                    ));
+                }
+                holders as f64 * fraction
+            }
+            _ => self.investor_count as f64,
+        };
+
+        Ok(ValuationInputs {
+            raw_forecast,
+            period: self.period,
+            risk_free_rate: self.risk_free_rate,
+            platform_risk_premium: self.platform_risk_premium,
+            platform_adjustment_factor: self.platform_adjustment / 100.0,
+            baseline_audience,
+            rpm,
+            incremental_rpm: self.incremental_rpm.unwrap_or(rpm),
+            investor_count,
+            lift_per_investor: self.lift_per_investor,
+            lift_ramp: self.lift_ramp,
+            fixed_lift: self.fixed_lift,
+            lift_acquisition_cost: self.lift_acquisition_cost,
+            baseline_only: self.baseline_only,
+            payout_ladder: self.payout_ladder.clone(),
+            monthly_breakdown: self.monthly_breakdown,
+            day_count: self.day_count,
+            compounding: self.compounding,
+            forecast_confidence: self.forecast_confidence,
+            lift_payout_days: self.lift_payout_days,
+            quarters: self.quarters,
+            quarterly_growth_rate: self.quarterly_growth_rate / 100.0,
+            two_stage_growth: self.stage1_quarters.map(|stage1_quarters| TwoStageGrowth {
+                stage1_quarters,
+                stage1_growth: self.stage1_growth.unwrap_or_default() / 100.0,
+                stage2_growth: self.stage2_growth.unwrap_or_default() / 100.0,
+            }),
+            reinvestment_rate: self.reinvestment_rate / 100.0,
+            revenue_floor: self.revenue_floor,
+            tax_rate: self.tax_rate / 100.0,
+            after_tax: self.after_tax,
+            strict: self.strict,
+            cap_at_face: self.cap_at_face,
+            lift_dominance_threshold: self.lift_dominance_threshold,
+            max_scenarios: self.max_scenarios,
+            max_payout_days: self.max_payout_days,
+            purchase_price: self.purchase_price,
+            market_price: self.market_price,
+            ownership_fraction,
+            token_supply: self.token_supply,
+            display_currency: self
+                .display_currency
+                .clone()
+                .zip(self.fx_rate)
+                .map(|(code, fx_rate)| DisplayCurrency { code, fx_rate }),
+            threads: self.threads,
+            discount_rate_overrides: self.discount_rates.clone(),
+            rate_matrix: self
+                .rate_matrix
+                .as_deref()
+                .map(load_rate_matrix)
+                .transpose()?,
+            rate_formula: self.rate_formula.clone(),
+            scenario_weights: ScenarioWeights {
+                payout: self.payout_weights.clone(),
+                volatility: self.volatility_weights.clone(),
+                lift: self.lift_weights.clone(),
+            },
+        })
+    }
+}
+
+/// Parse a percentage argument, accepting an optional trailing '%' sign
+/// so that `4.5` and `4.5%` are equivalent.
+pub fn parse_percentage(s: &str) -> Result<f64, String> {
+    let trimmed = s.trim();
+    let number = trimmed.strip_suffix('%').unwrap_or(trimmed).trim_end();
+    number
+        .parse::<f64>()
+        .map_err(|e| format!("invalid percentage '{}': {}", s, e))
+}
+
+/// Parse a percentage into a rate, refusing values outside the sane range
+pub fn parse_percent_rate(s: &str) -> Result<Rate, String> {
+    Rate::from_percent(parse_percentage(s)?)
+}
+
+/// Parse a decimal (e.g. 0.26 for 26%) into a rate, refusing values outside
+/// the sane range
+pub fn parse_decimal_rate(s: &str) -> Result<Rate, String> {
+    let decimal = s
+        .trim()
+        .parse::<f64>()
+        .map_err(|e| format!("invalid rate '{}': {}", s, e))?;
+    Rate::from_decimal(decimal)
+}
+
+/// Parse a three-letter ISO 4217 currency code, in either case, to upper case
+pub fn parse_currency_code(s: &str) -> Result<String, String> {
+    let code = s.trim();
+    if code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(code.to_ascii_uppercase())
+    } else {
+        Err(format!(
+            "invalid currency code '{}' (expected three letters, e.g. EUR)",
+            s
+        ))
+    }
+}
+
+/// Parse a locale name such as `en` or `de` into a number-formatting locale,
+/// falling back to `en` with a warning message for unknown names
+pub fn parse_locale(s: &str) -> (Locale, Option<String>) {
+    match Locale::from_name(s.trim()) {
+        Ok(locale) => (locale, None),
+        Err(_) => (
+            Locale::en,
+            Some(format!("Unsupported locale '{}'; using 'en' instead", s)),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_parse_percentage_accepts_trailing_percent_sign() {
+        assert_eq!(parse_percentage("4.5"), Ok(4.5));
+        assert_eq!(parse_percentage("4.5%"), Ok(4.5));
+        assert_eq!(parse_percentage("-9.1%"), Ok(-9.1));
+        assert!(parse_percentage("4.5%%").is_err());
+        assert!(parse_percentage("abc").is_err());
+    }
+
+    #[test]
+    fn test_percentage_flags_parse_with_percent_sign() {
+        let args =
+            Args::try_parse_from(["tokval", "-f", "1000", "-r", "5%", "-p", "12.5%"]).unwrap();
+        assert_eq!(args.risk_free_rate.as_decimal(), 0.05);
+        assert_eq!(args.platform_risk_premium.as_decimal(), 0.125);
+
+        let args = Args::try_parse_from(["tokval", "-f", "1000", "-a", "-5%"]).unwrap();
+        assert_eq!(args.platform_adjustment, -5.0);
+    }
+
+    #[test]
+    fn test_unknown_locale_falls_back_to_en() {
+        assert_eq!(parse_locale("de"), (Locale::de, None));
+        let (locale, warning) = parse_locale("xx-nowhere");
+        assert_eq!(locale, Locale::en);
+        assert!(warning.unwrap().contains("'xx-nowhere'"));
+    }
+
+    #[test]
+    fn test_forecast_units_scale_to_dollars() {
+        let raw_forecast = |units: &str| {
+            Args::try_parse_from(["tokval", "-f", "0.22", "--forecast-units", units])
+                .unwrap()
+                .valuation_inputs()
+                .unwrap()
+                .raw_forecast
+        };
+        assert_eq!(raw_forecast("millions"), 220_000.0);
+        assert_eq!(raw_forecast("thousands"), 220.0);
+        assert_eq!(raw_forecast("dollars"), 0.22);
+        assert!(
+            Args::try_parse_from(["tokval", "-f", "1", "--forecast-units", "billions"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_active_holders_replace_investor_count() {
+        let inputs = |argv: &[&str]| {
+            let mut full = vec!["tokval", "-f", "220000"];
+            full.extend_from_slice(argv);
+            Args::try_parse_from(full).unwrap().valuation_inputs()
+        };
+        let active = inputs(&["--total-holders", "1000", "--active-fraction", "0.3"]).unwrap();
+        let counted = inputs(&["--investor-count", "300"]).unwrap();
+        assert_relative_eq!(active.investor_count, 300.0);
+        for &lift in LiftScenario::all() {
+            let period_lift = |inputs: &ValuationInputs| {
+                lift.period_lift(
+                    inputs.investor_count,
+                    inputs.lift_per_investor,
+                    inputs.incremental_rpm,
+                    inputs.lift_ramp,
+                    inputs.period,
+                )
+            };
+            assert_relative_eq!(period_lift(&active), period_lift(&counted));
+        }
+
+        // A third of 1000 holders is an expected, fractional count
+        let third = inputs(&["--total-holders", "1000", "--active-fraction", "0.3333"]).unwrap();
+        assert_relative_eq!(third.investor_count, 333.3);
+
+        for fraction in ["--active-fraction=1.5", "--active-fraction=-0.1"] {
+            assert!(matches!(
+                inputs(&["--total-holders", "1000", fraction]),
+                Err(ModelError::InvalidInput(msg)) if msg.contains("--active-fraction")
+            ));
+        }
+        assert!(Args::try_parse_from(["tokval", "-f", "1", "--total-holders", "1000"]).is_err());
+    }
+
+    #[test]
+    fn test_token_position_becomes_ownership_fraction() {
+        let inputs = Args::try_parse_from([
+            "tokval",
+            "-f",
+            "1000",
+            "--tokens-held",
+            "250",
+            "--token-supply",
+            "10000",
+        ])
+        .unwrap()
+        .valuation_inputs()
+        .unwrap();
+        assert_eq!(inputs.ownership_fraction, Some(0.025));
+
+        let over = Args::try_parse_from([
+            "tokval",
+            "-f",
+            "1000",
+            "--tokens-held",
+            "20000",
+            "--token-supply",
+            "10000",
+        ])
+        .unwrap()
+        .valuation_inputs();
+        assert!(matches!(over, Err(ModelError::InvalidInput(_))));
+
+        assert!(
+            Args::try_parse_from([
+                "tokval",
+                "-f",
+                "1000",
+                "--ownership-fraction",
+                "0.1",
+                "--tokens-held",
+                "5",
+                "--token-supply",
+                "10",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_emit_central_is_shorthand_for_emit() {
+        let parse = |extra: &[&str]| {
+            let mut argv = vec!["tokval", "-f", "1000"];
+            argv.extend_from_slice(extra);
+            Args::try_parse_from(argv)
+        };
+        assert_eq!(parse(&[]).unwrap().emit_figure(), None);
+        assert_eq!(
+            parse(&["--emit-central"]).unwrap().emit_figure(),
+            Some(EmitFigure::Central)
+        );
+        assert_eq!(
+            parse(&["--emit", "max"]).unwrap().emit_figure(),
+            Some(EmitFigure::Max)
+        );
+        assert!(parse(&["--emit", "min", "--emit-central"]).is_err());
+        assert!(parse(&["--emit-central", "--format", "json"]).is_err());
+    }
+
+    #[test]
+    fn test_no_color_shades_heatmaps_with_blocks() {
+        let parse = |extra: &[&str]| {
+            let mut argv = vec!["tokval", "-f", "1000"];
+            argv.extend_from_slice(extra);
+            Args::try_parse_from(argv).unwrap()
+        };
+        assert_eq!(parse(&[]).heatmap_style(), None);
+        assert_eq!(
+            parse(&["--heatmap", "--no-color"]).heatmap_style(),
+            Some(HeatmapStyle::Blocks)
+        );
+        assert!(
+            Args::try_parse_from(["tokval", "-f", "1000", "--heatmap", "--format", "json"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_pretty_and_compact_override_the_terminal_default() {
+        let parse = |extra: &[&str]| {
+            let mut argv = vec!["tokval", "-f", "1000", "--format", "json"];
+            argv.extend_from_slice(extra);
+            Args::try_parse_from(argv)
+        };
+        assert!(parse(&["--pretty"]).unwrap().pretty_json());
+        assert!(!parse(&["--compact"]).unwrap().pretty_json());
+        // Archived reports are files, not terminal output
+        assert!(!parse(&["--archive", "reports"]).unwrap().pretty_json());
+        assert!(parse(&["--pretty", "--compact"]).is_err());
+    }
 }
@@ -0,0 +1,247 @@
+//! Layered configuration files for `--config`.
+//!
+//! A config file is a JSON object whose keys are the CLI flag names with
+//! underscores (e.g. `risk_free_rate`), as in a portfolio asset's `inputs`.
+//! Each file is a `PartialInputs` layer: keys it leaves out are unset and fall
+//! through to earlier layers, then to the CLI defaults. Layers merge left to
+//! right, and flags given on the command line override every layer. A later
+//! layer or a command-line flag also drops any layered input it cannot be
+//! combined with, e.g. `--pre-tax` drops a layered `after_tax`.
+
+use crate::cli::Args;
+use crate::error::{FileError, ModelError};
+use crate::json::JsonValue;
+use clap::{Command, CommandFactory};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// A value set by a config layer for one argument
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigValue {
+    /// A switch; `false` resets a switch turned on by an earlier layer
+    Flag(bool),
+    /// A value in the same text form the flag accepts
+    Value(String),
+}
+
+/// Inputs set by one or more config layers, keyed by argument id
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PartialInputs {
+    values: BTreeMap<String, ConfigValue>,
+}
+
+impl PartialInputs {
+    /// Load and merge config files in order, later files overriding earlier ones
+    pub fn load_layers(paths: &[PathBuf]) -> Result<Self, ModelError> {
+        paths.iter().try_fold(Self::default(), |merged, path| {
+            Ok(merged.merge(Self::load(path)?))
+        })
+    }
+
+    /// Load a single config file
+    pub fn load(path: &Path) -> Result<Self, ModelError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| ModelError::InputFile(format!("{}: {}", path.display(), e)))?;
+        let document = JsonValue::parse(&text).map_err(|e| FileError::from(e).in_file(path))?;
+        Self::from_json(&document).map_err(|e| e.in_file(path))
+    }
+
+    /// Read a layer from a JSON object of argument ids to values. Arrays are
+    /// joined with commas for the list-valued flags.
+    pub fn from_json(document: &JsonValue) -> Result<Self, FileError> {
+        let entries = document
+            .as_object()
+            .ok_or("config must be a JSON object of inputs")?;
+        let command = Args::command();
+
+        let mut values = BTreeMap::new();
+        for (key, value) in entries {
+            let known = command
+                .get_arguments()
+                .any(|arg| arg.get_id() == key.as_str() && arg.get_long().is_some());
+            if !known
+                || matches!(
+                    key.as_str(),
+                    "config" | "portfolio" | "scenario_profile" | "profile_dir" | "list_profiles"
+                )
+            {
+                return Err(FileError::for_field(key, "not a configurable input"));
+            }
+            let value = match value {
+                JsonValue::Bool(on) => ConfigValue::Flag(*on),
+                JsonValue::Array(items) => ConfigValue::Value(
+                    items
+                        .iter()
+                        .map(|item| scalar(key, item))
+                        .collect::<Result<Vec<_>, _>>()?
+                        .join(","),
+                ),
+                other => ConfigValue::Value(scalar(key, other)?),
+            };
+            values.insert(key.clone(), value);
+        }
+        Ok(Self { values })
+    }
+
+    /// Layer `overlay` on top of this one; every input it sets wins
+    pub fn merge(mut self, overlay: Self) -> Self {
+        let command = Args::command();
+        self.values
+            .retain(|id, _| !overlay.set_ids().any(|set| conflicts(&command, id, set)));
+        self.values.extend(overlay.values);
+        self
+    }
+
+    /// Ids of the inputs this layer passes on as flags, leaving out switches
+    /// it resets
+    fn set_ids(&self) -> impl Iterator<Item = &str> {
+        self.values
+            .iter()
+            .filter(|(_, value)| **value != ConfigValue::Flag(false))
+            .map(|(id, _)| id.as_str())
+    }
+
+    /// Ids of every input set by some layer
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    /// Command-line arguments for the set inputs, leaving out those for which
+    /// `overridden` holds so that flags given directly take precedence
+    pub fn to_args(&self, overridden: impl Fn(&str) -> bool) -> Vec<String> {
+        self.values
+            .iter()
+            .filter(|(id, _)| !overridden(id))
+            .filter_map(|(id, value)| {
+                let flag = format!("--{}", id.replace('_', "-"));
+                match value {
+                    ConfigValue::Flag(true) => Some(flag),
+                    ConfigValue::Flag(false) => None,
+                    ConfigValue::Value(text) => Some(format!("{}={}", flag, text)),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether the command refuses arguments `a` and `b` together, whichever of
+/// the two declares the conflict
+pub fn conflicts(command: &Command, a: &str, b: &str) -> bool {
+    let declares = |from: &str, to: &str| {
+        command
+            .get_arguments()
+            .find(|arg| arg.get_id() == from)
+            .is_some_and(|arg| {
+                command
+                    .get_arg_conflicts_with(arg)
+                    .iter()
+                    .any(|other| other.get_id() == to)
+            })
+    };
+    declares(a, b) || declares(b, a)
+}
+
+/// A number or string input in its flag text form
+fn scalar(key: &str, value: &JsonValue) -> Result<String, FileError> {
+    match value {
+        JsonValue::Number(n) => Ok(n.to_string()),
+        JsonValue::String(s) => Ok(s.clone()),
+        _ => Err(FileError::for_field(
+            key,
+            "must be a number, string, boolean or array of these",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn layer(text: &str) -> PartialInputs {
+        PartialInputs::from_json(&JsonValue::parse(text).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_later_layers_and_cli_flags_take_precedence() {
+        let base = layer(r#"{"forecast": 200000, "rpm": 12, "baseline_only": true}"#);
+        let sector = layer(r#"{"rpm": 15, "risk_free_rate": "4%", "baseline_only": false}"#);
+        let deal = layer(r#"{"risk_free_rate": 5, "payout_weights": [1, 2, 1]}"#);
+        let merged = base.merge(sector).merge(deal);
+
+        assert_eq!(
+            merged.to_args(|_| false),
+            [
+                "--forecast=200000",
+                "--payout-weights=1,2,1",
+                "--risk-free-rate=5",
+                "--rpm=15"
+            ]
+        );
+
+        let mut argv = vec!["tokval".to_string()];
+        argv.extend(merged.to_args(|id| id == "rpm"));
+        argv.push("--rpm=18".to_string());
+        let inputs = Args::try_parse_from(argv)
+            .unwrap()
+            .valuation_inputs()
+            .unwrap();
+        assert_eq!(inputs.raw_forecast, 200000.0);
+        assert_eq!(inputs.rpm, 18.0);
+        assert_eq!(inputs.risk_free_rate.as_decimal(), 0.05);
+        assert!(!inputs.baseline_only);
+        assert_eq!(inputs.scenario_weights.payout, Some(vec![1.0, 2.0, 1.0]));
+    }
+
+    #[test]
+    fn test_conflicting_inputs_give_way_to_later_layers_and_flags() {
+        let command = Args::command();
+        assert!(conflicts(&command, "after_tax", "pre_tax"));
+        assert!(conflicts(&command, "forecast_confidence", "discount_rates"));
+        assert!(!conflicts(&command, "rpm", "forecast"));
+
+        // A later layer drops an earlier input it conflicts with
+        let base = layer(r#"{"discount_rates": [0.2, 0.25, 0.3, 0.4], "rpm": 12}"#);
+        let overlay = layer(r#"{"rate_formula": "rf + 0.1"}"#);
+        assert_eq!(
+            base.merge(overlay).to_args(|_| false),
+            ["--rate-formula=rf + 0.1", "--rpm=12"]
+        );
+        // A reset switch drops nothing
+        let base = layer(r#"{"pre_tax": true}"#);
+        let overlay = layer(r#"{"after_tax": false}"#);
+        assert_eq!(base.merge(overlay).to_args(|_| false), ["--pre-tax"]);
+
+        // A flag given directly drops a layered input it conflicts with
+        let config = layer(r#"{"after_tax": true, "tax_rate": 20}"#);
+        let given = ["pre_tax"];
+        let mut argv = vec!["tokval".to_string(), "--forecast=100000".to_string()];
+        argv.extend(config.to_args(|id| {
+            given
+                .iter()
+                .any(|flag| *flag == id || conflicts(&command, id, flag))
+        }));
+        argv.push("--pre-tax".to_string());
+        let inputs = Args::try_parse_from(argv)
+            .unwrap()
+            .valuation_inputs()
+            .unwrap();
+        assert!(!inputs.after_tax);
+        assert_eq!(inputs.tax_rate, 0.2);
+    }
+
+    #[test]
+    fn test_unknown_and_nested_inputs_are_rejected() {
+        let parse = |text: &str| PartialInputs::from_json(&JsonValue::parse(text).unwrap());
+        assert_eq!(
+            parse(r#"{"forcast": 1}"#).unwrap_err().to_string(),
+            "field 'forcast': not a configurable input"
+        );
+        assert!(parse(r#"{"config": "base.json"}"#).is_err());
+        assert!(parse(r#"{"rpm": null}"#).is_err());
+        assert!(parse("[1, 2]").is_err());
+
+        let syntax = JsonValue::parse("{\n  \"rpm\": 12,\n  \"forecast\" 1\n}").unwrap_err();
+        assert!(FileError::from(syntax).to_string().starts_with("line 3: "));
+    }
+}
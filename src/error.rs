@@ -1,5 +1,7 @@
 //! Error types for the valuation model.
 
+use crate::model::ScenarioId;
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// Errors that can occur during valuation calculations
@@ -12,4 +14,126 @@ pub enum ModelError {
     /// Error during calculation
     #[error("Calculation error: {0}")]
     CalculationError(String),
+
+    /// A required scenario is missing from the valuation grid
+    #[error("Calculation error: no valuation for scenario {0}")]
+    ScenarioNotFound(ScenarioId),
+
+    /// Error reading or parsing an input file
+    #[error("Input file error: {0}")]
+    InputFile(String),
+
+    /// Malformed content in an input file, located to the line and field
+    #[error("Input file error: {}: {error}", path.display())]
+    ConfigError { path: PathBuf, error: FileError },
+
+    /// Error creating or writing an output file or directory
+    #[error("I/O error on {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    /// Computed values deviated from an `--assert-values` file
+    #[error("{failed} of {total} expected values deviated beyond tolerance")]
+    AssertionFailed { failed: usize, total: usize },
+
+    /// Several inputs failed validation at once
+    #[error("{} input problems:\n{}", .0.len(), format_violations(.0))]
+    Validation(Vec<ModelError>),
+}
+
+/// A problem in an input file's content, with the 1-based line and the field
+/// it was found in where they are known
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileError {
+    /// Line of the offending row
+    pub line: Option<usize>,
+    /// Column or key holding the offending value
+    pub field: Option<String>,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl FileError {
+    /// A problem on a line, in no single field
+    pub fn at_line(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            field: None,
+            message: message.into(),
+        }
+    }
+
+    /// A problem in one field of a line
+    pub fn at_field(line: usize, field: &str, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            field: Some(field.to_string()),
+            message: message.into(),
+        }
+    }
+
+    /// A problem in a field or key not tied to a line
+    pub fn for_field(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            field: Some(field.to_string()),
+            message: message.into(),
+        }
+    }
+
+    /// Attach the file the problem was found in
+    pub fn in_file(self, path: &std::path::Path) -> ModelError {
+        ModelError::ConfigError {
+            path: path.to_path_buf(),
+            error: self,
+        }
+    }
+}
+
+/// A problem with the file as a whole, such as a missing section
+impl From<String> for FileError {
+    fn from(message: String) -> Self {
+        Self {
+            line: None,
+            field: None,
+            message,
+        }
+    }
+}
+
+impl From<&str> for FileError {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+impl From<crate::json::JsonError> for FileError {
+    fn from(error: crate::json::JsonError) -> Self {
+        Self::at_line(
+            error.line,
+            format!("{} at column {}", error.message, error.column),
+        )
+    }
+}
+
+impl std::fmt::Display for FileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, &self.field) {
+            (Some(line), Some(field)) => write!(f, "line {}, field '{}': ", line, field)?,
+            (Some(line), None) => write!(f, "line {}: ", line)?,
+            (None, Some(field)) => write!(f, "field '{}': ", field)?,
+            (None, None) => {}
+        }
+        f.write_str(&self.message)
+    }
+}
+
+fn format_violations(errors: &[ModelError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("  - {}", e))
+        .collect::<Vec<_>>()
+        .join("\n")
 }
@@ -0,0 +1,216 @@
+//! Live risk-free rate lookup for `--fetch-risk-free-rate`.
+//!
+//! Compiled only with the `network` feature. The endpoint is queried with a
+//! plain HTTP/1.0 GET over `std::net`, so only `http://` URLs are supported;
+//! the response body must be the rate as a percentage, either bare (`5.25`,
+//! `5.25%`) or as a JSON object with a numeric `rate` key. Successful lookups
+//! are cached in the system temp directory for the rest of the UTC day.
+
+use crate::cli::parse_percentage;
+use crate::json::JsonValue;
+use crate::rate::Rate;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long to wait for the endpoint to connect and to respond
+pub const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The risk-free rate that was used and where it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedRate {
+    /// Risk-free rate
+    pub rate: Rate,
+    /// Description of the source, for the assumptions section
+    pub source: String,
+    /// Set when the fetch failed and the supplied rate was kept
+    pub warning: Option<String>,
+}
+
+/// Fetch the rate from `url`, reusing today's cached value when present,
+/// and fall back to `fallback` if the lookup fails
+pub fn resolve_risk_free_rate(url: &str, fallback: Rate) -> ResolvedRate {
+    match fetch_with_cache(url, FETCH_TIMEOUT, &cache_path(), today()) {
+        Ok((rate, cached)) => ResolvedRate {
+            rate,
+            source: if cached {
+                format!("fetched from {} (cached today)", url)
+            } else {
+                format!("fetched from {}", url)
+            },
+            warning: None,
+        },
+        Err(e) => ResolvedRate {
+            rate: fallback,
+            source: "--risk-free-rate (fetch failed)".to_string(),
+            warning: Some(format!(
+                "Could not fetch the risk-free rate from {}: {}; using {:.2}% instead",
+                url,
+                e,
+                fallback.as_percent()
+            )),
+        },
+    }
+}
+
+/// Return the rate and whether it came from the cache
+fn fetch_with_cache(
+    url: &str,
+    timeout: Duration,
+    cache: &Path,
+    day: u64,
+) -> Result<(Rate, bool), String> {
+    if let Some(rate) = read_cache(cache, url, day) {
+        return Ok((rate, true));
+    }
+    let rate = parse_rate(&http_get(url, timeout)?)?;
+    // A cache that cannot be written only costs a refetch next run
+    let _ = std::fs::write(cache, format!("{}\t{}\t{}\n", day, url, rate.as_percent()));
+    Ok((rate, false))
+}
+
+fn cache_path() -> PathBuf {
+    std::env::temp_dir().join("tokval-risk-free-rate.cache")
+}
+
+/// Days since the Unix epoch, so the cache expires at UTC midnight
+fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or(0)
+}
+
+fn read_cache(path: &Path, url: &str, day: u64) -> Option<Rate> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let mut fields = text.trim_end().splitn(3, '\t');
+    let cached_day: u64 = fields.next()?.parse().ok()?;
+    let cached_url = fields.next()?;
+    let rate = Rate::from_percent(fields.next()?.parse().ok()?).ok()?;
+    (cached_day == day && cached_url == url).then_some(rate)
+}
+
+/// Parse a response body holding a percentage, bare or as `{"rate": ...}`
+fn parse_rate(body: &str) -> Result<Rate, String> {
+    let body = body.trim();
+    let rate = match parse_percentage(body) {
+        Ok(rate) => rate,
+        Err(_) => JsonValue::parse(body)
+            .ok()
+            .and_then(|json| json.get("rate").and_then(JsonValue::as_f64))
+            .ok_or_else(|| "response is neither a number nor JSON with a 'rate'".to_string())?,
+    };
+    Rate::from_percent(rate)
+}
+
+/// Minimal blocking HTTP GET returning the body of a 200 response
+fn http_get(url: &str, timeout: Duration) -> Result<String, String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or("only http:// endpoints are supported")?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let address = if authority.contains(':') {
+        authority.to_string()
+    } else {
+        format!("{}:80", authority)
+    };
+    let socket = address
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("could not resolve {}", authority))?;
+
+    let mut stream = TcpStream::connect_timeout(&socket, timeout).map_err(|e| e.to_string())?;
+    stream
+        .set_read_timeout(Some(timeout))
+        .and_then(|()| stream.set_write_timeout(Some(timeout)))
+        .map_err(|e| e.to_string())?;
+    write!(
+        stream,
+        "GET {} HTTP/1.0\r\nHost: {}\r\nAccept: application/json, text/plain\r\nConnection: close\r\n\r\n",
+        path, authority
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .map_err(|e| e.to_string())?;
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or("malformed HTTP response")?;
+    let status = head
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or("");
+    if status != "200" {
+        return Err(format!("HTTP status {}", status));
+    }
+    Ok(body.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    fn serve_once(response: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/rate", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        url
+    }
+
+    #[test]
+    fn test_parse_rate_accepts_bare_and_json_bodies() {
+        let percent = |body: &str| parse_rate(body).map(Rate::as_percent);
+        assert_eq!(percent("5.25\n"), Ok(5.25));
+        assert_eq!(percent("5.25%"), Ok(5.25));
+        assert_eq!(percent(r#"{"rate": 4.8, "tenor": "3M"}"#), Ok(4.8));
+        assert!(parse_rate("<html>").is_err());
+        assert!(parse_rate("inf").is_err());
+        assert!(parse_rate("9000").is_err());
+    }
+
+    #[test]
+    fn test_fetch_caches_for_the_day() {
+        let cache = std::env::temp_dir().join(format!("tokval-test-{}.cache", std::process::id()));
+        let _ = std::fs::remove_file(&cache);
+        let url = serve_once("HTTP/1.0 200 OK\r\n\r\n{\"rate\": 5.1}");
+
+        let rate = Rate::from_percent(5.1).unwrap();
+        assert_eq!(
+            fetch_with_cache(&url, FETCH_TIMEOUT, &cache, 20_000),
+            Ok((rate, false))
+        );
+        // The server is gone; the same day is answered from the cache
+        let (cached, from_cache) = fetch_with_cache(&url, FETCH_TIMEOUT, &cache, 20_000).unwrap();
+        assert!(from_cache);
+        assert!((cached.as_decimal() - rate.as_decimal()).abs() < 1e-15);
+        // A new day misses the cache and the fetch fails
+        assert!(fetch_with_cache(&url, FETCH_TIMEOUT, &cache, 20_001).is_err());
+        let _ = std::fs::remove_file(&cache);
+    }
+
+    #[test]
+    fn test_failed_fetch_falls_back() {
+        let url = serve_once("HTTP/1.0 503 Service Unavailable\r\n\r\n");
+        let fallback = Rate::from_percent(4.5).unwrap();
+        let resolved = resolve_risk_free_rate(&url, fallback);
+        assert_eq!(resolved.rate, fallback);
+        assert!(resolved.warning.unwrap().contains("HTTP status 503"));
+
+        let resolved = resolve_risk_free_rate("https://example.com/rate", fallback);
+        assert!(resolved.warning.unwrap().contains("only http://"));
+    }
+}
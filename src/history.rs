@@ -0,0 +1,248 @@
+//! Monthly audience and RPM history for grounding the lift model.
+//!
+//! A history file is a CSV with a header row containing `month`, `audience`
+//! and `rpm` columns (in any order); each further row is one month, oldest
+//! first.
+
+use crate::error::{FileError, ModelError};
+use std::path::Path;
+
+/// How monthly history is collapsed into a single baseline figure
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum HistoryAggregation {
+    /// Average audience, with RPM weighted by each month's audience
+    #[default]
+    Mean,
+    /// Median audience and median RPM
+    Median,
+    /// The most recent month
+    Last,
+}
+
+impl std::str::FromStr for HistoryAggregation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "mean" => Ok(Self::Mean),
+            "median" => Ok(Self::Median),
+            "last" => Ok(Self::Last),
+            other => Err(format!(
+                "unknown history aggregation '{}' (expected mean, median or last)",
+                other
+            )),
+        }
+    }
+}
+
+/// One month of observed audience and monetization
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryMonth {
+    /// Month label as written in the file, e.g. "2024-01"
+    pub month: String,
+    /// Monthly unique audience
+    pub audience: f64,
+    /// Revenue per thousand impressions for the month
+    pub rpm: f64,
+}
+
+/// Monthly history in file order, guaranteed non-empty
+#[derive(Debug, Clone)]
+pub struct History {
+    months: Vec<HistoryMonth>,
+}
+
+impl History {
+    /// Baseline monthly audience under the chosen aggregation
+    pub fn baseline_audience(&self, aggregation: HistoryAggregation) -> f64 {
+        let audiences: Vec<f64> = self.months.iter().map(|m| m.audience).collect();
+        match aggregation {
+            HistoryAggregation::Mean => audiences.iter().sum::<f64>() / audiences.len() as f64,
+            HistoryAggregation::Median => median(audiences),
+            HistoryAggregation::Last => audiences[audiences.len() - 1],
+        }
+    }
+
+    /// Blended RPM under the chosen aggregation; the mean weights each month
+    /// by its audience so that larger months dominate as they do in revenue
+    pub fn blended_rpm(&self, aggregation: HistoryAggregation) -> f64 {
+        match aggregation {
+            HistoryAggregation::Mean => {
+                let audience: f64 = self.months.iter().map(|m| m.audience).sum();
+                if audience == 0.0 {
+                    0.0
+                } else {
+                    self.months.iter().map(|m| m.audience * m.rpm).sum::<f64>() / audience
+                }
+            }
+            HistoryAggregation::Median => median(self.months.iter().map(|m| m.rpm).collect()),
+            HistoryAggregation::Last => self.months[self.months.len() - 1].rpm,
+        }
+    }
+}
+
+fn median(mut values: Vec<f64>) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Load monthly history from a CSV file
+pub fn load_history(path: &Path) -> Result<History, ModelError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| ModelError::InputFile(format!("{}: {}", path.display(), e)))?;
+    parse_history(&text).map_err(|e| e.in_file(path))
+}
+
+/// Parse monthly history from CSV text, locating any problem by line and column
+pub fn parse_history(text: &str) -> Result<History, FileError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (header_index, header) = lines.next().ok_or("history file is empty")?;
+    let columns: Vec<String> = header
+        .split(',')
+        .map(|c| c.trim().to_ascii_lowercase())
+        .collect();
+    let index_of = |name: &str| {
+        columns.iter().position(|c| c == name).ok_or_else(|| {
+            FileError::at_field(
+                header_index + 1,
+                name,
+                "history file is missing this column",
+            )
+        })
+    };
+    let month_col = index_of("month")?;
+    let audience_col = index_of("audience")?;
+    let rpm_col = index_of("rpm")?;
+
+    let mut months = Vec::new();
+    for (line_index, line) in lines {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let field = |col: usize| {
+            fields.get(col).copied().ok_or_else(|| {
+                FileError::at_field(
+                    line_index + 1,
+                    &columns[col],
+                    format!("expected {} fields, got {}", columns.len(), fields.len()),
+                )
+            })
+        };
+        let number = |col: usize, name: &str| -> Result<f64, FileError> {
+            let value = field(col)?;
+            match value.parse::<f64>() {
+                Ok(n) if n.is_finite() && n >= 0.0 => Ok(n),
+                _ => Err(FileError::at_field(
+                    line_index + 1,
+                    name,
+                    format!("must be a non-negative number, got '{}'", value),
+                )),
+            }
+        };
+        months.push(HistoryMonth {
+            month: field(month_col)?.to_string(),
+            audience: number(audience_col, "audience")?,
+            rpm: number(rpm_col, "rpm")?,
+        });
+    }
+
+    if months.is_empty() {
+        return Err("history file has no data rows".into());
+    }
+    Ok(History { months })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const HISTORY: &str = "month,audience,rpm\n\
+        2024-01,900000,12\n\
+        2024-02,1000000,15\n\
+        2024-03,1400000,18\n";
+
+    #[test]
+    fn test_aggregations() {
+        let history = parse_history(HISTORY).unwrap();
+
+        assert_relative_eq!(
+            history.baseline_audience(HistoryAggregation::Mean),
+            1_100_000.0
+        );
+        assert_relative_eq!(
+            history.blended_rpm(HistoryAggregation::Mean),
+            (0.9 * 12.0 + 1.0 * 15.0 + 1.4 * 18.0) / 3.3,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            history.baseline_audience(HistoryAggregation::Median),
+            1_000_000.0
+        );
+        assert_relative_eq!(history.blended_rpm(HistoryAggregation::Median), 15.0);
+        assert_relative_eq!(
+            history.baseline_audience(HistoryAggregation::Last),
+            1_400_000.0
+        );
+        assert_relative_eq!(history.blended_rpm(HistoryAggregation::Last), 18.0);
+    }
+
+    #[test]
+    fn test_rejects_missing_columns_and_rows() {
+        assert!(
+            parse_history("month,audience\n2024-01,1000\n")
+                .unwrap_err()
+                .to_string()
+                .contains("'rpm'")
+        );
+        assert!(
+            parse_history("month,audience,rpm\n")
+                .unwrap_err()
+                .to_string()
+                .contains("no data rows")
+        );
+        assert!(
+            parse_history("rpm,month,audience\n15,2024-01,abc\n")
+                .unwrap_err()
+                .to_string()
+                .contains("line 2")
+        );
+    }
+
+    #[test]
+    fn test_errors_locate_the_offending_row_and_column() {
+        let mut text = String::from("month,audience,rpm\n");
+        for month in 1..=199 {
+            text.push_str(&format!("m{},1000,12\n", month));
+        }
+        text.push_str("m200,1000,twelve\n");
+        let error = parse_history(&text).unwrap_err();
+        assert_eq!(error.line, Some(201));
+        assert_eq!(error.field.as_deref(), Some("rpm"));
+        assert_eq!(
+            error.to_string(),
+            "line 201, field 'rpm': must be a non-negative number, got 'twelve'"
+        );
+
+        let error = parse_history("month,audience,rpm\n2024-01,1000\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, field 'rpm': expected 3 fields, got 2"
+        );
+
+        let error = parse_history("month,audience\n").unwrap_err();
+        assert!(
+            error
+                .in_file(Path::new("history.csv"))
+                .to_string()
+                .starts_with("Input file error: history.csv: line 1, field 'rpm'")
+        );
+    }
+}
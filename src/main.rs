@@ -3,39 +3,289 @@
 //! A command-line tool for calculating the fair present value of a publisher's
 //! tokenized quarterly ad revenue with comprehensive sensitivity analysis and reporting.
 
+mod archive;
+mod assertions;
+mod bounds;
 mod cli;
+mod config;
 mod error;
+#[cfg(feature = "network")]
+mod fetch;
+mod history;
+mod json;
 mod model;
+mod money;
+mod portfolio;
+mod profile;
+mod profiling;
+mod rate;
+mod rate_formula;
+mod rate_matrix;
+mod report;
 mod report_generator;
+mod round_trip;
 mod valuation;
 
-use clap::Parser;
-use cli::Args;
+use cli::{Args, parse_locale};
 use error::ModelError;
-use model::ValuationInputs;
-use report_generator::generate_full_report;
-use valuation::calculate_full_valuation;
+use json::JsonValue;
+use model::{InputSources, ModelWarning, OutputFormat, ValuationInputs};
+use profiling::Timer;
+use report_generator::{
+    ReportOptions, ScenarioLabels, format_warnings, generate_discount_curve_csv,
+    generate_full_report, generate_json_report, generate_lift_explanation, generate_long_csv,
+    generate_markdown_report, generate_portfolio_report, generate_prometheus, generate_raw_report,
+};
+use valuation::{
+    build_derivation_tree, calculate_validated_valuation, compare_compounding_methods,
+    compare_day_counts, compare_lift_models, scenario_count, validate_inputs, validate_percentiles,
+    value_lift_only,
+};
 
 fn main() -> Result<(), ModelError> {
-    let args = Args::parse();
-
-    let inputs = ValuationInputs {
-        raw_forecast: args.forecast,
-        risk_free_rate: args.risk_free_rate / 100.0,
-        platform_risk_premium: args.platform_risk_premium / 100.0,
-        platform_adjustment_factor: args.platform_adjustment / 100.0,
-        baseline_audience: args.baseline_audience,
-        rpm: args.rpm,
-        investor_count: args.investor_count,
-        lift_per_investor: args.lift_per_investor,
+    let (args, mut input_sources) = Args::parse_with_sources()?;
+    let mut timer = Timer::new(args.profile);
+
+    if args.print_bounds {
+        println!("{}", bounds::bounds_json());
+        return Ok(());
+    }
+
+    if args.list_profiles {
+        let dir = profile::profile_dir(args.profile_dir.as_deref())?;
+        for name in profile::list_profiles(&dir)? {
+            println!("{}", name);
+        }
+        return Ok(());
+    }
+
+    if let Some(path) = &args.portfolio {
+        let positions = timer.time("input validation", || {
+            let positions = portfolio::load_portfolio(path)?;
+            for position in &positions {
+                validate_inputs(&position.inputs)?;
+            }
+            Ok::<_, ModelError>(positions)
+        })?;
+        if args.dry_run {
+            if args.verbose {
+                println!("Portfolio of {} positions is valid.", positions.len());
+            }
+            return Ok(());
+        }
+        let result = timer.time("grid valuation", || portfolio::value_portfolio(&positions))?;
+        let report = timer.time("report generation", || generate_portfolio_report(&result));
+        println!("{}", report);
+        timer.report();
+        return Ok(());
+    }
+
+    let mut inputs = args.valuation_inputs()?;
+    let serialize = |json: &JsonValue| {
+        if args.pretty_json() {
+            json.pretty()
+        } else {
+            json.to_string()
+        }
+    };
+    let (risk_free_rate_source, rate_warning) =
+        apply_fetched_risk_free_rate(&args, &mut inputs, &mut input_sources);
+    let optional_locale = |name: Option<&str>| match name {
+        Some(name) => {
+            let (locale, warning) = parse_locale(name);
+            (Some(locale), warning)
+        }
+        None => (None, None),
     };
+    let (secondary_locale, locale_warning) = optional_locale(args.also_format_locale.as_deref());
+    let (audience_locale, audience_locale_warning) =
+        optional_locale(args.audience_locale.as_deref());
+    let options = ReportOptions {
+        redact: args.redact,
+        secondary_locale,
+        audience_locale,
+        labels: ScenarioLabels::new(
+            args.payout_labels.clone(),
+            args.volatility_labels.clone(),
+            args.lift_labels.clone(),
+        )?,
+        only_lift: args.only_lift,
+        input_sources: Some(input_sources),
+        percentiles: args.percentiles.clone(),
+        risk_free_rate_source,
+        show_step_deltas: args.show_step_deltas,
+        since_baseline: args.since_baseline,
+        show_formula: args.show_formula,
+        ranked: args.ranked_rows(),
+        heatmap: args.heatmap_style(),
+        rate_components_as: args.rate_components_as,
+        sections: args.sections.clone(),
+    };
+
+    timer.time("input validation", || validate_inputs(&inputs))?;
+    validate_percentiles(&options.percentiles)?;
+    if args.dry_run {
+        if args.verbose {
+            println!(
+                "Inputs are valid ({} scenario evaluations).",
+                scenario_count(&inputs)
+            );
+        }
+        return Ok(());
+    }
 
     // Calculate all valuation data
-    let report_data = calculate_full_valuation(&inputs)?;
+    let mut report_data =
+        timer.time("grid valuation", || calculate_validated_valuation(&inputs))?;
+    if args.compare_methods {
+        report_data.method_comparison =
+            Some(timer.time("method comparison", || compare_compounding_methods(&inputs))?);
+    }
+    if args.compare_payout_conventions {
+        report_data.day_count_comparison =
+            Some(timer.time("day-count comparison", || compare_day_counts(&inputs))?);
+    }
+    if args.compare_lift_models {
+        report_data.lift_model_comparison =
+            Some(timer.time("lift model comparison", || compare_lift_models(&inputs))?);
+    }
+    if args.value_lift_only {
+        report_data.lift_option =
+            Some(timer.time("lift option valuation", || value_lift_only(&report_data))?);
+    }
+    report_data.warnings.extend(
+        [rate_warning, locale_warning, audience_locale_warning]
+            .into_iter()
+            .flatten()
+            .map(ModelWarning::Note),
+    );
+    for warning in format_warnings(&report_data, &options) {
+        eprintln!("Warning: {}", warning);
+    }
+
+    if let Some(path) = &args.assert_values {
+        let expectations = assertions::load_expectations(path)?;
+        let mismatches = assertions::check_values(&report_data, &expectations);
+        for mismatch in &mismatches {
+            eprintln!("Mismatch: {}", mismatch);
+        }
+        if !mismatches.is_empty() {
+            return Err(ModelError::AssertionFailed {
+                failed: mismatches.len(),
+                total: expectations.len(),
+            });
+        }
+        println!("All {} expected values matched.", expectations.len());
+        timer.report();
+        return Ok(());
+    }
+
+    if args.round_trip_check {
+        let result = timer.time("round-trip check", || {
+            round_trip::check_json_round_trip(&report_data)
+        });
+        for mismatch in &result.mismatches {
+            eprintln!("Round-trip mismatch: {}", mismatch);
+        }
+        if !result.mismatches.is_empty() {
+            return Err(ModelError::AssertionFailed {
+                failed: result.mismatches.len(),
+                total: result.checked,
+            });
+        }
+        println!("All {} JSON report values round-tripped.", result.checked);
+        timer.report();
+        return Ok(());
+    }
+
+    if let Some(figure) = args.emit_figure() {
+        println!("{}", figure.value(&report_data.summary));
+        timer.report();
+        return Ok(());
+    }
 
-    // Generate and print the full report
-    let report = generate_full_report(&report_data);
-    println!("{}", report);
+    if args.explain_json {
+        let tree = timer.time("report generation", || {
+            JsonValue::from(&build_derivation_tree(&report_data))
+        });
+        println!("{}", serialize(&tree));
+        timer.report();
+        return Ok(());
+    }
+
+    if args.export_curve {
+        let csv = timer.time("report generation", || {
+            generate_discount_curve_csv(&report_data, args.curve_step)
+        })?;
+        print!("{}", csv);
+        timer.report();
+        return Ok(());
+    }
+
+    if args.explain_lift {
+        println!("{}", generate_lift_explanation(&report_data, &options));
+        timer.report();
+        return Ok(());
+    }
+
+    // Generate the report in the requested format; only CSV and Prometheus
+    // text end with their own newline
+    let report = timer.time("report generation", || match args.format {
+        OutputFormat::CsvLong => generate_long_csv(&report_data),
+        OutputFormat::Prometheus => {
+            let asset = args
+                .asset_id
+                .clone()
+                .unwrap_or_else(|| archive::input_fingerprint(&inputs));
+            generate_prometheus(&report_data, &[("asset", &asset)])
+        }
+        OutputFormat::Json => format!("{}\n", serialize(&generate_json_report(&report_data))),
+        _ if args.raw_numbers => format!("{}\n", generate_raw_report(&report_data)),
+        OutputFormat::Markdown => format!("{}\n", generate_markdown_report(&report_data, &options)),
+        OutputFormat::Text => format!("{}\n", generate_full_report(&report_data, &options)),
+    });
+
+    match &args.archive {
+        Some(dir) => {
+            let path = archive::write_archive(dir, &inputs, args.format.extension(), &report)?;
+            println!("Report written to {}", path.display());
+        }
+        None => print!("{}", report),
+    }
+
+    timer.report();
 
     Ok(())
 }
+
+/// Replace the risk-free rate with a live lookup when `--fetch-risk-free-rate`
+/// is given, returning the rate's source and any fallback warning
+#[cfg(feature = "network")]
+fn apply_fetched_risk_free_rate(
+    args: &Args,
+    inputs: &mut ValuationInputs,
+    input_sources: &mut InputSources,
+) -> (Option<String>, Option<String>) {
+    let Some(url) = args
+        .risk_free_rate_url
+        .as_deref()
+        .filter(|_| args.fetch_risk_free_rate)
+    else {
+        return (None, None);
+    };
+    let resolved = fetch::resolve_risk_free_rate(url, args.risk_free_rate);
+    if resolved.warning.is_none() {
+        inputs.risk_free_rate = resolved.rate;
+        input_sources.mark_fetched("risk_free_rate");
+    }
+    (Some(resolved.source), resolved.warning)
+}
+
+#[cfg(not(feature = "network"))]
+fn apply_fetched_risk_free_rate(
+    _args: &Args,
+    _inputs: &mut ValuationInputs,
+    _input_sources: &mut InputSources,
+) -> (Option<String>, Option<String>) {
+    (None, None)
+}
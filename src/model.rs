@@ -1,9 +1,13 @@
 //! Core data models and types for the valuation system.
 
-use std::collections::HashMap;
+use crate::json::JsonValue;
+use crate::rate::Rate;
+use crate::rate_formula::RateFormula;
+use crate::rate_matrix::RateMatrix;
+use std::collections::{HashMap, HashSet};
 
 /// Payout timing scenarios representing different payment delays
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PayoutScenario {
     /// Payment after 60 days
     Day60,
@@ -11,30 +15,29 @@ pub enum PayoutScenario {
     Day90,
     /// Payment after 120 days
     Day120,
+    /// Payment in tranches according to a user-supplied `PayoutLadder`
+    Laddered,
 }
 
 impl PayoutScenario {
-    /// Returns all payout scenarios in order.
+    /// Returns all single-payment (bullet) scenarios in order.
     pub fn all() -> &'static [Self] {
         &[Self::Day60, Self::Day90, Self::Day120]
     }
 
-    /// Get the time period in years for DCF calculation. Assumes a 365-day year.
-    pub fn years(&self) -> f64 {
-        match self {
-            Self::Day60 => 60.0 / 365.0,
-            Self::Day90 => 90.0 / 365.0,
-            Self::Day120 => 120.0 / 365.0,
-        }
+    /// Get the time period in years for DCF calculation under the given day-count convention.
+    /// Returns `None` for laddered payouts, whose timing lives in the `PayoutLadder`.
+    pub fn years(&self, day_count: DayCount) -> Option<f64> {
+        self.days().map(|days| day_count.year_fraction(days))
     }
 
-    /// Get days as integer
-    #[allow(dead_code)]
-    pub fn days(&self) -> u32 {
+    /// Get days as integer, `None` for laddered payouts
+    pub fn days(&self) -> Option<u32> {
         match self {
-            Self::Day60 => 60,
-            Self::Day90 => 90,
-            Self::Day120 => 120,
+            Self::Day60 => Some(60),
+            Self::Day90 => Some(90),
+            Self::Day120 => Some(120),
+            Self::Laddered => None,
         }
     }
 }
@@ -46,12 +49,421 @@ impl std::fmt::Display for PayoutScenario {
             Self::Day60 => write!(f, "60 Days"),
             Self::Day90 => write!(f, "90 Days"),
             Self::Day120 => write!(f, "120 Days"),
+            Self::Laddered => write!(f, "Laddered"),
+        }
+    }
+}
+
+/// Day-count conventions for converting payout delays into year fractions
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum DayCount {
+    /// Actual days over a 365-day year
+    #[default]
+    Actual365,
+    /// Actual days over a 360-day year
+    Actual360,
+    /// 30-day months over a 360-day year
+    Thirty360,
+}
+
+impl DayCount {
+    /// Returns all day-count conventions in order
+    pub fn all() -> &'static [Self] {
+        &[Self::Actual365, Self::Actual360, Self::Thirty360]
+    }
+
+    /// Convert a payout delay in days into a year fraction.
+    ///
+    /// The model has no calendar dates, so 30/360 counts every 30 days of
+    /// delay as one 30-day month; the payouts are whole months, so a 90-day
+    /// payout is 3 months, or 0.25 years.
+    pub fn year_fraction(&self, days: u32) -> f64 {
+        match self {
+            Self::Actual365 => days as f64 / 365.0,
+            Self::Actual360 => days as f64 / 360.0,
+            Self::Thirty360 => {
+                let months = days as f64 / 30.0;
+                (months * 30.0) / 360.0
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for DayCount {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "actual365" | "act/365" | "actual/365" => Ok(Self::Actual365),
+            "actual360" | "act/360" | "actual/360" => Ok(Self::Actual360),
+            "thirty360" | "30/360" => Ok(Self::Thirty360),
+            other => Err(format!(
+                "unknown day-count convention '{}' (expected actual365, actual360 or thirty360)",
+                other
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for DayCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Actual365 => write!(f, "Actual/365"),
+            Self::Actual360 => write!(f, "Actual/360"),
+            Self::Thirty360 => write!(f, "30/360"),
+        }
+    }
+}
+
+/// How much the publisher's forecast can be trusted, setting how far the
+/// volatility premiums spread around the typical scenario's
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ForecastConfidence {
+    /// Premiums pulled halfway toward the typical premium
+    High,
+    /// The standard premiums
+    #[default]
+    Medium,
+    /// Premiums pushed half as far again from the typical premium
+    Low,
+}
+
+impl ForecastConfidence {
+    /// Multiplier on each premium's distance from the typical premium
+    pub fn spread(&self) -> f64 {
+        match self {
+            Self::High => 0.5,
+            Self::Medium => 1.0,
+            Self::Low => 1.5,
         }
     }
+
+    /// Volatility premium of `volatility` at this confidence level
+    pub fn premium(&self, volatility: VolatilityScenario) -> Rate {
+        let typical = VolatilityScenario::Typical.premium().as_decimal();
+        let premium = typical + (volatility.premium().as_decimal() - typical) * self.spread();
+        // Every spread keeps the built-in premiums well inside the rate range
+        Rate::from_decimal(premium).unwrap_or_else(|_| volatility.premium())
+    }
+}
+
+impl std::str::FromStr for ForecastConfidence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "high" => Ok(Self::High),
+            "medium" => Ok(Self::Medium),
+            "low" => Ok(Self::Low),
+            other => Err(format!(
+                "unknown forecast confidence '{}' (expected high, medium or low)",
+                other
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ForecastConfidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::High => write!(f, "High"),
+            Self::Medium => write!(f, "Medium"),
+            Self::Low => write!(f, "Low"),
+        }
+    }
+}
+
+/// Compounding conventions for discounting a cash flow over time
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Compounding {
+    /// Annual compounding: PV = CF / (1 + r)^t
+    #[default]
+    Discrete,
+    /// Continuous compounding: PV = CF * e^(-r * t)
+    Continuous,
+}
+
+impl Compounding {
+    /// Fraction of a cash flow retained after discounting at `rate` for `years`
+    pub fn discount_factor(&self, rate: f64, years: f64) -> f64 {
+        match self {
+            Self::Discrete => (1.0 + rate).powf(-years),
+            Self::Continuous => (-rate * years).exp(),
+        }
+    }
+
+    /// Holding period in years at which `rate` produces the given discount factor
+    pub fn implied_years(&self, rate: f64, factor: f64) -> f64 {
+        let growth = match self {
+            Self::Discrete => (1.0 + rate).ln(),
+            Self::Continuous => rate,
+        };
+        if growth == 0.0 || factor <= 0.0 {
+            0.0
+        } else {
+            -factor.ln() / growth
+        }
+    }
+}
+
+impl std::str::FromStr for Compounding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "discrete" | "annual" => Ok(Self::Discrete),
+            "continuous" => Ok(Self::Continuous),
+            other => Err(format!(
+                "unknown compounding convention '{}' (expected discrete or continuous)",
+                other
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for Compounding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Discrete => write!(f, "Discrete (annual)"),
+            Self::Continuous => write!(f, "Continuous"),
+        }
+    }
+}
+
+/// How the lift audience builds up over the quarter
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum LiftRamp {
+    /// The full lift audience is present from the first month
+    #[default]
+    Instant,
+    /// The lift audience grows linearly, reaching full size in the third month
+    Linear,
+}
+
+impl LiftRamp {
+    /// Share of the end-state lift audience present in each month of the quarter
+    pub fn monthly_weights(&self) -> [f64; 3] {
+        match self {
+            Self::Instant => [1.0, 1.0, 1.0],
+            Self::Linear => [1.0 / 3.0, 2.0 / 3.0, 1.0],
+        }
+    }
+}
+
+impl std::str::FromStr for LiftRamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "instant" => Ok(Self::Instant),
+            "linear" => Ok(Self::Linear),
+            other => Err(format!(
+                "unknown lift ramp '{}' (expected instant or linear)",
+                other
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for LiftRamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Instant => write!(f, "Instant (full audience all quarter)"),
+            Self::Linear => write!(f, "Linear (1/3, 2/3, then full audience by month)"),
+        }
+    }
+}
+
+/// Length of the revenue period the forecast, lift and horizon are counted in
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum RevenuePeriod {
+    /// 52 periods a year
+    Weekly,
+    /// 12 periods a year
+    Monthly,
+    /// 4 periods a year
+    #[default]
+    Quarterly,
+}
+
+impl RevenuePeriod {
+    /// Number of periods in a year
+    pub fn per_year(&self) -> u32 {
+        match self {
+            Self::Weekly => 52,
+            Self::Monthly => 12,
+            Self::Quarterly => 4,
+        }
+    }
+
+    /// Length of one period in months
+    pub fn months(&self) -> f64 {
+        12.0 / self.per_year() as f64
+    }
+
+    /// Singular noun for one period, e.g. "quarter"
+    pub fn noun(&self) -> &'static str {
+        match self {
+            Self::Weekly => "week",
+            Self::Monthly => "month",
+            Self::Quarterly => "quarter",
+        }
+    }
+}
+
+impl std::str::FromStr for RevenuePeriod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "weekly" => Ok(Self::Weekly),
+            "monthly" => Ok(Self::Monthly),
+            "quarterly" => Ok(Self::Quarterly),
+            other => Err(format!(
+                "unknown revenue period '{}' (expected weekly, monthly or quarterly)",
+                other
+            )),
+        }
+    }
+}
+
+/// Scale the `--forecast` figure is entered in
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum ForecastUnits {
+    #[default]
+    Dollars,
+    Thousands,
+    Millions,
+}
+
+impl ForecastUnits {
+    /// Dollars per unit of the entered forecast
+    pub fn factor(&self) -> f64 {
+        match self {
+            Self::Dollars => 1.0,
+            Self::Thousands => 1_000.0,
+            Self::Millions => 1_000_000.0,
+        }
+    }
+}
+
+impl std::str::FromStr for ForecastUnits {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "dollars" => Ok(Self::Dollars),
+            "thousands" => Ok(Self::Thousands),
+            "millions" => Ok(Self::Millions),
+            other => Err(format!(
+                "unknown forecast units '{}' (expected dollars, thousands or millions)",
+                other
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for RevenuePeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Weekly => write!(f, "Weekly"),
+            Self::Monthly => write!(f, "Monthly"),
+            Self::Quarterly => write!(f, "Quarterly"),
+        }
+    }
+}
+
+/// A single tranche of a laddered payout schedule
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PayoutTranche {
+    /// Days after the quarter at which this tranche is paid
+    pub days: u32,
+    /// Fraction of the quarterly cash flow paid in this tranche
+    pub fraction: f64,
+}
+
+impl PayoutTranche {
+    /// Get the time period in years for DCF calculation under the given day-count convention.
+    pub fn years(&self, day_count: DayCount) -> f64 {
+        day_count.year_fraction(self.days)
+    }
+}
+
+/// A schedule of payout tranches replacing the single-payment assumption.
+///
+/// Parsed from `DAYS:FRACTION` pairs separated by commas, e.g. `30:0.3,60:0.4,90:0.3`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayoutLadder {
+    /// Tranches in the order supplied
+    pub tranches: Vec<PayoutTranche>,
+}
+
+impl PayoutLadder {
+    /// Sum of all tranche fractions; should be 1.0 for a complete schedule
+    pub fn total_fraction(&self) -> f64 {
+        self.tranches.iter().map(|t| t.fraction).sum()
+    }
+
+    /// The ladder with tranches sharing a payout day combined into the first
+    /// of them, in first-seen order, and the days that were repeated
+    pub fn merge_repeated_days(&self) -> (PayoutLadder, Vec<u32>) {
+        let mut tranches: Vec<PayoutTranche> = Vec::with_capacity(self.tranches.len());
+        let mut repeated = Vec::new();
+        for tranche in &self.tranches {
+            match tranches.iter_mut().find(|t| t.days == tranche.days) {
+                Some(first) => {
+                    first.fraction += tranche.fraction;
+                    if !repeated.contains(&tranche.days) {
+                        repeated.push(tranche.days);
+                    }
+                }
+                None => tranches.push(*tranche),
+            }
+        }
+        (PayoutLadder { tranches }, repeated)
+    }
+}
+
+impl std::str::FromStr for PayoutLadder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tranches = s
+            .split(',')
+            .map(|pair| {
+                let (days, fraction) = pair
+                    .trim()
+                    .split_once(':')
+                    .ok_or_else(|| format!("expected DAYS:FRACTION, got '{}'", pair))?;
+                let days = days
+                    .trim()
+                    .parse::<u32>()
+                    .map_err(|e| format!("invalid day offset '{}': {}", days, e))?;
+                let fraction = fraction
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|e| format!("invalid fraction '{}': {}", fraction, e))?;
+                Ok(PayoutTranche { days, fraction })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(PayoutLadder { tranches })
+    }
+}
+
+impl std::fmt::Display for PayoutLadder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self
+            .tranches
+            .iter()
+            .map(|t| format!("{}d: {:.0}%", t.days, t.fraction * 100.0))
+            .collect();
+        write!(f, "{}", parts.join(", "))
+    }
 }
 
 /// Market volatility scenarios affecting risk premium.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum VolatilityScenario {
     /// Low market volatility
     Low,
@@ -70,19 +482,14 @@ impl VolatilityScenario {
     }
 
     /// Get the volatility premium for this scenario.
-    pub fn premium(&self) -> f64 {
+    pub fn premium(&self) -> Rate {
         match self {
-            Self::Low => 0.05,     // 5%
-            Self::Typical => 0.10, // 10%
-            Self::High => 0.20,    // 20%
-            Self::Extreme => 0.30, // 30%
+            Self::Low => Rate::constant(0.05),     // 5%
+            Self::Typical => Rate::constant(0.10), // 10%
+            Self::High => Rate::constant(0.20),    // 20%
+            Self::Extreme => Rate::constant(0.30), // 30%
         }
     }
-
-    /// Get the volatility percentage for display.
-    pub fn percentage(&self) -> f64 {
-        self.premium() * 100.0
-    }
 }
 
 /// Implement Display for VolatilityScenario.
@@ -98,7 +505,7 @@ impl std::fmt::Display for VolatilityScenario {
 }
 
 /// Investor participation lift scenarios, now representing an activation factor.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum LiftScenario {
     /// Low investor activation (e.g., 50% of potential)
     Low,
@@ -124,14 +531,50 @@ impl LiftScenario {
     }
 
     /// Calculate the additional monthly audience based on investor drivers.
-    pub fn additional_audience(&self, investor_count: u32, lift_per_investor: f64) -> f64 {
-        (investor_count as f64) * lift_per_investor * self.activation_factor()
+    /// The investor count is an expected number of active investors, so it
+    /// may be fractional.
+    pub fn additional_audience(&self, investor_count: f64, lift_per_investor: f64) -> f64 {
+        investor_count * lift_per_investor * self.activation_factor()
     }
 
-    /// Get the quarterly lift dollar amount (audience * RPM * 3 months)
-    pub fn quarterly_lift(&self, investor_count: u32, lift_per_investor: f64, rpm: f64) -> f64 {
+    /// Get the lift dollar amount for one revenue period: the end-state
+    /// audience * incremental RPM, summed over a quarter's three months as
+    /// weighted by `ramp` and scaled to the period's length in months.
+    ///
+    /// Lift audience is monetized at its own RPM, which may sit below the
+    /// baseline audience's RPM.
+    pub fn period_lift(
+        &self,
+        investor_count: f64,
+        lift_per_investor: f64,
+        incremental_rpm: f64,
+        ramp: LiftRamp,
+        period: RevenuePeriod,
+    ) -> f64 {
         let audience = self.additional_audience(investor_count, lift_per_investor);
-        audience * (rpm / 1000.0) * 3.0
+        let monthly_revenue = audience * (incremental_rpm / 1000.0);
+        let quarter: f64 = ramp
+            .monthly_weights()
+            .iter()
+            .map(|weight| monthly_revenue * weight)
+            .sum();
+        quarter * (period.months() / 3.0)
+    }
+}
+
+impl std::str::FromStr for LiftScenario {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "medium" => Ok(Self::Medium),
+            "high" => Ok(Self::High),
+            other => Err(format!(
+                "unknown lift scenario '{}' (expected low, medium or high)",
+                other
+            )),
+        }
     }
 }
 
@@ -145,56 +588,691 @@ impl std::fmt::Display for LiftScenario {
     }
 }
 
+/// Currency the monetary outputs are shown in, converted from the USD
+/// valuation at a fixed exchange rate
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayCurrency {
+    /// ISO 4217 code, e.g. `EUR`
+    pub code: String,
+    /// Units of the display currency per US dollar
+    pub fx_rate: f64,
+}
+
+impl DisplayCurrency {
+    /// Convert a USD amount into the display currency
+    pub fn convert(&self, amount: f64) -> f64 {
+        amount * self.fx_rate
+    }
+
+    /// Prefix for amounts: the symbol of a common currency, otherwise the
+    /// code followed by a space
+    pub fn symbol(&self) -> String {
+        match self.code.as_str() {
+            "USD" => "$".to_string(),
+            "EUR" => "€".to_string(),
+            "GBP" => "£".to_string(),
+            "JPY" => "¥".to_string(),
+            code => format!("{} ", code),
+        }
+    }
+}
+
+/// Cash flow growth that changes rate once: `stage1_growth` for the first
+/// `stage1_quarters` periods, `stage2_growth` for every period after
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TwoStageGrowth {
+    /// Periods grown at the stage-1 rate
+    pub stage1_quarters: u32,
+    /// Per-period growth rate of the first stage (as decimal)
+    pub stage1_growth: f64,
+    /// Per-period growth rate once the first stage ends (as decimal)
+    pub stage2_growth: f64,
+}
+
+impl TwoStageGrowth {
+    /// Cumulative growth factor applied to period `quarter` (0-based)
+    pub fn factor(&self, quarter: u32) -> f64 {
+        let stage1 = quarter.min(self.stage1_quarters);
+        let stage2 = quarter - stage1;
+        (1.0 + self.stage1_growth).powi(stage1 as i32)
+            * (1.0 + self.stage2_growth).powi(stage2 as i32)
+    }
+
+    /// Whether the stage-2 rate grows any of the first `quarters` periods
+    pub fn reaches_stage2(&self, quarters: u32) -> bool {
+        quarters.saturating_sub(1) > self.stage1_quarters
+    }
+}
+
 /// Input parameters for valuation calculations
 #[derive(Debug, Clone)]
 pub struct ValuationInputs {
-    // Publisher's raw quarterly revenue forecast
+    // Publisher's raw revenue forecast for one period
     pub raw_forecast: f64,
-    /// Risk-free rate (as decimal, e.g., 0.045 for 4.5%)
-    pub risk_free_rate: f64,
-    /// Platform risk premium (as decimal)
-    pub platform_risk_premium: f64,
+    /// Length of the revenue period the forecast, lift and horizon are counted in
+    pub period: RevenuePeriod,
+    /// Risk-free rate
+    pub risk_free_rate: Rate,
+    /// Platform risk premium
+    pub platform_risk_premium: Rate,
     /// Platform adjustment factor (as decimal, negative for reduction)
     pub platform_adjustment_factor: f64,
     /// Baseline monthly audience for lift calculations
     pub baseline_audience: f64,
     /// Revenue per thousand impressions (RPM) for lift calculations
     pub rpm: f64,
-    /// Investor count for lift calculations
-    pub investor_count: u32,
+    /// RPM applied to incremental (lift) audience, defaulting to `rpm`
+    pub incremental_rpm: f64,
+    /// Effective (active) investor count for lift calculations
+    pub investor_count: f64,
     /// Lift per investor for lift calculations
     pub lift_per_investor: f64,
+    /// How the lift audience builds up over the quarter
+    pub lift_ramp: LiftRamp,
+    /// Fixed per-period lift at medium activation, replacing the investor-driver
+    /// model when set; other lift scenarios scale it by their activation factor
+    pub fixed_lift: Option<f64>,
+    /// Cost of acquiring the modeled lift audience, for the payback period
+    pub lift_acquisition_cost: Option<f64>,
+    /// Skip the lift scenarios and value the baseline revenue only
+    pub baseline_only: bool,
+    /// Optional tranche schedule replacing the single-payment payout scenarios
+    pub payout_ladder: Option<PayoutLadder>,
+    /// Split each quarter into three monthly cash flows, 30 days apart and the
+    /// last on the payout date, instead of one bullet payment
+    pub monthly_breakdown: bool,
+    /// Day-count convention for converting payout delays into years
+    pub day_count: DayCount,
+    /// Days until lift revenue is paid, when it settles apart from the baseline
+    pub lift_payout_days: Option<u32>,
+    /// Compounding convention used when discounting cash flows
+    pub compounding: Compounding,
+    /// Forecast confidence, widening or narrowing the volatility premiums
+    pub forecast_confidence: ForecastConfidence,
+    /// Number of consecutive periods of cash flow to value (1 = a single period)
+    pub quarters: u32,
+    /// Period-over-period cash flow growth rate (as decimal)
+    pub quarterly_growth_rate: f64,
+    /// Staged growth replacing `quarterly_growth_rate`, when given
+    pub two_stage_growth: Option<TwoStageGrowth>,
+    /// Share of each period's lift revenue reinvested into audience (as decimal)
+    pub reinvestment_rate: f64,
+    /// Contractual minimum revenue per period, applied before growth and discounting
+    pub revenue_floor: Option<f64>,
+    /// Tax rate applied to every cash flow before discounting (as decimal)
+    pub tax_rate: f64,
+    /// Report after-tax rather than pre-tax values as the headline figures
+    pub after_tax: bool,
+    /// Treat model guard conditions as errors rather than clamping with a warning
+    pub strict: bool,
+    /// Cap each present value at its undiscounted face value
+    pub cap_at_face: bool,
+    /// Quarterly lift above this multiple of the adjusted baseline draws a warning
+    pub lift_dominance_threshold: f64,
+    /// Upper bound on scenario evaluations before the grid is computed
+    pub max_scenarios: usize,
+    /// Longest supplied payout delay accepted, in days
+    pub max_payout_days: u32,
+    /// Price paid for the token pool, for value-per-dollar comparisons
+    pub purchase_price: Option<f64>,
+    /// Observed market price of the token pool, to back out the implied stress level
+    pub market_price: Option<f64>,
+    /// Investor's share of the token pool (0-1], scaling values to their position
+    pub ownership_fraction: Option<f64>,
+    /// Total tokens in the pool, for per-token values
+    pub token_supply: Option<f64>,
+    /// Currency to show monetary outputs in, `None` for USD
+    pub display_currency: Option<DisplayCurrency>,
+    /// Worker threads for the scenario grid, `None` for every available core
+    pub threads: Option<usize>,
+    /// Total discount rates supplied directly per volatility scenario, in
+    /// `VolatilityScenario::all()` order, bypassing the rate components
+    pub discount_rate_overrides: Option<Vec<Rate>>,
+    /// Total discount rate per (payout, volatility) cell, used verbatim in
+    /// place of the composed rates
+    pub rate_matrix: Option<RateMatrix>,
+    /// Formula giving each volatility scenario's total rate from its components
+    pub rate_formula: Option<RateFormula>,
+    /// Relative likelihood of each scenario, for the weighted distribution
+    pub scenario_weights: ScenarioWeights,
+}
+
+impl ValuationInputs {
+    /// Payout scenarios valued for these inputs: the ladder when one is
+    /// supplied, otherwise the standard 60/90/120-day bullets.
+    pub fn payout_scenarios(&self) -> &'static [PayoutScenario] {
+        if self.payout_ladder.is_some() {
+            &[PayoutScenario::Laddered]
+        } else {
+            PayoutScenario::all()
+        }
+    }
+
+    /// Factor taking pre-tax cash flows and values to the headline figures:
+    /// the after-tax share when `after_tax` is set, otherwise 1
+    pub fn headline_factor(&self) -> f64 {
+        if self.after_tax {
+            1.0 - self.tax_rate
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Relative likelihood of the scenarios along each grid dimension.
+///
+/// Each list, when present, supplies one non-negative weight per scenario in
+/// `all()` order; unset lists weight every scenario equally. The dimensions
+/// are treated as independent, so a cell's probability is the product of its
+/// normalized weights. Cells outside a dimension's `all()` (a laddered payout,
+/// the baseline row) take that dimension's full weight.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScenarioWeights {
+    pub payout: Option<Vec<f64>>,
+    pub volatility: Option<Vec<f64>>,
+    pub lift: Option<Vec<f64>>,
+}
+
+impl ScenarioWeights {
+    /// Whether any dimension carries supplied rather than equal weights
+    pub fn is_weighted(&self) -> bool {
+        self.payout.is_some() || self.volatility.is_some() || self.lift.is_some()
+    }
+
+    /// Probability of a grid cell under independent dimensions
+    pub fn probability(&self, id: ScenarioId) -> f64 {
+        let lift = id.lift.map_or(1.0, |lift| {
+            normalized_weight(&self.lift, LiftScenario::all(), lift)
+        });
+        normalized_weight(&self.payout, PayoutScenario::all(), id.payout)
+            * normalized_weight(&self.volatility, VolatilityScenario::all(), id.volatility)
+            * lift
+    }
+}
+
+/// Share of a dimension's total weight held by `value`, 1.0 when `value` is
+/// not one of the dimension's scenarios
+fn normalized_weight<T: PartialEq>(weights: &Option<Vec<f64>>, all: &[T], value: T) -> f64 {
+    let Some(index) = all.iter().position(|v| *v == value) else {
+        return 1.0;
+    };
+    match weights {
+        Some(weights) => weights[index] / weights.iter().sum::<f64>(),
+        None => 1.0 / all.len() as f64,
+    }
+}
+
+/// How much of the weighted scenario distribution the headline figures cover
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CentralLikelihood {
+    /// Probability of the single scenario behind the central estimate
+    pub central_probability: f64,
+    /// Probability of an outcome at or below the central estimate
+    pub at_or_below_central: f64,
 }
 
 /// Components used to calculate the discount rate
 #[derive(Debug, Clone)]
 pub struct DiscountRateComponents {
     /// Base risk-free rate
-    pub risk_free_rate: f64,
+    pub risk_free_rate: Rate,
     /// Premium based on market volatility
-    pub volatility_premium: f64,
+    pub volatility_premium: Rate,
     /// Premium for platform and publisher risk
-    pub platform_risk_premium: f64,
+    pub platform_risk_premium: Rate,
+    /// Total rate supplied directly or given by a rate formula, used in place
+    /// of the component sum
+    pub override_rate: Option<Rate>,
 }
 
 impl DiscountRateComponents {
     /// Calculate the total discount rate
-    pub fn total_rate(&self) -> f64 {
-        self.risk_free_rate + self.volatility_premium + self.platform_risk_premium
+    pub fn total_rate(&self) -> Rate {
+        self.override_rate
+            .unwrap_or(self.risk_free_rate + self.volatility_premium + self.platform_risk_premium)
+    }
+}
+
+/// Output format for a single-asset valuation
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// The full text report
+    #[default]
+    Text,
+    /// The full report as Markdown, with pipe tables
+    Markdown,
+    /// Long-format CSV with one row per grid cell, for pivot tables
+    CsvLong,
+    /// Versioned JSON document for archival and downstream tools
+    Json,
+    /// Headline metrics as gauges in the Prometheus text exposition format
+    Prometheus,
+}
+
+impl OutputFormat {
+    /// File extension for a report in this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Markdown => "md",
+            Self::CsvLong => "csv",
+            Self::Json => "json",
+            Self::Prometheus => "prom",
+        }
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "csv-long" => Ok(Self::CsvLong),
+            "json" => Ok(Self::Json),
+            "prometheus" => Ok(Self::Prometheus),
+            other => Err(format!(
+                "unknown output format '{}' (expected text, markdown, csv-long, json or prometheus)",
+                other
+            )),
+        }
+    }
+}
+
+/// A single headline figure printed bare by `--emit`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EmitFigure {
+    /// Lowest scenario valuation
+    Min,
+    /// Highest scenario valuation
+    Max,
+    /// Central ("most likely") estimate
+    Central,
+}
+
+impl EmitFigure {
+    /// The figure's value from the summary statistics
+    pub fn value(&self, summary: &SummaryStatistics) -> f64 {
+        match self {
+            Self::Min => summary.min_valuation,
+            Self::Max => summary.max_valuation,
+            Self::Central => summary.central_estimate,
+        }
+    }
+}
+
+impl std::str::FromStr for EmitFigure {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "min" => Ok(Self::Min),
+            "max" => Ok(Self::Max),
+            "central" => Ok(Self::Central),
+            other => Err(format!(
+                "unknown figure '{}' (expected min, max or central)",
+                other
+            )),
+        }
+    }
+}
+
+/// How the discount-rate components are shown
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum RateComponentUnits {
+    /// Stacked percentages
+    #[default]
+    Percent,
+    /// Basis points, each component with its share of the total rate
+    Bps,
+}
+
+impl std::str::FromStr for RateComponentUnits {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "percent" => Ok(Self::Percent),
+            "bps" => Ok(Self::Bps),
+            other => Err(format!(
+                "unknown rate component units '{}' (expected percent or bps)",
+                other
+            )),
+        }
+    }
+}
+
+/// How heatmap cells are shaded
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HeatmapStyle {
+    /// ANSI background colors, red (lowest) through yellow to green (highest)
+    Color,
+    /// A block-density character before each value, light (lowest) to full (highest)
+    Blocks,
+}
+
+/// A report section that can be reordered or omitted with `--sections`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReportSection {
+    /// Executive summary
+    Summary,
+    /// Model warnings collected during calculation
+    Warnings,
+    /// Section 1: Methodology Overview
+    Methodology,
+    /// Section 2: Model Assumptions and Inputs
+    Assumptions,
+    /// Section 3: Risk-Adjusted Discount Rates
+    DiscountRates,
+    /// Section 4: Baseline Valuation Analysis
+    Baseline,
+    /// Section 5: Investor Lift Model and Analysis
+    Lift,
+    /// Section 6: Full Valuation Analysis (the lift matrices)
+    Matrix,
+    /// Section 7: Key Insights and Risk Factors
+    Insights,
+    /// Section 8: Conclusion
+    Conclusion,
+    /// Appendix comparing discrete and continuous discounting
+    Appendix,
+    /// Footnote repeating key figures in a secondary locale
+    Footnote,
+}
+
+impl ReportSection {
+    /// Every section in default report order
+    pub fn all() -> &'static [Self] {
+        &[
+            Self::Summary,
+            Self::Warnings,
+            Self::Methodology,
+            Self::Assumptions,
+            Self::DiscountRates,
+            Self::Baseline,
+            Self::Lift,
+            Self::Matrix,
+            Self::Insights,
+            Self::Conclusion,
+            Self::Appendix,
+            Self::Footnote,
+        ]
+    }
+}
+
+impl std::str::FromStr for ReportSection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "summary" => Ok(Self::Summary),
+            "warnings" => Ok(Self::Warnings),
+            "methodology" => Ok(Self::Methodology),
+            "assumptions" => Ok(Self::Assumptions),
+            "discount-rates" => Ok(Self::DiscountRates),
+            "baseline" => Ok(Self::Baseline),
+            "lift" => Ok(Self::Lift),
+            "matrix" => Ok(Self::Matrix),
+            "insights" => Ok(Self::Insights),
+            "conclusion" => Ok(Self::Conclusion),
+            "appendix" => Ok(Self::Appendix),
+            "footnote" => Ok(Self::Footnote),
+            other => Err(format!(
+                "unknown report section '{}' (expected summary, warnings, methodology, \
+assumptions, discount-rates, baseline, lift, matrix, insights, conclusion, appendix or footnote)",
+                other
+            )),
+        }
+    }
+}
+
+/// Which arguments were set explicitly rather than taken from their defaults
+#[derive(Debug, Clone, Default)]
+pub struct InputSources {
+    explicit: HashSet<String>,
+    configured: HashSet<String>,
+    profiled: HashSet<String>,
+    fetched: HashSet<String>,
+}
+
+impl InputSources {
+    /// Sources for a run where `explicit` holds the ids of the arguments set on
+    /// the command line or in the environment
+    pub fn new(explicit: HashSet<String>) -> Self {
+        Self {
+            explicit,
+            configured: HashSet::new(),
+            profiled: HashSet::new(),
+            fetched: HashSet::new(),
+        }
+    }
+
+    /// Record that an argument's value came from a `--config` file
+    pub fn mark_configured(&mut self, id: &str) {
+        self.configured.insert(id.to_string());
+    }
+
+    /// Record that an argument's value came from the `--scenario-profile`
+    pub fn mark_profiled(&mut self, id: &str) {
+        self.profiled.insert(id.to_string());
+    }
+
+    /// Whether the argument with this id was set explicitly
+    pub fn is_explicit(&self, id: &str) -> bool {
+        self.explicit.contains(id)
+    }
+
+    /// Record that an argument's value was replaced by a live lookup
+    #[cfg(feature = "network")]
+    pub fn mark_fetched(&mut self, id: &str) {
+        self.fetched.insert(id.to_string());
+    }
+
+    /// Provenance label for an argument: "fetched" for live lookups, "config"
+    /// for `--config` files, "profile" for the `--scenario-profile`, "user",
+    /// "history" for inputs derived from a `--history` file, "holders" for an
+    /// investor count derived from `--total-holders`, or "default"
+    pub fn source(&self, id: &str) -> &'static str {
+        if self.fetched.contains(id) {
+            "fetched"
+        } else if self.configured.contains(id) {
+            "config"
+        } else if self.profiled.contains(id) {
+            "profile"
+        } else if self.is_explicit(id) {
+            "user"
+        } else if matches!(id, "baseline_audience" | "rpm") && self.is_explicit("history") {
+            "history"
+        } else if id == "investor_count" && self.is_explicit("total_holders") {
+            "holders"
+        } else {
+            "default"
+        }
     }
 }
 
 /// Result of a single valuation calculation
 #[derive(Debug, Clone)]
 pub struct ValuationResult {
-    /// Calculated present value
+    /// Calculated present value, pre- or after-tax as the inputs select
     pub present_value: f64,
+    /// Present value of the cash flows before tax
+    pub pre_tax_present_value: f64,
+    /// Present value of the cash flows after tax
+    pub after_tax_present_value: f64,
+    /// Undiscounted cash flow being valued (baseline plus lift over the horizon),
+    /// on the same tax basis as `present_value`
+    pub face_value: f64,
+    /// Present value of the investor's share of the pool, when a position is given
+    pub position_value: Option<f64>,
+    /// Present value converted to the display currency, when one is given
+    pub display_value: Option<f64>,
+    /// Quarterly revenue added to reach the revenue floor, zero when it does not bind
+    pub floor_top_up: f64,
     /// Payout timing scenario used
     pub payout_scenario: PayoutScenario,
     /// Volatility scenario used
     pub volatility_scenario: VolatilityScenario,
     /// Lift scenario used
     pub lift_scenario: Option<LiftScenario>,
+    /// Classification of the cell, filled in once the summary is known
+    pub tags: ScenarioTags,
+}
+
+/// Coarse stance of a scenario combination's assumptions
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum RiskBucket {
+    /// Leans on slow payout, high volatility or little lift
+    Conservative,
+    /// Balanced, like the central scenario
+    #[default]
+    Base,
+    /// Leans on fast payout, low volatility or strong lift
+    Aggressive,
+}
+
+impl RiskBucket {
+    /// Bucket of a grid cell. Each dimension scores +1 for its favourable
+    /// end (60 days, low volatility, high lift), -1 for its adverse end
+    /// (120 days, high or extreme volatility, low lift) and 0 in between;
+    /// a positive total is aggressive and a negative one conservative. The
+    /// baseline takes no view on lift, so it scores 0 for lift.
+    pub fn of(scenario: ScenarioId) -> Self {
+        let payout: i32 = match scenario.payout {
+            PayoutScenario::Day60 => 1,
+            PayoutScenario::Day90 | PayoutScenario::Laddered => 0,
+            PayoutScenario::Day120 => -1,
+        };
+        let volatility = match scenario.volatility {
+            VolatilityScenario::Low => 1,
+            VolatilityScenario::Typical => 0,
+            VolatilityScenario::High | VolatilityScenario::Extreme => -1,
+        };
+        let lift = match scenario.lift {
+            Some(LiftScenario::High) => 1,
+            Some(LiftScenario::Medium) | None => 0,
+            Some(LiftScenario::Low) => -1,
+        };
+        match (payout + volatility + lift).signum() {
+            1 => Self::Aggressive,
+            -1 => Self::Conservative,
+            _ => Self::Base,
+        }
+    }
+}
+
+impl std::fmt::Display for RiskBucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Conservative => write!(f, "conservative"),
+            Self::Base => write!(f, "base"),
+            Self::Aggressive => write!(f, "aggressive"),
+        }
+    }
+}
+
+/// Derived flags that let consumers pick out key grid cells
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct ScenarioTags {
+    /// No lift is included
+    pub is_baseline: bool,
+    /// The cell behind the central estimate
+    pub is_central: bool,
+    /// The cell with the lowest present value
+    pub is_min: bool,
+    /// The cell with the highest present value
+    pub is_max: bool,
+    /// Stance of the cell's assumptions
+    pub risk_bucket: RiskBucket,
+}
+
+impl ScenarioTags {
+    /// Tags of `scenario` given the grid's summary statistics
+    pub fn new(scenario: ScenarioId, summary: &SummaryStatistics) -> Self {
+        Self {
+            is_baseline: scenario.lift.is_none(),
+            is_central: scenario == summary.central_scenario,
+            is_min: scenario == summary.min_scenario,
+            is_max: scenario == summary.max_scenario,
+            risk_bucket: RiskBucket::of(scenario),
+        }
+    }
+}
+
+/// Identifies one cell of the scenario grid.
+///
+/// Ordering follows declaration order: payout (earliest first), then
+/// volatility (lowest first), then lift (baseline, then Low to High).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScenarioId {
+    /// Payout timing scenario
+    pub payout: PayoutScenario,
+    /// Volatility scenario
+    pub volatility: VolatilityScenario,
+    /// Lift scenario, `None` for baseline
+    pub lift: Option<LiftScenario>,
+}
+
+impl std::fmt::Display for ScenarioId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.lift {
+            Some(lift) => write!(f, "{} / {} / {}", self.payout, self.volatility, lift),
+            None => write!(f, "{} / {} / Baseline", self.payout, self.volatility),
+        }
+    }
+}
+
+impl ValuationResult {
+    /// The grid cell this result was calculated for
+    pub fn scenario(&self) -> ScenarioId {
+        ScenarioId {
+            payout: self.payout_scenario,
+            volatility: self.volatility_scenario,
+            lift: self.lift_scenario,
+        }
+    }
+
+    /// Fraction of the undiscounted cash flow retained after discounting
+    /// (the effective discount factor), e.g. 0.88 for 88%
+    pub fn retention_ratio(&self, cash_flow: f64) -> f64 {
+        if cash_flow == 0.0 {
+            0.0
+        } else {
+            self.present_value / cash_flow
+        }
+    }
+}
+
+/// A condition worth flagging that did not stop the valuation.
+///
+/// Warnings that quote amounts keep them as numbers, so the report can
+/// render them in its display currency, or as index points when redacting.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModelWarning {
+    /// A message quoting no amounts
+    Note(String),
+    /// No stress level moves the expected value to the market price
+    MarketPriceOutOfRange {
+        /// Observed market price of the pool
+        market_price: f64,
+        /// Lowest expected value any stress level gives
+        low: f64,
+        /// Highest expected value any stress level gives
+        high: f64,
+    },
+    /// Lift scenarios whose per-period revenue outweighs the baseline it lifts,
+    /// usually monthly figures entered as quarterly
+    LiftDominance {
+        /// Revenue period the lift and baseline are quoted per
+        period: RevenuePeriod,
+        /// Multiple of the adjusted baseline the lift exceeded
+        threshold: f64,
+        /// Adjusted baseline revenue per period
+        adjusted_baseline: f64,
+        /// Each dominating lift scenario with its per-period revenue
+        lifts: Vec<(LiftScenario, f64)>,
+    },
 }
 
 /// Comprehensive data structure containing all report data
@@ -210,6 +1288,197 @@ pub struct ReportData {
     pub summary: SummaryStatistics,
     /// Lift model assumptions
     pub lift_assumptions: LiftAssumptions,
+    /// Effective per-period growth rate per volatility scenario, after the growth guard
+    pub growth_rates: HashMap<VolatilityScenario, f64>,
+    /// Warnings collected while calculating, e.g. clamped inputs
+    pub warnings: Vec<ModelWarning>,
+    /// Discrete vs continuous present values per scenario, when requested
+    pub method_comparison: Option<Vec<MethodComparison>>,
+    /// Central estimate under each day-count convention, when requested
+    pub day_count_comparison: Option<Vec<DayCountComparison>>,
+    /// Central estimate under driver-based and fixed lift, when requested
+    pub lift_model_comparison: Option<LiftModelComparison>,
+    /// Present value of the lift cash flow alone per lift cell, when requested
+    pub lift_option: Option<Vec<LiftOptionValue>>,
+    /// Stress level at which the weighted expected value meets the market
+    /// price, when one is given and the grid can rationalize it
+    pub implied_stress: Option<f64>,
+    /// Modeling assumptions applied to this run, in report order
+    pub assumptions: Vec<ModelingAssumption>,
+    /// The central scenario's discount haircut at each payout, split into
+    /// time value and risk
+    pub haircut_splits: Vec<HaircutSplit>,
+}
+
+/// A modeling assumption in force for a run, stated for review
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelingAssumption {
+    /// What the assumption governs, e.g. "Discounting"
+    pub topic: &'static str,
+    /// The assumption as applied, reflecting the run's options
+    pub statement: AssumptionStatement,
+}
+
+impl ModelingAssumption {
+    /// An assumption about `topic`
+    pub fn new(topic: &'static str, statement: impl Into<AssumptionStatement>) -> Self {
+        Self {
+            topic,
+            statement: statement.into(),
+        }
+    }
+}
+
+/// What a modeling assumption states. Statements that quote figures keep
+/// them as numbers, so the report can format them for its audience.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssumptionStatement {
+    /// Prose quoting no figures
+    Text(String),
+    /// Flat RPMs, independent of audience size
+    FlatRpm {
+        /// RPM earned on the baseline audience
+        baseline: f64,
+        /// RPM earned on the lift audience
+        lift: f64,
+    },
+    /// Volatility premiums spread around the typical premium
+    ForecastConfidence(ForecastConfidence),
+    /// Lift fixed per period at medium activation
+    FixedLift {
+        /// Lift revenue per period at medium activation
+        amount: f64,
+        /// Revenue period the lift is quoted per
+        period: RevenuePeriod,
+    },
+    /// Lift audience linear in the investor count, with no saturation
+    LinearAudience {
+        /// Monthly visitors each investor brings
+        lift_per_investor: f64,
+    },
+    /// Several periods of revenue, growing at a flat or two-stage rate
+    Horizon {
+        /// Periods valued
+        quarters: u32,
+        /// Length of each period
+        period: RevenuePeriod,
+        /// Flat per-period growth rate (as decimal), unless staged
+        growth: f64,
+        /// Staged growth, replacing the flat rate when set
+        two_stage: Option<TwoStageGrowth>,
+    },
+    /// Cash flows taxed before discounting
+    TaxedAt {
+        /// Tax rate (as decimal)
+        rate: f64,
+    },
+}
+
+impl From<&str> for AssumptionStatement {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<String> for AssumptionStatement {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl ReportData {
+    /// Total discount rate applied to a grid cell: its rate matrix entry when
+    /// a matrix was supplied, otherwise the volatility scenario's rate
+    pub fn discount_rate(&self, scenario: ScenarioId) -> f64 {
+        self.inputs
+            .rate_matrix
+            .as_ref()
+            .and_then(|matrix| matrix.rate(scenario.payout, scenario.volatility))
+            .unwrap_or_else(|| {
+                self.discount_rates[&scenario.volatility]
+                    .total_rate()
+                    .as_decimal()
+            })
+    }
+}
+
+/// Present value of one scenario under both compounding conventions
+#[derive(Debug, Clone)]
+pub struct MethodComparison {
+    /// Grid cell being compared
+    pub scenario: ScenarioId,
+    /// Present value with discrete (annual) compounding
+    pub discrete: f64,
+    /// Present value with continuous compounding
+    pub continuous: f64,
+}
+
+impl MethodComparison {
+    /// Gap of the continuous value relative to the discrete value (as percentage)
+    pub fn gap_percentage(&self) -> f64 {
+        if self.discrete == 0.0 {
+            0.0
+        } else {
+            (self.continuous - self.discrete) / self.discrete * 100.0
+        }
+    }
+}
+
+/// Central estimate under one day-count convention
+#[derive(Debug, Clone)]
+pub struct DayCountComparison {
+    /// Convention used to convert payout delays into years
+    pub day_count: DayCount,
+    /// Central present value under that convention
+    pub central_estimate: f64,
+}
+
+/// Central estimate under the investor-driver lift model and a fixed lift
+#[derive(Debug, Clone)]
+pub struct LiftModelComparison {
+    /// Fixed per-period lift at medium activation being compared
+    pub fixed_lift: f64,
+    /// Central present value with lift driven by investor count and RPM
+    pub driver_based: f64,
+    /// Central present value with the fixed per-period lift
+    pub fixed: f64,
+}
+
+impl LiftModelComparison {
+    /// Fixed-lift value less the driver-based value
+    pub fn difference(&self) -> f64 {
+        self.fixed - self.driver_based
+    }
+
+    /// Gap of the fixed-lift value relative to the driver-based value (as percentage)
+    pub fn gap_percentage(&self) -> f64 {
+        if self.driver_based == 0.0 {
+            0.0
+        } else {
+            self.difference() / self.driver_based * 100.0
+        }
+    }
+}
+
+/// Present value of one lift cell's lift cash flow alone, the "growth option"
+#[derive(Debug, Clone)]
+pub struct LiftOptionValue {
+    /// Lift grid cell valued
+    pub scenario: ScenarioId,
+    /// Present value of the cell's lift cash flow, without the baseline
+    pub present_value: f64,
+}
+
+/// The central scenario's discount haircut at one payout: face value less
+/// present value, split at the value discounted at the risk-free rate alone
+#[derive(Debug, Clone, PartialEq)]
+pub struct HaircutSplit {
+    /// Payout the central volatility and lift are valued at
+    pub payout: PayoutScenario,
+    /// Face value less the present value at the risk-free rate
+    pub time: f64,
+    /// Present value at the risk-free rate less the present value at the full rate
+    pub risk: f64,
 }
 
 /// Summary statistics for the executive summary
@@ -219,20 +1488,54 @@ pub struct SummaryStatistics {
     pub min_valuation: f64,
     /// Maximum valuation across all scenarios
     pub max_valuation: f64,
-    /// Central estimate (typical volatility, 90 days, medium lift)
+    /// Scenario producing the minimum valuation
+    pub min_scenario: ScenarioId,
+    /// Scenario producing the maximum valuation
+    pub max_scenario: ScenarioId,
+    /// Scenario behind the central estimate
+    pub central_scenario: ScenarioId,
+    /// Central estimate (typical volatility, 90 days or ladder, medium lift or baseline)
     pub central_estimate: f64,
+    /// Undiscounted cash flow behind the central estimate
+    pub central_face_value: f64,
+    /// Investable low corner: longest payout, extreme volatility, low lift (or baseline)
+    pub downside: f64,
+    /// Investable high corner: shortest payout, low volatility, high lift (or baseline)
+    pub upside: f64,
+    /// Central estimate scaled to the investor's position, when one is given
+    pub position_value: Option<f64>,
+    /// Central estimate converted to the display currency, when one is given
+    pub display_central_estimate: Option<f64>,
     /// Best case volatility scenario
     pub best_volatility: VolatilityScenario,
     /// Worst case volatility scenario
     pub worst_volatility: VolatilityScenario,
-    /// Impact of volatility change (as percentage)
-    pub volatility_impact: f64,
-    /// Impact of lift change from low to high (as percentage)
-    pub lift_impact: f64,
-    /// Impact of payout cycle extension from 60 to 120 days (as percentage)
-    pub payout_impact: f64,
+    /// Value lost moving from low to extreme volatility (as percentage of the
+    /// low-volatility value), `None` when that value is zero
+    pub volatility_impact: Option<f64>,
+    /// Value gained moving from low to high lift (as percentage of the low-lift
+    /// value), `None` in baseline-only mode or when that value is zero
+    pub lift_impact: Option<f64>,
+    /// Value lost extending payout from 60 to 120 days (as percentage of the
+    /// 60-day value), `None` when laddered or when that value is zero
+    pub payout_impact: Option<f64>,
+    /// Value lost delaying payout from 60 to 90 days (as percentage of the
+    /// 60-day value), `None` when laddered or when that value is zero
+    pub payout_impact_60_90: Option<f64>,
+    /// Value lost delaying payout from 90 to 120 days (as percentage of the
+    /// 90-day value), `None` when laddered or when that value is zero
+    pub payout_impact_90_120: Option<f64>,
     /// Adjusted baseline revenue
     pub adjusted_baseline: f64,
+    /// Central estimate over adjusted revenue for one period, `None` for a zero baseline
+    pub quarterly_revenue_multiple: Option<f64>,
+    /// Central estimate over annualized adjusted revenue, `None` for a zero baseline
+    pub annual_revenue_multiple: Option<f64>,
+    /// Central estimate per dollar of purchase price, `None` without a price
+    pub value_ratio: Option<f64>,
+    /// Probability of a value within `AGREEMENT_BAND` of the central estimate,
+    /// under the weighted scenario distribution
+    pub scenario_agreement: f64,
 }
 
 /// Assumptions for the lift model
@@ -242,8 +1545,135 @@ pub struct LiftAssumptions {
     pub baseline_audience: f64,
     /// Revenue per thousand impressions
     pub rpm: f64,
-    /// Number of investors
-    pub investor_count: u32,
+    /// Revenue per thousand impressions earned by lift audience
+    pub incremental_rpm: f64,
+    /// Effective (active) number of investors
+    pub investor_count: f64,
     /// Lift per investor
     pub lift_per_investor: f64,
+    /// How the lift audience builds up over the quarter
+    pub lift_ramp: LiftRamp,
+    /// Fixed per-period lift at medium activation, in place of the drivers
+    pub fixed_lift: Option<f64>,
+    /// Revenue period the lift is counted in
+    pub period: RevenuePeriod,
+}
+
+impl LiftAssumptions {
+    /// Per-period lift dollar amount for `lift`: the fixed lift scaled by the
+    /// activation factor when one is given, otherwise driven by investors
+    pub fn period_lift(&self, lift: LiftScenario) -> f64 {
+        match self.fixed_lift {
+            Some(fixed) => fixed * lift.activation_factor(),
+            None => lift.period_lift(
+                self.investor_count,
+                self.lift_per_investor,
+                self.incremental_rpm,
+                self.lift_ramp,
+                self.period,
+            ),
+        }
+    }
+
+    /// Periods of `lift`'s revenue needed to recover `cost`, or `None` when
+    /// the lift earns nothing and never pays it back
+    pub fn payback_periods(&self, lift: LiftScenario, cost: f64) -> Option<f64> {
+        let revenue = self.period_lift(lift);
+        (revenue > 0.0).then(|| cost / revenue)
+    }
+}
+
+/// One value in a derivation tree, with the formula and values it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivationNode {
+    /// Name of the quantity, e.g. `discount_rate`
+    pub name: String,
+    /// Value of the quantity
+    pub value: f64,
+    /// How the value is computed from `inputs`, `None` for supplied inputs
+    pub formula: Option<String>,
+    /// Values this one was derived from
+    pub inputs: Vec<DerivationNode>,
+}
+
+impl DerivationNode {
+    /// A supplied input with nothing beneath it
+    pub fn leaf(name: &str, value: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+            formula: None,
+            inputs: Vec::new(),
+        }
+    }
+
+    /// A value computed by `formula` from `inputs`
+    pub fn derived(name: &str, value: f64, formula: &str, inputs: Vec<DerivationNode>) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+            formula: Some(formula.to_string()),
+            inputs,
+        }
+    }
+}
+
+impl From<&DerivationNode> for JsonValue {
+    fn from(node: &DerivationNode) -> Self {
+        JsonValue::Object(vec![
+            ("name".to_string(), node.name.as_str().into()),
+            ("value".to_string(), node.value.into()),
+            ("formula".to_string(), node.formula.as_deref().into()),
+            (
+                "inputs".to_string(),
+                JsonValue::Array(node.inputs.iter().map(JsonValue::from).collect()),
+            ),
+        ])
+    }
+}
+
+/// Full calculation tree for every grid cell, tracing each reported figure
+/// back to the inputs it was derived from
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivationTree {
+    /// Adjusted baseline revenue shared by every scenario
+    pub adjusted_baseline: DerivationNode,
+    /// Discount rate per volatility scenario, in `VolatilityScenario::all()` order
+    pub discount_rates: Vec<(VolatilityScenario, DerivationNode)>,
+    /// Present value derivation per grid cell, in grid order
+    pub scenarios: Vec<(ScenarioId, DerivationNode)>,
+}
+
+impl From<&DerivationTree> for JsonValue {
+    fn from(tree: &DerivationTree) -> Self {
+        JsonValue::Object(vec![
+            (
+                "adjusted_baseline".to_string(),
+                (&tree.adjusted_baseline).into(),
+            ),
+            (
+                "discount_rates".to_string(),
+                JsonValue::Object(
+                    tree.discount_rates
+                        .iter()
+                        .map(|(volatility, node)| (volatility.to_string(), node.into()))
+                        .collect(),
+                ),
+            ),
+            (
+                "scenarios".to_string(),
+                JsonValue::Array(
+                    tree.scenarios
+                        .iter()
+                        .map(|(scenario, node)| {
+                            JsonValue::Object(vec![
+                                ("scenario".to_string(), scenario.to_string().into()),
+                                ("derivation".to_string(), node.into()),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ),
+        ])
+    }
 }
@@ -0,0 +1,110 @@
+//! Fixed-point money type for cash flow arithmetic.
+//!
+//! Rates stay as `f64`; currency amounts are held as an integer count of
+//! micro-units so that sums and reconciliations do not accumulate float drift.
+
+/// Number of fractional units per whole currency unit (six decimal places).
+const SCALE: i128 = 1_000_000;
+
+/// Largest amount held, in micro-units (10^24 currency units). Far below
+/// `i128::MAX`, so sums of many maximal amounts stay representable.
+const MAX_MICROS: f64 = 1e30;
+
+/// A currency amount stored as a fixed-point decimal with six fractional digits.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(i128);
+
+impl Money {
+    /// The zero amount.
+    pub const ZERO: Money = Money(0);
+
+    /// Convert an `f64` amount, rounding to the nearest micro-unit. Returns
+    /// `None` for NaN, infinities and amounts beyond 10^24 currency units.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        Self::from_scaled(value * SCALE as f64)
+    }
+
+    /// Convert back to `f64` for rate-based calculations and display.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / SCALE as f64
+    }
+
+    /// Multiply by a floating-point factor (e.g., a discount factor),
+    /// rounding the result to the nearest micro-unit. Returns `None` when the
+    /// result is not a representable amount.
+    pub fn scale(self, factor: f64) -> Option<Self> {
+        Self::from_scaled(self.0 as f64 * factor)
+    }
+
+    /// Add two amounts, returning `None` if the total overflows.
+    pub fn checked_add(self, rhs: Money) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Money)
+    }
+
+    /// Sum amounts, returning `None` if the total overflows.
+    pub fn checked_sum(amounts: impl IntoIterator<Item = Money>) -> Option<Self> {
+        amounts
+            .into_iter()
+            .try_fold(Money::ZERO, |total, amount| total.checked_add(amount))
+    }
+
+    fn from_scaled(micros: f64) -> Option<Self> {
+        let micros = micros.round();
+        (micros.abs() <= MAX_MICROS).then_some(Money(micros as i128))
+    }
+}
+
+impl std::fmt::Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let abs = self.0.abs();
+        write!(f, "{}{}.{:06}", sign, abs / SCALE, abs % SCALE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum_reconciles_where_f64_drifts() {
+        // Ten tranches of $0.10 should reconcile to exactly $1.00.
+        let float_total: f64 = std::iter::repeat_n(0.1_f64, 10).sum();
+        assert_ne!(float_total, 1.0);
+
+        let tenth = Money::from_f64(0.1).unwrap();
+        let money_total = Money::checked_sum(std::iter::repeat_n(tenth, 10));
+        assert_eq!(money_total, Money::from_f64(1.0));
+    }
+
+    #[test]
+    fn test_unrepresentable_amounts_are_refused() {
+        assert_eq!(Money::from_f64(f64::NAN), None);
+        assert_eq!(Money::from_f64(f64::INFINITY), None);
+        assert_eq!(Money::from_f64(1e25), None);
+        assert!(Money::from_f64(1e24).is_some());
+        assert_eq!(Money::from_f64(1.0).unwrap().scale(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_sums_are_checked() {
+        let big = Money(i128::MAX - 1);
+        assert_eq!(big.checked_add(Money(1)), Some(Money(i128::MAX)));
+        assert_eq!(big.checked_add(Money(2)), None);
+        assert_eq!(Money::checked_sum([big, big]), None);
+        assert_eq!(Money::checked_sum([]), Some(Money::ZERO));
+    }
+
+    #[test]
+    fn test_scale_rounds_to_micro_units() {
+        let amount = Money::from_f64(100.0).unwrap();
+        assert_eq!(amount.scale(1.0 / 3.0), Money::from_f64(33.333333));
+        assert_eq!(amount.scale(-0.5), Money::from_f64(-50.0));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Money::from_f64(1234.5).unwrap().to_string(), "1234.500000");
+        assert_eq!(Money::from_f64(-0.25).unwrap().to_string(), "-0.250000");
+    }
+}
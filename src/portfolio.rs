@@ -0,0 +1,255 @@
+//! Aggregate valuation of a portfolio of token positions.
+//!
+//! Each asset is valued independently with the single-asset engine and the
+//! position's share of the token pool is summed into portfolio totals.
+
+use crate::cli::Args;
+use crate::error::{FileError, ModelError};
+use crate::json::JsonValue;
+use crate::model::ValuationInputs;
+use crate::valuation::calculate_validated_valuation;
+use clap::Parser;
+use std::path::Path;
+
+/// A holding in a single tokenized revenue stream
+#[derive(Debug, Clone)]
+pub struct PortfolioPosition {
+    /// Display name of the asset
+    pub name: String,
+    /// Number of tokens held in this asset
+    pub tokens_held: f64,
+    /// Total tokens issued against the asset's revenue pool
+    pub token_supply: f64,
+    /// Valuation inputs for the asset's full token pool
+    pub inputs: ValuationInputs,
+}
+
+impl PortfolioPosition {
+    /// Fraction of the token pool owned by this position
+    pub fn ownership(&self) -> f64 {
+        self.tokens_held / self.token_supply
+    }
+}
+
+/// Valuation of a single position within the portfolio
+#[derive(Debug, Clone)]
+pub struct PositionResult {
+    /// Display name of the asset
+    pub name: String,
+    /// Number of tokens held
+    pub tokens_held: f64,
+    /// Fraction of the token pool owned
+    pub ownership: f64,
+    /// Central estimate of the position's value
+    pub central_value: f64,
+    /// Lowest scenario value of the position
+    pub min_value: f64,
+    /// Highest scenario value of the position
+    pub max_value: f64,
+}
+
+/// Portfolio-level valuation under independent scenario assumptions
+#[derive(Debug, Clone)]
+pub struct PortfolioResult {
+    /// Per-asset results in file order
+    pub positions: Vec<PositionResult>,
+    /// Sum of the positions' central estimates
+    pub total_present_value: f64,
+    /// Sum of the positions' lowest scenario values. Each asset contributes
+    /// its own worst scenario, so no single scenario need produce this total.
+    pub min_value: f64,
+    /// Sum of the positions' highest scenario values, each asset's own best
+    /// scenario
+    pub max_value: f64,
+}
+
+impl PortfolioResult {
+    /// Share of the portfolio's central value contributed by a position (as percentage)
+    pub fn contribution(&self, position: &PositionResult) -> f64 {
+        if self.total_present_value == 0.0 {
+            0.0
+        } else {
+            position.central_value / self.total_present_value * 100.0
+        }
+    }
+}
+
+/// Load portfolio positions from a JSON file.
+///
+/// The file holds an `assets` array; each asset has a `name`, `tokens_held`,
+/// `token_supply` and an `inputs` object whose keys are the CLI flag names
+/// (e.g. `forecast`, `risk_free_rate`), so omitted inputs take the CLI defaults.
+pub fn load_portfolio(path: &Path) -> Result<Vec<PortfolioPosition>, ModelError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| ModelError::InputFile(format!("{}: {}", path.display(), e)))?;
+    let document = JsonValue::parse(&text).map_err(|e| FileError::from(e).in_file(path))?;
+    parse_portfolio(&document)
+}
+
+/// Build portfolio positions from a parsed JSON document
+pub fn parse_portfolio(document: &JsonValue) -> Result<Vec<PortfolioPosition>, ModelError> {
+    let assets = document
+        .get("assets")
+        .and_then(JsonValue::as_array)
+        .ok_or_else(|| ModelError::InputFile("portfolio must contain an 'assets' array".into()))?;
+
+    assets
+        .iter()
+        .enumerate()
+        .map(|(index, asset)| parse_position(index, asset))
+        .collect()
+}
+
+fn parse_position(index: usize, asset: &JsonValue) -> Result<PortfolioPosition, ModelError> {
+    let name = asset
+        .get("name")
+        .and_then(JsonValue::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("Asset {}", index + 1));
+    let number = |key: &str| {
+        asset.get(key).and_then(JsonValue::as_f64).ok_or_else(|| {
+            ModelError::InputFile(format!("asset '{}' is missing numeric '{}'", name, key))
+        })
+    };
+    let tokens_held = number("tokens_held")?;
+    let token_supply = number("token_supply")?;
+    if token_supply <= 0.0 || tokens_held < 0.0 || tokens_held > token_supply {
+        return Err(ModelError::InvalidInput(format!(
+            "asset '{}' must hold between 0 and token_supply tokens, with a positive supply",
+            name
+        )));
+    }
+
+    let inputs = asset
+        .get("inputs")
+        .ok_or_else(|| ModelError::InputFile(format!("asset '{}' is missing 'inputs'", name)))?;
+
+    Ok(PortfolioPosition {
+        inputs: inputs_from_json(&name, inputs)?,
+        name,
+        tokens_held,
+        token_supply,
+    })
+}
+
+/// Translate an `inputs` object into CLI arguments so that defaults and
+/// value parsing match the single-asset command exactly
+fn inputs_from_json(name: &str, inputs: &JsonValue) -> Result<ValuationInputs, ModelError> {
+    let entries = inputs.as_object().ok_or_else(|| {
+        ModelError::InputFile(format!("asset '{}' inputs must be an object", name))
+    })?;
+
+    let mut argv = vec!["tokval".to_string()];
+    for (key, value) in entries {
+        let flag = format!("--{}", key.replace('_', "-"));
+        match value {
+            JsonValue::Bool(true) => argv.push(flag),
+            JsonValue::Bool(false) => {}
+            JsonValue::Number(n) => argv.push(format!("{}={}", flag, n)),
+            JsonValue::String(s) => argv.push(format!("{}={}", flag, s)),
+            _ => {
+                return Err(ModelError::InputFile(format!(
+                    "asset '{}' input '{}' must be a number, string or boolean",
+                    name, key
+                )));
+            }
+        }
+    }
+
+    Args::try_parse_from(argv)
+        .map_err(|e| {
+            let message = e.to_string();
+            let first_line = message.lines().next().unwrap_or_default();
+            ModelError::InputFile(format!(
+                "asset '{}': {}",
+                name,
+                first_line.trim_start_matches("error: ")
+            ))
+        })?
+        .valuation_inputs()
+}
+
+/// Value each position independently and sum into portfolio totals. Each
+/// position's inputs must already have passed
+/// [`validate_inputs`](crate::valuation::validate_inputs).
+pub fn value_portfolio(positions: &[PortfolioPosition]) -> Result<PortfolioResult, ModelError> {
+    let results = positions
+        .iter()
+        .map(|position| {
+            let report = calculate_validated_valuation(&position.inputs)?;
+            let ownership = position.ownership();
+            Ok(PositionResult {
+                name: position.name.clone(),
+                tokens_held: position.tokens_held,
+                ownership,
+                central_value: report.summary.central_estimate * ownership,
+                min_value: report.summary.min_valuation * ownership,
+                max_value: report.summary.max_valuation * ownership,
+            })
+        })
+        .collect::<Result<Vec<_>, ModelError>>()?;
+
+    Ok(PortfolioResult {
+        total_present_value: results.iter().map(|r| r.central_value).sum(),
+        min_value: results.iter().map(|r| r.min_value).sum(),
+        max_value: results.iter().map(|r| r.max_value).sum(),
+        positions: results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::valuation::calculate_full_valuation;
+    use approx::assert_relative_eq;
+
+    const PORTFOLIO: &str = r#"{
+        "assets": [
+            {"name": "Alpha", "tokens_held": 2500, "token_supply": 10000,
+             "inputs": {"forecast": 220000}},
+            {"name": "Beta", "tokens_held": 1000, "token_supply": 1000,
+             "inputs": {"forecast": 50000, "risk_free_rate": "5%", "baseline_only": true}}
+        ]
+    }"#;
+
+    #[test]
+    fn test_portfolio_sums_independent_positions() {
+        let positions = parse_portfolio(&JsonValue::parse(PORTFOLIO).unwrap()).unwrap();
+        assert_eq!(positions.len(), 2);
+        assert_relative_eq!(positions[1].inputs.risk_free_rate.as_decimal(), 0.05);
+        assert!(positions[1].inputs.baseline_only);
+
+        let result = value_portfolio(&positions).unwrap();
+        let alpha = calculate_full_valuation(&positions[0].inputs).unwrap();
+        let beta = calculate_full_valuation(&positions[1].inputs).unwrap();
+
+        assert_relative_eq!(
+            result.total_present_value,
+            alpha.summary.central_estimate * 0.25 + beta.summary.central_estimate,
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            result.min_value,
+            alpha.summary.min_valuation * 0.25 + beta.summary.min_valuation,
+            epsilon = 1e-6
+        );
+        let contributions: f64 = result
+            .positions
+            .iter()
+            .map(|p| result.contribution(p))
+            .sum();
+        assert_relative_eq!(contributions, 100.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_portfolio_rejects_oversized_position() {
+        let doc = JsonValue::parse(
+            r#"{"assets": [{"tokens_held": 5, "token_supply": 1, "inputs": {"forecast": 1}}]}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            parse_portfolio(&doc),
+            Err(ModelError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,200 @@
+//! Named scenario profiles for `--scenario-profile`.
+//!
+//! A profile library is a directory of `<name>.toml` files, found through
+//! `--profile-dir` or the `TOKVAL_PROFILES` environment variable. Each profile
+//! bundles inputs such as rates, discount rates and lift drivers under the same
+//! keys as a `--config` file, and is applied as the first config layer: config
+//! files and command-line flags override it.
+//!
+//! Profiles are read as flat TOML: `key = value` lines with strings, numbers,
+//! booleans or single-line arrays of these, and `#` comments. Tables and
+//! multi-line values are not supported.
+
+use crate::config::PartialInputs;
+use crate::error::{FileError, ModelError};
+use crate::json::JsonValue;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the profile library when `--profile-dir` is not given
+pub const PROFILES_ENV: &str = "TOKVAL_PROFILES";
+
+/// The profile library directory: `explicit` if given, else `$TOKVAL_PROFILES`
+pub fn profile_dir(explicit: Option<&Path>) -> Result<PathBuf, ModelError> {
+    explicit
+        .map(Path::to_path_buf)
+        .or_else(|| {
+            std::env::var_os(PROFILES_ENV)
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from)
+        })
+        .ok_or_else(|| {
+            ModelError::InvalidInput(format!(
+                "No profile library; pass --profile-dir or set {}",
+                PROFILES_ENV
+            ))
+        })
+}
+
+/// Names of the profiles in `dir`, sorted
+pub fn list_profiles(dir: &Path) -> Result<Vec<String>, ModelError> {
+    let entries = std::fs::read_dir(dir).map_err(|source| ModelError::Io {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "toml" {
+                return None;
+            }
+            Some(path.file_stem()?.to_string_lossy().into_owned())
+        })
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Load the profile called `name` from `dir`
+pub fn load_profile(dir: &Path, name: &str) -> Result<PartialInputs, ModelError> {
+    let path = dir.join(format!("{}.toml", name));
+    if name.contains(['/', '\\']) || !path.is_file() {
+        let available = list_profiles(dir)?;
+        return Err(ModelError::InvalidInput(format!(
+            "Profile '{}' not found in {}; available profiles: {}",
+            name,
+            dir.display(),
+            if available.is_empty() {
+                "none".to_string()
+            } else {
+                available.join(", ")
+            }
+        )));
+    }
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| ModelError::InputFile(format!("{}: {}", path.display(), e)))?;
+    parse_profile(&text).map_err(|e| e.in_file(&path))
+}
+
+/// Read a profile's flat TOML into config inputs
+fn parse_profile(text: &str) -> Result<PartialInputs, FileError> {
+    let mut entries = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') {
+            return Err(FileError::at_line(
+                number,
+                "tables are not supported; a profile is a flat list of key = value",
+            ));
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| FileError::at_line(number, "expected key = value"))?;
+        let key = key.trim().trim_matches('"').to_string();
+        let value = parse_value(value.trim()).map_err(|e| FileError::at_field(number, &key, e))?;
+        entries.push((key, value));
+    }
+    PartialInputs::from_json(&JsonValue::Object(entries))
+}
+
+/// The line up to any `#` that is not inside a string
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, '#') => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// A TOML string, number, boolean or single-line array of these
+fn parse_value(text: &str) -> Result<JsonValue, String> {
+    if let Some(items) = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        return items
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(parse_value)
+            .collect::<Result<_, _>>()
+            .map(JsonValue::Array);
+    }
+    for quote in ['"', '\''] {
+        if let Some(inner) = text.strip_prefix(quote).and_then(|t| t.strip_suffix(quote)) {
+            return Ok(JsonValue::String(inner.to_string()));
+        }
+    }
+    match text {
+        "true" => Ok(JsonValue::Bool(true)),
+        "false" => Ok(JsonValue::Bool(false)),
+        _ => text
+            .replace('_', "")
+            .parse::<f64>()
+            .map(JsonValue::Number)
+            .map_err(|_| format!("unsupported value '{}'", text)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_toml_profile_parses_into_inputs() {
+        let profile = parse_profile(
+            r#"
+# Bear market, 2024 edition
+risk_free_rate = 5.25          # percent
+discount_rates = [0.22, 0.30, 0.42, 0.55]
+lift_ramp = "linear"
+investor_count = 1_500
+baseline_only = false
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            profile.to_args(|_| false),
+            [
+                "--discount-rates=0.22,0.3,0.42,0.55",
+                "--investor-count=1500",
+                "--lift-ramp=linear",
+                "--risk-free-rate=5.25"
+            ]
+        );
+
+        let error = parse_profile("rpm = 12\n[rates]\n").unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("line 2: tables are not supported")
+        );
+        let error = parse_profile("\nrpm = twelve").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, field 'rpm': unsupported value 'twelve'"
+        );
+    }
+
+    #[test]
+    fn test_missing_profile_lists_the_available_ones() {
+        let dir = std::env::temp_dir().join(format!("tokval-profiles-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("steady-state.toml"), "rpm = 15\n").unwrap();
+        std::fs::write(dir.join("2024-bear.toml"), "risk_free_rate = 5.25\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a profile").unwrap();
+
+        assert_eq!(list_profiles(&dir).unwrap(), ["2024-bear", "steady-state"]);
+        assert!(load_profile(&dir, "steady-state").is_ok());
+        let message = load_profile(&dir, "2023-bull").unwrap_err().to_string();
+        assert!(message.contains("Profile '2023-bull' not found"));
+        assert!(message.ends_with("available profiles: 2024-bear, steady-state"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
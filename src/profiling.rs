@@ -0,0 +1,87 @@
+//! Lightweight stage timing for the `--profile` flag.
+
+use std::time::{Duration, Instant};
+
+/// Records wall-clock durations for named calculation stages.
+///
+/// When disabled, `time` simply runs the closure without touching the clock.
+#[derive(Debug, Default)]
+pub struct Timer {
+    enabled: bool,
+    stages: Vec<(&'static str, Duration)>,
+}
+
+impl Timer {
+    /// Create a timer; stages are only recorded when `enabled` is true.
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            stages: Vec::new(),
+        }
+    }
+
+    /// Run `f`, recording its duration under `stage` if profiling is enabled.
+    pub fn time<T>(&mut self, stage: &'static str, f: impl FnOnce() -> T) -> T {
+        if !self.enabled {
+            return f();
+        }
+        let start = Instant::now();
+        let result = f();
+        self.stages.push((stage, start.elapsed()));
+        result
+    }
+
+    /// Format the recorded stages as a breakdown table with totals.
+    pub fn summary(&self) -> String {
+        let total: Duration = self.stages.iter().map(|(_, d)| *d).sum();
+        let mut out = String::from("Profile (stage timings):\n");
+        for (stage, duration) in &self.stages {
+            let share = if total.is_zero() {
+                0.0
+            } else {
+                duration.as_secs_f64() / total.as_secs_f64() * 100.0
+            };
+            out.push_str(&format!(
+                "  {:<20} {:>10.3} ms  {:>5.1}%\n",
+                stage,
+                duration.as_secs_f64() * 1000.0,
+                share
+            ));
+        }
+        out.push_str(&format!(
+            "  {:<20} {:>10.3} ms\n",
+            "total",
+            total.as_secs_f64() * 1000.0
+        ));
+        out
+    }
+
+    /// Print the breakdown to stderr if profiling is enabled.
+    pub fn report(&self) {
+        if self.enabled {
+            eprint!("{}", self.summary());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_timer_records_nothing() {
+        let mut timer = Timer::new(false);
+        assert_eq!(timer.time("stage", || 42), 42);
+        assert!(timer.stages.is_empty());
+    }
+
+    #[test]
+    fn test_enabled_timer_records_stages_in_order() {
+        let mut timer = Timer::new(true);
+        timer.time("first", || ());
+        timer.time("second", || ());
+        let names: Vec<_> = timer.stages.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, vec!["first", "second"]);
+        assert!(timer.summary().contains("total"));
+    }
+}
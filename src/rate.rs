@@ -0,0 +1,99 @@
+//! Decimal rate type for discount rates and their components.
+//!
+//! Rates are entered as percentages on the command line but calculated as
+//! decimals, and mixing the two up has been a recurring bug. A `Rate` is only
+//! built through a constructor that says which form it is given in and checks
+//! the decimal lies in a sane range, so a premium of 12 passed where 0.12 was
+//! meant is refused where it enters instead of producing a nonsensical
+//! valuation.
+
+use std::ops::Add;
+
+/// Lowest decimal rate, exclusive: at -100% discount factors are undefined
+pub const MIN_DECIMAL: f64 = -1.0;
+
+/// Highest decimal rate accepted (500%)
+pub const MAX_DECIMAL: f64 = 5.0;
+
+/// An annual rate held as a decimal (0.045 for 4.5%)
+#[derive(Debug, Copy, Clone, Default, PartialEq, PartialOrd)]
+pub struct Rate(f64);
+
+impl Rate {
+    /// A rate known to lie in range, such as a built-in premium
+    pub const fn constant(decimal: f64) -> Self {
+        assert!(decimal > MIN_DECIMAL && decimal <= MAX_DECIMAL);
+        Rate(decimal)
+    }
+
+    /// Create a rate from a decimal, e.g. 0.045 for 4.5%
+    pub fn from_decimal(decimal: f64) -> Result<Self, String> {
+        if decimal.is_finite() && decimal > MIN_DECIMAL && decimal <= MAX_DECIMAL {
+            Ok(Rate(decimal))
+        } else {
+            Err(format!(
+                "rate {} ({}%) is outside the supported range of -100% (exclusive) to 500%",
+                decimal,
+                decimal * 100.0
+            ))
+        }
+    }
+
+    /// Create a rate from a percentage, e.g. 4.5 for 4.5%
+    pub fn from_percent(percent: f64) -> Result<Self, String> {
+        Self::from_decimal(percent / 100.0)
+    }
+
+    /// The rate as a decimal, for calculation
+    pub fn as_decimal(self) -> f64 {
+        self.0
+    }
+
+    /// The rate as a percentage, for display
+    pub fn as_percent(self) -> f64 {
+        self.0 * 100.0
+    }
+}
+
+/// Sum rate components into a total rate. The sum is not re-checked: each
+/// component is in range and the total only feeds discounting.
+impl Add for Rate {
+    type Output = Rate;
+
+    fn add(self, rhs: Rate) -> Rate {
+        Rate(self.0 + rhs.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_range_construction_fails() {
+        // A 12% premium passed as a decimal would be 1,200%
+        assert!(Rate::from_decimal(0.12).is_ok());
+        assert!(Rate::from_decimal(12.0).is_err());
+        assert!(Rate::from_percent(4500.0).is_err());
+        assert!(Rate::from_decimal(-1.0).is_err());
+        assert!(Rate::from_decimal(f64::NAN).is_err());
+        assert!(Rate::from_decimal(f64::INFINITY).is_err());
+
+        assert_eq!(Rate::from_decimal(5.0).unwrap().as_percent(), 500.0);
+        assert_eq!(Rate::from_percent(-99.0).unwrap().as_decimal(), -0.99);
+        assert!(
+            Rate::from_percent(501.0)
+                .unwrap_err()
+                .contains("outside the supported range")
+        );
+    }
+
+    #[test]
+    fn test_percent_and_decimal_agree() {
+        let rate = Rate::from_percent(4.5).unwrap();
+        assert_eq!(rate, Rate::from_decimal(0.045).unwrap());
+        assert_eq!(rate.as_decimal(), 0.045);
+        assert_eq!(rate.as_percent(), 4.5);
+        assert_eq!((rate + Rate::constant(0.1)).as_decimal(), 0.045 + 0.1);
+    }
+}
@@ -0,0 +1,355 @@
+//! Custom total discount rate formulas for `--rate-formula`.
+//!
+//! A formula is an arithmetic expression over the rate components of a
+//! volatility scenario, e.g. `rf + 0.5*vol_premium + platform_premium`. It
+//! supports decimal numbers, `+ - * / ^`, unary minus and parentheses, with
+//! the usual precedence (`^` binds tightest and is right-associative).
+//! Variables are the components as decimals:
+//!
+//! - `rf`: the risk-free rate
+//! - `vol_premium`: the volatility scenario's premium
+//! - `platform_premium`: the platform/publisher risk premium
+//!
+//! The formula is evaluated once per volatility scenario and its result
+//! replaces the component sum as that scenario's total rate.
+
+use crate::model::DiscountRateComponents;
+use crate::rate::Rate;
+
+/// A rate component a formula can refer to
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Variable {
+    RiskFree,
+    VolatilityPremium,
+    PlatformPremium,
+}
+
+impl Variable {
+    const ALL: [Variable; 3] = [
+        Self::RiskFree,
+        Self::VolatilityPremium,
+        Self::PlatformPremium,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::RiskFree => "rf",
+            Self::VolatilityPremium => "vol_premium",
+            Self::PlatformPremium => "platform_premium",
+        }
+    }
+
+    fn value(self, components: &DiscountRateComponents) -> f64 {
+        match self {
+            Self::RiskFree => components.risk_free_rate,
+            Self::VolatilityPremium => components.volatility_premium,
+            Self::PlatformPremium => components.platform_risk_premium,
+        }
+        .as_decimal()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Number(f64),
+    Variable(Variable),
+    Negate(Box<Expr>),
+    Binary(Operator, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn evaluate(&self, components: &DiscountRateComponents) -> f64 {
+        match self {
+            Self::Number(value) => *value,
+            Self::Variable(variable) => variable.value(components),
+            Self::Negate(operand) => -operand.evaluate(components),
+            Self::Binary(operator, left, right) => {
+                let (left, right) = (left.evaluate(components), right.evaluate(components));
+                match operator {
+                    Operator::Add => left + right,
+                    Operator::Subtract => left - right,
+                    Operator::Multiply => left * right,
+                    Operator::Divide => left / right,
+                    Operator::Power => left.powf(right),
+                }
+            }
+        }
+    }
+}
+
+/// A parsed `--rate-formula` expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateFormula {
+    source: String,
+    expr: Expr,
+}
+
+impl RateFormula {
+    /// Total rate the formula gives for a scenario's components, or why it is
+    /// not a usable rate (out of range, or not finite after e.g. a division by zero)
+    pub fn rate(&self, components: &DiscountRateComponents) -> Result<Rate, String> {
+        Rate::from_decimal(self.expr.evaluate(components))
+            .map_err(|e| format!("rate formula '{}' is unusable: {}", self.source, e))
+    }
+
+    /// Names of the variables a formula can use
+    pub fn variable_names() -> impl Iterator<Item = &'static str> {
+        Variable::ALL.into_iter().map(Variable::name)
+    }
+}
+
+impl std::fmt::Display for RateFormula {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl std::str::FromStr for RateFormula {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            text: s,
+            position: 0,
+            depth: 0,
+        };
+        let expr = parser.expression()?;
+        parser.skip_whitespace();
+        if let Some(c) = parser.peek() {
+            return Err(parser.error(format!("unexpected '{}'", c)));
+        }
+        Ok(Self {
+            source: s.trim().to_string(),
+            expr,
+        })
+    }
+}
+
+/// Deepest nesting of parentheses, negations and exponents a formula may use,
+/// well within the stack the recursive parser and evaluator need
+const MAX_DEPTH: usize = 64;
+
+/// Recursive-descent parser over the formula text
+struct Parser<'a> {
+    text: &'a str,
+    position: usize,
+    /// Number of `unary` rules currently being parsed
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.position += c.len_utf8();
+        }
+    }
+
+    /// Consume the next non-space character if it is one of `operators`
+    fn operator(&mut self, operators: &[(char, Operator)]) -> Option<Operator> {
+        self.skip_whitespace();
+        let c = self.peek()?;
+        let (_, operator) = operators.iter().find(|(symbol, _)| *symbol == c)?;
+        self.position += 1;
+        Some(*operator)
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> String {
+        format!(
+            "{} at position {} of the rate formula",
+            message,
+            self.position + 1
+        )
+    }
+
+    /// expression := term (('+' | '-') term)*
+    fn expression(&mut self) -> Result<Expr, String> {
+        let mut expr = self.term()?;
+        while let Some(operator) = self.operator(&[('+', Operator::Add), ('-', Operator::Subtract)])
+        {
+            expr = Expr::Binary(operator, Box::new(expr), Box::new(self.term()?));
+        }
+        Ok(expr)
+    }
+
+    /// term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
+        while let Some(operator) =
+            self.operator(&[('*', Operator::Multiply), ('/', Operator::Divide)])
+        {
+            expr = Expr::Binary(operator, Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    /// unary := '-' unary | power
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("formula nests too deeply"));
+        }
+        self.depth += 1;
+        let expr = if self.operator(&[('-', Operator::Subtract)]).is_some() {
+            self.unary().map(|operand| Expr::Negate(Box::new(operand)))
+        } else {
+            self.power()
+        };
+        self.depth -= 1;
+        expr
+    }
+
+    /// power := atom ('^' unary)?
+    fn power(&mut self) -> Result<Expr, String> {
+        let base = self.atom()?;
+        if self.operator(&[('^', Operator::Power)]).is_some() {
+            return Ok(Expr::Binary(
+                Operator::Power,
+                Box::new(base),
+                Box::new(self.unary()?),
+            ));
+        }
+        Ok(base)
+    }
+
+    /// atom := number | variable | '(' expression ')'
+    fn atom(&mut self) -> Result<Expr, String> {
+        self.skip_whitespace();
+        let start = self.position;
+        match self.peek() {
+            Some('(') => {
+                self.position += 1;
+                let expr = self.expression()?;
+                self.skip_whitespace();
+                if self.peek() != Some(')') {
+                    return Err(self.error("expected ')'"));
+                }
+                self.position += 1;
+                Ok(expr)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+                    self.position += 1;
+                }
+                let number = &self.text[start..self.position];
+                number.parse().map(Expr::Number).map_err(|_| {
+                    self.position = start;
+                    self.error(format!("invalid number '{}'", number))
+                })
+            }
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    self.position += 1;
+                }
+                let name = &self.text[start..self.position];
+                Variable::ALL
+                    .into_iter()
+                    .find(|variable| variable.name() == name)
+                    .map(Expr::Variable)
+                    .ok_or_else(|| {
+                        self.position = start;
+                        self.error(format!(
+                            "unknown variable '{}' (expected {})",
+                            name,
+                            RateFormula::variable_names().collect::<Vec<_>>().join(", ")
+                        ))
+                    })
+            }
+            Some(c) => Err(self.error(format!("unexpected '{}'", c))),
+            None => Err(self.error("unexpected end")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn components() -> DiscountRateComponents {
+        DiscountRateComponents {
+            risk_free_rate: Rate::constant(0.04),
+            volatility_premium: Rate::constant(0.10),
+            platform_risk_premium: Rate::constant(0.12),
+            override_rate: None,
+        }
+    }
+
+    fn rate(formula: &str) -> f64 {
+        formula
+            .parse::<RateFormula>()
+            .unwrap()
+            .rate(&components())
+            .unwrap()
+            .as_decimal()
+    }
+
+    #[test]
+    fn test_formula_follows_precedence() {
+        assert_eq!(
+            rate("rf + vol_premium + platform_premium"),
+            0.04 + 0.10 + 0.12
+        );
+        assert_eq!(
+            rate("rf + 0.5*vol_premium + platform_premium"),
+            0.04 + 0.5 * 0.10 + 0.12
+        );
+        assert_eq!(rate("(rf + vol_premium) * 2"), (0.04 + 0.10) * 2.0);
+        assert_eq!(rate("rf - -vol_premium / 2"), 0.04 + 0.10 / 2.0);
+        assert_eq!(rate("2 ^ 3 ^ 0 / 10"), 0.2);
+        assert_eq!(rate("-0.1^2 + rf"), -(0.1f64.powf(2.0)) + 0.04);
+    }
+
+    #[test]
+    fn test_invalid_formulas_are_refused() {
+        let error = |formula: &str| formula.parse::<RateFormula>().unwrap_err();
+        assert_eq!(
+            error("rf + tenor_factor"),
+            "unknown variable 'tenor_factor' (expected rf, vol_premium, platform_premium) \
+at position 6 of the rate formula"
+        );
+        assert_eq!(
+            error("(rf + vol_premium"),
+            "expected ')' at position 18 of the rate formula"
+        );
+        assert_eq!(
+            error("rf +"),
+            "unexpected end at position 5 of the rate formula"
+        );
+        assert_eq!(
+            error("rf 2"),
+            "unexpected '2' at position 4 of the rate formula"
+        );
+        assert!(error("1.2.3").starts_with("invalid number '1.2.3'"));
+
+        // Deep nesting is refused rather than overflowing the stack
+        let nested = |depth: usize| format!("{}rf{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(nested(MAX_DEPTH - 1).parse::<RateFormula>().is_ok());
+        assert!(error(&nested(5000)).starts_with("formula nests too deeply"));
+        assert!(error(&format!("{}rf", "-".repeat(5000))).starts_with("formula nests too deeply"));
+        assert!(
+            error(&format!("{}rf", "rf ^ ".repeat(5000))).starts_with("formula nests too deeply")
+        );
+
+        let formula: RateFormula = "rf / (vol_premium - 0.1)".parse().unwrap();
+        assert!(
+            formula
+                .rate(&components())
+                .unwrap_err()
+                .contains("outside the supported range")
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! Per-cell discount rates for `--rate-matrix`.
+//!
+//! A rate matrix is a CSV whose header row is `payout` followed by one column
+//! per volatility scenario (`low`, `typical`, `high`, `extreme`, in any
+//! order). Each further row starts with a payout delay in days (`60`, `90`,
+//! `120`) or `laddered`, and gives the total discount rate for each volatility
+//! scenario as a decimal (e.g. `0.26`). Rates are used verbatim in place of
+//! the composed risk-free + volatility + platform rate.
+
+use crate::error::{FileError, ModelError};
+use crate::model::{PayoutScenario, VolatilityScenario};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Total discount rate per (payout, volatility) grid cell
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateMatrix {
+    rates: HashMap<(PayoutScenario, VolatilityScenario), f64>,
+}
+
+impl RateMatrix {
+    /// Rate for one cell, `None` if the matrix has no entry for it
+    pub fn rate(&self, payout: PayoutScenario, volatility: VolatilityScenario) -> Option<f64> {
+        self.rates.get(&(payout, volatility)).copied()
+    }
+
+    /// Payout scenarios with a row in the matrix, earliest first
+    pub fn payouts(&self) -> Vec<PayoutScenario> {
+        let mut payouts: Vec<PayoutScenario> = self.rates.keys().map(|(p, _)| *p).collect();
+        payouts.sort();
+        payouts.dedup();
+        payouts
+    }
+
+    /// Every rate in the matrix
+    pub fn rates(&self) -> impl Iterator<Item = f64> + '_ {
+        self.rates.values().copied()
+    }
+}
+
+/// Load a rate matrix from a CSV file
+pub fn load_rate_matrix(path: &Path) -> Result<RateMatrix, ModelError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| ModelError::InputFile(format!("{}: {}", path.display(), e)))?;
+    parse_rate_matrix(&text).map_err(|e| e.in_file(path))
+}
+
+/// Parse a rate matrix from CSV text, locating any problem by line and column
+pub fn parse_rate_matrix(text: &str) -> Result<RateMatrix, FileError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (header_index, header) = lines.next().ok_or("rate matrix is empty")?;
+    let header_line = header_index + 1;
+    let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+    if !columns[0].eq_ignore_ascii_case("payout") {
+        return Err(FileError::at_line(
+            header_line,
+            "rate matrix header must start with a 'payout' column",
+        ));
+    }
+    let volatilities = columns[1..]
+        .iter()
+        .map(|name| parse_volatility(name).map_err(|e| FileError::at_field(header_line, name, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+    for volatility in VolatilityScenario::all() {
+        match volatilities.iter().filter(|v| *v == volatility).count() {
+            1 => {}
+            0 => {
+                return Err(FileError::at_line(
+                    header_line,
+                    format!("rate matrix is missing the {} column", volatility),
+                ));
+            }
+            _ => {
+                return Err(FileError::at_line(
+                    header_line,
+                    format!("rate matrix repeats the {} column", volatility),
+                ));
+            }
+        }
+    }
+
+    let mut rates = HashMap::new();
+    for (line_index, line) in lines {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let line = line_index + 1;
+        if fields.len() != columns.len() {
+            return Err(FileError::at_line(
+                line,
+                format!("expected {} fields, got {}", columns.len(), fields.len()),
+            ));
+        }
+        let payout =
+            parse_payout(fields[0]).map_err(|e| FileError::at_field(line, columns[0], e))?;
+        for ((volatility, column), field) in
+            volatilities.iter().zip(&columns[1..]).zip(&fields[1..])
+        {
+            let rate = field
+                .parse::<f64>()
+                .ok()
+                .filter(|rate| rate.is_finite())
+                .ok_or_else(|| {
+                    FileError::at_field(line, column, format!("invalid rate '{}'", field))
+                })?;
+            if rates.insert((payout, *volatility), rate).is_some() {
+                return Err(FileError::at_field(
+                    line,
+                    columns[0],
+                    format!("repeats the {} row", payout),
+                ));
+            }
+        }
+    }
+
+    if rates.is_empty() {
+        return Err("rate matrix has no payout rows".into());
+    }
+    Ok(RateMatrix { rates })
+}
+
+fn parse_volatility(name: &str) -> Result<VolatilityScenario, String> {
+    match name.to_ascii_lowercase().as_str() {
+        "low" | "low vol" => Ok(VolatilityScenario::Low),
+        "typical" => Ok(VolatilityScenario::Typical),
+        "high" | "high vol" => Ok(VolatilityScenario::High),
+        "extreme" => Ok(VolatilityScenario::Extreme),
+        other => Err(format!(
+            "unknown volatility column '{}' (expected low, typical, high or extreme)",
+            other
+        )),
+    }
+}
+
+fn parse_payout(label: &str) -> Result<PayoutScenario, String> {
+    let label = label.to_ascii_lowercase();
+    if label == "laddered" {
+        return Ok(PayoutScenario::Laddered);
+    }
+    let days = label.strip_suffix(" days").unwrap_or(&label);
+    PayoutScenario::all()
+        .iter()
+        .copied()
+        .find(|p| p.days().is_some_and(|d| d.to_string() == days))
+        .ok_or_else(|| {
+            format!(
+                "unknown payout '{}' (expected 60, 90, 120 or laddered)",
+                label
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MATRIX: &str = "payout,low,typical,high,extreme\n\
+        60,0.18,0.24,0.34,0.45\n\
+        90,0.20,0.26,0.36,0.47\n\
+        120 Days,0.22,0.28,0.38,0.49\n";
+
+    #[test]
+    fn test_parse_rate_matrix() {
+        let matrix = parse_rate_matrix(MATRIX).unwrap();
+        assert_eq!(
+            matrix.rate(PayoutScenario::Day90, VolatilityScenario::High),
+            Some(0.36)
+        );
+        assert_eq!(
+            matrix.rate(PayoutScenario::Day120, VolatilityScenario::Extreme),
+            Some(0.49)
+        );
+        assert_eq!(
+            matrix.rate(PayoutScenario::Laddered, VolatilityScenario::Low),
+            None
+        );
+        assert_eq!(matrix.payouts(), PayoutScenario::all());
+    }
+
+    #[test]
+    fn test_rejects_malformed_matrices() {
+        assert!(
+            parse_rate_matrix("payout,low,typical,high\n60,0.1,0.2,0.3\n")
+                .unwrap_err()
+                .to_string()
+                .contains("Extreme")
+        );
+        assert!(
+            parse_rate_matrix("payout,low,typical,high,extreme\n60,0.1,0.2,0.3\n")
+                .unwrap_err()
+                .to_string()
+                .contains("line 2")
+        );
+        assert!(
+            parse_rate_matrix("payout,low,typical,high,extreme\n45,0.1,0.2,0.3,0.4\n")
+                .unwrap_err()
+                .to_string()
+                .contains("unknown payout")
+        );
+        assert!(
+            parse_rate_matrix(
+                "payout,low,typical,high,extreme\n60,0.1,0.2,0.3,0.4\n60,0.1,0.2,0.3,0.4\n"
+            )
+            .unwrap_err()
+            .to_string()
+            .contains("repeats")
+        );
+    }
+
+    #[test]
+    fn test_errors_locate_the_offending_row_and_column() {
+        let error =
+            parse_rate_matrix(&format!("{}laddered,0.2,0.3,n/a,0.5\n", MATRIX)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 5, field 'high': invalid rate 'n/a'"
+        );
+
+        let error = parse_rate_matrix("payout,low,typical,stormy\n").unwrap_err();
+        assert_eq!(error.line, Some(1));
+        assert_eq!(error.field.as_deref(), Some("stormy"));
+    }
+}
@@ -0,0 +1,180 @@
+//! Format-neutral report model.
+//!
+//! Section builders describe the report as an ordered list of blocks (prose,
+//! tables and labelled values) without deciding how they look. Each output
+//! format is a renderer over the same `Report`, so a new format only needs a
+//! new `render_*` method rather than another walk over `ReportData`.
+
+use comfy_table::modifiers::UTF8_ROUND_CORNERS;
+use comfy_table::{Table, presets::UTF8_FULL};
+
+/// A report as an ordered list of format-neutral blocks
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    pub blocks: Vec<Block>,
+}
+
+/// One piece of report content
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    /// Free text, carrying its own line breaks
+    Prose(String),
+    /// A captioned table of pre-formatted cells
+    Table(TableData),
+    /// A bulleted list of labelled values
+    KeyValues(Vec<(String, String)>),
+}
+
+/// A captioned table whose cells are already formatted for display
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableData {
+    pub caption: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl TableData {
+    /// Start an empty table with a caption and column headers
+    pub fn new<H: Into<String>>(
+        caption: impl Into<String>,
+        headers: impl IntoIterator<Item = H>,
+    ) -> Self {
+        Self {
+            caption: caption.into(),
+            headers: headers.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a row of formatted cells
+    pub fn add_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    /// The table drawn with box characters, without its caption
+    fn to_box_table(&self) -> Table {
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_header(self.headers.clone());
+        for row in &self.rows {
+            table.add_row(row.clone());
+        }
+        table
+    }
+
+    /// The table as a GitHub-flavored Markdown pipe table, without its caption
+    fn to_markdown(&self) -> String {
+        let line = |cells: &[String]| {
+            let cells: Vec<String> = cells.iter().map(|c| c.replace('|', "\\|")).collect();
+            format!("| {} |", cells.join(" | "))
+        };
+        let mut lines = vec![
+            line(&self.headers),
+            format!("|{}|", vec!["---"; self.headers.len()].join("|")),
+        ];
+        lines.extend(self.rows.iter().map(|row| line(row)));
+        lines.join("\n")
+    }
+}
+
+/// Renders the caption and box-drawn table as in the text report
+impl std::fmt::Display for TableData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\n\n{}", self.caption, self.to_box_table())
+    }
+}
+
+impl Report {
+    /// Append free text
+    pub fn prose(&mut self, text: impl Into<String>) {
+        self.blocks.push(Block::Prose(text.into()));
+    }
+
+    /// Append a table
+    pub fn table(&mut self, table: TableData) {
+        self.blocks.push(Block::Table(table));
+    }
+
+    /// Append labelled values
+    pub fn key_values<K: Into<String>, V: Into<String>>(
+        &mut self,
+        pairs: impl IntoIterator<Item = (K, V)>,
+    ) {
+        self.blocks.push(Block::KeyValues(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        ));
+    }
+
+    /// Render as plain text with box-drawn tables
+    pub fn render_text(&self) -> String {
+        let mut output = String::new();
+        for block in &self.blocks {
+            match block {
+                Block::Prose(text) => output.push_str(text),
+                Block::Table(table) => output.push_str(&table.to_string()),
+                Block::KeyValues(pairs) => {
+                    for (key, value) in pairs {
+                        output.push_str(&format!("* {}: {}\n", key, value));
+                    }
+                }
+            }
+        }
+        output
+    }
+
+    /// Render as Markdown, with captions in bold above pipe tables
+    pub fn render_markdown(&self) -> String {
+        let mut output = String::new();
+        for block in &self.blocks {
+            match block {
+                Block::Prose(text) => output.push_str(text),
+                // The trailing blank line ends the table before any following text
+                Block::Table(table) => output.push_str(&format!(
+                    "**{}**\n\n{}\n",
+                    table.caption,
+                    table.to_markdown()
+                )),
+                Block::KeyValues(pairs) => {
+                    for (key, value) in pairs {
+                        output.push_str(&format!("* **{}:** {}\n", key, value));
+                    }
+                }
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Report {
+        let mut report = Report::default();
+        report.prose("Inputs:\n");
+        report.key_values([("Forecast", "$1,000.00")]);
+        let mut table = TableData::new("Table 1: Rates", ["Scenario", "Rate"]);
+        table.add_row(vec!["Low | Vol".to_string(), "20.0%".to_string()]);
+        report.table(table);
+        report
+    }
+
+    #[test]
+    fn test_text_and_markdown_render_the_same_blocks() {
+        let report = sample();
+
+        let text = report.render_text();
+        assert!(text.starts_with("Inputs:\n* Forecast: $1,000.00\nTable 1: Rates\n\n╭"));
+        assert!(text.contains("│ Low | Vol"));
+
+        let markdown = report.render_markdown();
+        assert!(markdown.contains("* **Forecast:** $1,000.00\n"));
+        assert!(markdown.contains("**Table 1: Rates**\n\n| Scenario | Rate |\n|---|---|\n"));
+        assert!(markdown.contains("| Low \\| Vol | 20.0% |\n"));
+    }
+}
@@ -1,76 +1,692 @@
 //! Report generation module for creating comprehensive financial analysis reports.
 
+use crate::error::ModelError;
+use crate::json::JsonValue;
 use crate::model::*;
+use crate::portfolio::PortfolioResult;
+use crate::rate::Rate;
+use crate::rate_matrix::RateMatrix;
+use crate::report::{Block, Report, TableData};
+use crate::valuation::{
+    AGREEMENT_BAND, central_likelihood, discount_curve, horizon_cash_flow, longest_payout_days,
+    pct_change, scenario_percentiles, value_at_risk,
+};
 use comfy_table::modifiers::UTF8_ROUND_CORNERS;
-use comfy_table::{Table, presets::UTF8_FULL};
+use comfy_table::{Cell, Color, Table, presets::UTF8_FULL};
 use num_format::{Locale, ToFormattedString};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-/// Generate the complete financial report
-pub fn generate_full_report(data: &ReportData) -> String {
-    let mut report = String::new();
+/// Version of the valuation model, reported in every output format
+pub const MODEL_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-    // Header
-    report.push_str(&format!("{}\n", "=".repeat(82)));
-    report
-        .push_str("  Valuation and Sensitivity Analysis of Tokenized Future Advertising Revenue\n");
-    report.push_str(&format!("{}\n\n", "=".repeat(82)));
+/// Version of the JSON report structure; bump whenever fields are added,
+/// removed or change meaning
+pub const JSON_SCHEMA_VERSION: u32 = 15;
+
+/// Rendering options for the text report
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions {
+    /// Replace absolute currency figures with index points (adjusted baseline = 100)
+    pub redact: bool,
+    /// Additional locale in which to repeat the key figures as a footnote
+    pub secondary_locale: Option<Locale>,
+    /// Locale for audience counts; `None` follows the currency locale (en)
+    pub audience_locale: Option<Locale>,
+    /// Display labels overriding the scenario names
+    pub labels: ScenarioLabels,
+    /// Present only this lift scenario's tables and matrix
+    pub only_lift: Option<LiftScenario>,
+    /// Which inputs were set explicitly, to mark user vs default values
+    pub input_sources: Option<InputSources>,
+    /// Percentiles (0-100, ascending) of the scenario distribution to tabulate
+    pub percentiles: Vec<f64>,
+    /// Where the risk-free rate came from, when it was looked up rather than supplied
+    pub risk_free_rate_source: Option<String>,
+    /// Annotate each lift matrix with the change between adjacent volatility columns
+    pub show_step_deltas: bool,
+    /// Follow each lift-matrix value with its change over the matching baseline cell
+    pub since_baseline: bool,
+    /// Footnote each table that has one with the formula behind its figures
+    pub show_formula: bool,
+    /// List the scenarios by descending present value, at most this many
+    pub ranked: Option<usize>,
+    /// Sections to render, in order; `None` renders every section in default order
+    pub sections: Option<Vec<ReportSection>>,
+    /// Shade the valuation matrices as heatmaps in this style
+    pub heatmap: Option<HeatmapStyle>,
+    /// Units for the discount-rate components in Table 1
+    pub rate_components_as: RateComponentUnits,
+}
+
+/// Client-facing display labels for the scenario enums.
+///
+/// Each list, when present, supplies one label per scenario in `all()` order;
+/// unset lists fall back to the enums' `Display` names.
+#[derive(Debug, Clone, Default)]
+pub struct ScenarioLabels {
+    payout: Option<Vec<String>>,
+    volatility: Option<Vec<String>>,
+    lift: Option<Vec<String>>,
+}
+
+impl ScenarioLabels {
+    /// Build labels, checking each list has one entry per scenario
+    pub fn new(
+        payout: Option<Vec<String>>,
+        volatility: Option<Vec<String>>,
+        lift: Option<Vec<String>>,
+    ) -> Result<Self, ModelError> {
+        check_label_count("payout", &payout, PayoutScenario::all().len())?;
+        check_label_count("volatility", &volatility, VolatilityScenario::all().len())?;
+        check_label_count("lift", &lift, LiftScenario::all().len())?;
+        Ok(Self {
+            payout,
+            volatility,
+            lift,
+        })
+    }
+
+    /// Display label for a payout scenario
+    pub fn payout(&self, payout: PayoutScenario) -> String {
+        lookup_label(&self.payout, PayoutScenario::all(), payout)
+    }
+
+    /// Display label for a volatility scenario
+    pub fn volatility(&self, volatility: VolatilityScenario) -> String {
+        lookup_label(&self.volatility, VolatilityScenario::all(), volatility)
+    }
+
+    /// Volatility label annotated with its premium in percent, e.g. "Low Vol (5%)"
+    pub fn volatility_with_premium(&self, volatility: VolatilityScenario, premium: f64) -> String {
+        format!(
+            "{} ({})",
+            self.volatility(volatility),
+            format_premium(premium)
+        )
+    }
+
+    /// Display label for a lift scenario
+    pub fn lift(&self, lift: LiftScenario) -> String {
+        lookup_label(&self.lift, LiftScenario::all(), lift)
+    }
+
+    /// Display label for a full scenario triple
+    pub fn scenario(&self, id: ScenarioId) -> String {
+        let lift = id
+            .lift
+            .map(|lift| self.lift(lift))
+            .unwrap_or_else(|| "Baseline".to_string());
+        format!(
+            "{} / {} / {}",
+            self.payout(id.payout),
+            self.volatility(id.volatility),
+            lift
+        )
+    }
+}
+
+fn check_label_count(
+    kind: &str,
+    labels: &Option<Vec<String>>,
+    expected: usize,
+) -> Result<(), ModelError> {
+    match labels {
+        Some(labels) if labels.len() != expected => Err(ModelError::InvalidInput(format!(
+            "Expected {} {} labels, got {}",
+            expected,
+            kind,
+            labels.len()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn lookup_label<T: PartialEq + std::fmt::Display>(
+    labels: &Option<Vec<String>>,
+    all: &[T],
+    value: T,
+) -> String {
+    labels
+        .as_ref()
+        .zip(all.iter().position(|v| *v == value))
+        .map(|(labels, index)| labels[index].clone())
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// Data and rendering options shared by the section builders
+struct ReportContext<'a> {
+    data: &'a ReportData,
+    options: &'a ReportOptions,
+}
+
+impl ReportContext<'_> {
+    /// Scenario display labels
+    fn labels(&self) -> &ScenarioLabels {
+        &self.options.labels
+    }
+
+    /// Volatility premium of a scenario in percent, as the inputs set it
+    fn volatility_premium(&self, volatility: VolatilityScenario) -> f64 {
+        self.data.discount_rates[&volatility]
+            .volatility_premium
+            .as_percent()
+    }
+
+    /// Column headers for a payout-by-volatility matrix
+    fn volatility_headers(&self) -> Vec<String> {
+        let mut headers = vec!["Payout Timing".to_string()];
+        headers.extend(VolatilityScenario::all().iter().map(|v| {
+            self.labels()
+                .volatility_with_premium(*v, self.volatility_premium(*v))
+        }));
+        headers
+    }
+
+    /// Column headers for the lift scenarios, after a leading set of headers
+    fn lift_headers(&self, leading: &[&str]) -> Vec<String> {
+        let mut headers: Vec<String> = leading.iter().map(|h| h.to_string()).collect();
+        headers.extend(self.lift_scenarios().map(|l| self.labels().lift(l)));
+        headers
+    }
+
+    /// Lift scenarios to present, honoring any single-scenario filter
+    fn lift_scenarios(&self) -> impl Iterator<Item = LiftScenario> + '_ {
+        LiftScenario::all()
+            .iter()
+            .copied()
+            .filter(|lift| self.options.only_lift.is_none_or(|only| only == *lift))
+    }
+
+    /// Payout row used for central figures: 90 days, or the supplied ladder
+    fn central_payout(&self) -> PayoutScenario {
+        if self.data.inputs.payout_ladder.is_some() {
+            PayoutScenario::Laddered
+        } else {
+            PayoutScenario::Day90
+        }
+    }
 
-    // Executive Summary
-    report.push_str(&generate_executive_summary(data));
+    /// Value shown in the valuation matrices: the investor's position when
+    /// one is given, otherwise the whole pool
+    fn matrix_value(&self, result: &ValuationResult) -> f64 {
+        result.position_value.unwrap_or(result.present_value)
+    }
+
+    /// Matrix title suffix naming the position the values are scaled to
+    fn position_caption(&self) -> String {
+        self.data
+            .inputs
+            .ownership_fraction
+            .map(|share| {
+                format!(
+                    " - Your Position ({} of Pool)",
+                    format_percent(share * 100.0, 2)
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// Note explaining why no lift was modeled when every lift scenario adds
+    /// nothing, so the lift matrices would only repeat the baseline
+    fn no_lift_note(&self) -> Option<String> {
+        let assumptions = &self.data.lift_assumptions;
+        let cause = if let Some(fixed) = assumptions.fixed_lift {
+            if fixed != 0.0 {
+                return None;
+            }
+            "fixed lift = 0"
+        } else if assumptions.investor_count == 0.0 {
+            "investor_count = 0"
+        } else if assumptions.lift_per_investor == 0.0 {
+            "lift_per_investor = 0"
+        } else if assumptions.incremental_rpm == 0.0 {
+            "incremental RPM = 0"
+        } else {
+            return None;
+        };
+        Some(format!("No investor lift modeled ({})", cause))
+    }
 
-    // Section 1: Methodology Overview
-    report.push_str(&generate_methodology_section());
+    /// Format a currency amount, or its index relative to the adjusted baseline when redacting
+    fn currency(&self, value: f64) -> String {
+        self.currency_in(value, &Locale::en)
+    }
 
-    // Section 2: Model Assumptions and Inputs
-    report.push_str(&generate_assumptions_section(data));
+    /// Format an audience count, rounded, with the audience locale's digit grouping
+    fn audience(&self, count: f64) -> String {
+        let locale = self.options.audience_locale.as_ref().unwrap_or(&Locale::en);
+        (count.round() as i64).to_formatted_string(locale)
+    }
 
-    // Section 3: Risk-Adjusted Discount Rates
-    report.push_str(&generate_discount_rates_section(data));
+    /// Format a currency amount using the given locale's digit grouping, in
+    /// the display currency when one is given
+    fn currency_in(&self, value: f64, locale: &Locale) -> String {
+        if self.options.redact {
+            format_index(value, self.data.summary.adjusted_baseline)
+        } else {
+            match &self.data.inputs.display_currency {
+                Some(currency) => format_money(currency.convert(value), locale, &currency.symbol()),
+                None => format_currency_in(value, locale),
+            }
+        }
+    }
 
-    // Section 4: Baseline Valuation Analysis
-    report.push_str(&generate_baseline_valuation_section(data));
+    /// Format a per-unit price such as an RPM to the cent, in the display
+    /// currency when one is given
+    fn price(&self, value: f64) -> String {
+        match &self.data.inputs.display_currency {
+            Some(currency) => format!(
+                "{}{}",
+                currency.symbol(),
+                format_decimal(currency.convert(value), 2)
+            ),
+            None => format!("${}", format_decimal(value, 2)),
+        }
+    }
+}
 
-    // Section 5: Investor Lift Model and Analysis
-    report.push_str(&generate_lift_model_section(data));
+/// Generate the complete financial report
+pub fn generate_full_report(data: &ReportData, options: &ReportOptions) -> String {
+    build_report(data, options).render_text()
+}
 
-    // Section 6: Full Valuation Analysis
-    report.push_str(&generate_full_valuation_section(data));
+/// Generate the complete financial report as Markdown
+pub fn generate_markdown_report(data: &ReportData, options: &ReportOptions) -> String {
+    build_report(data, options).render_markdown()
+}
 
-    // Section 7: Key Insights and Risk Factors
-    report.push_str(&generate_insights_section(data));
+/// Lay out the complete financial report as format-neutral blocks
+pub fn build_report(data: &ReportData, options: &ReportOptions) -> Report {
+    let ctx = ReportContext { data, options };
+    let mut report = Report::default();
 
-    // Section 8: Conclusion
-    report.push_str(&generate_conclusion_section(data));
+    // Header
+    report.prose(format!(
+        "{}\n  Valuation and Sensitivity Analysis of Tokenized Future Advertising Revenue\n{}\n\n",
+        "=".repeat(82),
+        "=".repeat(82)
+    ));
 
+    let sections = options.sections.as_deref().unwrap_or(ReportSection::all());
+    for section in sections {
+        render_section(*section, &ctx, &mut report);
+    }
+    if options.show_formula {
+        add_formula_footnotes(&data.inputs, &mut report);
+    }
     report
 }
 
-fn generate_executive_summary(data: &ReportData) -> String {
+/// Formula behind a table's figures, keyed by the title in its caption. The
+/// formula follows the inputs' conventions, and tables whose figures come
+/// from supplied rates or a fixed lift have none.
+fn table_formula(inputs: &ValuationInputs, caption: &str) -> Option<String> {
+    let (_, title) = caption.split_once(": ")?;
+    let years = match inputs.day_count {
+        DayCount::Actual365 => "days/365",
+        DayCount::Actual360 => "days/360",
+        DayCount::Thirty360 => "(days/30)×30/360",
+    };
+    let present_value = match inputs.compounding {
+        Compounding::Discrete => format!("PV = CF / (1+r)^({})", years),
+        Compounding::Continuous => format!("PV = CF × e^(-r × {})", years),
+    };
+    let rate = match &inputs.rate_formula {
+        _ if inputs.discount_rate_overrides.is_some() || inputs.rate_matrix.is_some() => None,
+        Some(formula) => Some(format!("total = {}", formula)),
+        None => Some("total = rf + vol_premium + platform_premium".to_string()),
+    };
+    let audience = inputs
+        .fixed_lift
+        .is_none()
+        .then(|| "audience = investors × lift_per_investor × activation".to_string());
+    [
+        ("Risk-Adjusted Discount Rates", rate),
+        ("Baseline Valuation Matrix", Some(present_value.clone())),
+        ("Final Valuation Matrix", Some(present_value)),
+        ("Investor Lift Activation Scenarios", audience),
+    ]
+    .into_iter()
+    .find(|(prefix, _)| title.starts_with(prefix))
+    .and_then(|(_, formula)| formula)
+}
+
+/// Follow every table that has a formula with it as a footnote
+fn add_formula_footnotes(inputs: &ValuationInputs, report: &mut Report) {
+    let blocks = std::mem::take(&mut report.blocks);
+    for block in blocks {
+        let formula = match &block {
+            Block::Table(table) => table_formula(inputs, &table.caption),
+            _ => None,
+        };
+        report.blocks.push(block);
+        if let Some(formula) = formula {
+            report.prose(format!("\n\nFormula: {}\n", formula));
+        }
+    }
+}
+
+/// Append a section's blocks, or nothing when it does not apply to the data
+fn render_section(section: ReportSection, ctx: &ReportContext, report: &mut Report) {
+    let data = ctx.data;
+    // Baseline-only reports stop after the floor valuation
+    let lift_section = matches!(
+        section,
+        ReportSection::Lift
+            | ReportSection::Matrix
+            | ReportSection::Insights
+            | ReportSection::Conclusion
+    );
+    if lift_section && data.inputs.baseline_only {
+        return;
+    }
+
+    match section {
+        ReportSection::Summary => report.prose(generate_executive_summary(ctx)),
+        ReportSection::Warnings => report.prose(generate_warnings_section(ctx)),
+        ReportSection::Methodology => report.prose(generate_methodology_section()),
+        ReportSection::Assumptions => generate_assumptions_section(ctx, report),
+        ReportSection::DiscountRates => generate_discount_rates_section(ctx, report),
+        ReportSection::Baseline => generate_baseline_valuation_section(ctx, report),
+        ReportSection::Lift => generate_lift_model_section(ctx, report),
+        ReportSection::Matrix => generate_full_valuation_section(ctx, report),
+        ReportSection::Insights => generate_insights_section(ctx, report),
+        ReportSection::Conclusion => report.prose(generate_conclusion_section(ctx)),
+        ReportSection::Appendix => {
+            if let Some(comparison) = &data.method_comparison {
+                generate_method_comparison_appendix(ctx, comparison, report);
+            }
+            if let Some(comparison) = &data.day_count_comparison {
+                generate_day_count_appendix(ctx, comparison, report);
+            }
+        }
+        ReportSection::Footnote => {
+            if let Some(locale) = &ctx.options.secondary_locale {
+                generate_secondary_locale_footnote(ctx, locale, report);
+            }
+        }
+    }
+}
+
+/// Renders the full report with default options
+impl std::fmt::Display for ReportData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&generate_full_report(self, &ReportOptions::default()))
+    }
+}
+
+fn generate_executive_summary(ctx: &ReportContext) -> String {
+    let data = ctx.data;
+    // Worded by sign, so a lift that costs value never reads as an increase
+    let lift_line = match data.summary.lift_impact {
+        Some(impact) if impact < 0.0 => format!(
+            "Moving from a Low to a High Lift scenario changes the valuation by {}.",
+            format_percent(impact, 1)
+        ),
+        Some(impact) => format!(
+            "Moving from a Low to a High Lift scenario increases the valuation by {}.",
+            format_percent(impact, 1)
+        ),
+        None if data.inputs.baseline_only => {
+            "N/A (lift scenarios skipped in baseline-only mode).".to_string()
+        }
+        None => ZERO_BASE_NOTE.to_string(),
+    };
+    let payout_line = match data.summary.payout_impact {
+        Some(impact) => format!(
+            "Extending the payout cycle from 60 to 120 days reduces the valuation by {}.",
+            format_percent(impact, 1)
+        ),
+        None if data.inputs.payout_ladder.is_some() => LADDER_NOTE.to_string(),
+        None => ZERO_BASE_NOTE.to_string(),
+    };
+
+    let position_line = match (data.summary.position_value, data.inputs.ownership_fraction) {
+        (Some(value), Some(share)) => format!(
+            "* Your position's fair value: {} ({} of the pool's central estimate).\n",
+            ctx.currency(value),
+            format_percent(share * 100.0, 2)
+        ),
+        _ => String::new(),
+    };
+
+    let per_token_line = match data.inputs.token_supply {
+        Some(supply) if supply > 0.0 && !ctx.options.redact => {
+            match &data.inputs.display_currency {
+                Some(currency) => format_per_token_line(
+                    currency.convert(data.summary.central_estimate),
+                    supply,
+                    &currency.symbol(),
+                ),
+                None => format_per_token_line(data.summary.central_estimate, supply, "$"),
+            }
+        }
+        _ => String::new(),
+    };
+
+    let stress_line = match data.implied_stress {
+        Some(stress) => format!(
+            "* Market-Implied Stress: The market price implies stress-level {} (0 keeps the scenario weights as given; positive values shift weight toward higher volatility).\n",
+            format_decimal(stress, 2)
+        ),
+        None => String::new(),
+    };
+
     format!(
         r#"Executive Summary & Strategic Recommendations
 --------------------------------------------
 This report presents a comprehensive financial model for the valuation of a novel digital asset: tokenized quarterly advertising revenue. The analysis employs discounted cash flow (DCF) methodology with multi-scenario sensitivity analysis to establish fair market value ranges under varying market conditions and investor participation levels.
 
-* Absolute Valuation Range: The fair market value for the total token pool lies between a low of {} and a high of {}.
-* Central Estimate ("Most Likely" Valuation): The most probable fair market value is estimated to be {}.
-* Analysis of Key Value Drivers and Sensitivities:
-    * Market Volatility: Moving from {:.0}% to {:.0}% volatility decreases the asset's valuation by {:.1}%.
-    * Investor Lift: Moving from a Low to a High Lift scenario increases the valuation by {:.1}%.
-    * Payout Cycle: Extending the payout cycle from 60 to 120 days reduces the valuation by {:.1}%.
+{}{}* Absolute Valuation Range: The fair market value for the total token pool lies between a low of {} ({}) and a high of {} ({}).
+* Central Estimate ("Most Likely" Valuation): The most probable fair market value is estimated to be {}. Central discounts a {} face value to {} present value.
+{}
+{}
+{}* Analysis of Key Value Drivers and Sensitivities:
+    * Market Volatility: Moving from {} to {} volatility decreases the asset's valuation by {}.
+    * Investor Lift: {}
+    * Payout Cycle: {}
 
 "#,
-        format_currency(data.summary.min_valuation),
-        format_currency(data.summary.max_valuation),
-        format_currency(data.summary.central_estimate),
-        data.summary.best_volatility.percentage(),
-        data.summary.worst_volatility.percentage(),
-        data.summary.volatility_impact,
-        data.summary.lift_impact,
-        data.summary.payout_impact,
+        position_line,
+        per_token_line,
+        ctx.currency(data.summary.min_valuation),
+        ctx.labels().scenario(data.summary.min_scenario),
+        ctx.currency(data.summary.max_valuation),
+        ctx.labels().scenario(data.summary.max_scenario),
+        ctx.currency(data.summary.central_estimate),
+        ctx.currency(data.summary.central_face_value),
+        ctx.currency(data.summary.central_estimate),
+        format_investable_range_line(ctx),
+        format_likelihood_line(data),
+        stress_line,
+        format_premium(ctx.volatility_premium(data.summary.best_volatility)),
+        format_premium(ctx.volatility_premium(data.summary.worst_volatility)),
+        format_optional_percentage(data.summary.volatility_impact),
+        lift_line,
+        payout_line,
+    )
+}
+
+/// Sensitivity note when the payout scenarios are replaced by a ladder
+const LADDER_NOTE: &str = "N/A (payout follows the supplied tranche ladder).";
+
+/// Sensitivity note when the valuation a change is measured from is zero
+const ZERO_BASE_NOTE: &str = "N/A (no percentage change from a zero valuation).";
+
+/// Executive summary line giving the downside and upside corners around the
+/// central estimate, which unlike the absolute range need not be symmetric
+fn format_investable_range_line(ctx: &ReportContext) -> String {
+    let summary = &ctx.data.summary;
+    let versus_central = |value: f64| format_change_from_central(summary.central_estimate, value);
+    let (low_lift, high_lift) = if ctx.data.inputs.baseline_only {
+        ("", "")
+    } else {
+        (", low lift", ", high lift")
+    };
+    format!(
+        "* Investable Range: Central {}, with downside to {} ({}; longest payout, extreme volatility{}) and upside to {} ({}; shortest payout, low volatility{}).",
+        ctx.currency(summary.central_estimate),
+        ctx.currency(summary.downside),
+        versus_central(summary.downside),
+        low_lift,
+        ctx.currency(summary.upside),
+        versus_central(summary.upside),
+        high_lift
     )
 }
 
+/// Executive summary line stating how likely the central scenario is, and
+/// where the central estimate sits, under the scenario weights
+fn format_likelihood_line(data: &ReportData) -> String {
+    let likelihood = central_likelihood(data);
+    format!(
+        "* Likelihood: Under {} scenario weights, the central scenario itself carries {} of the probability, and the value falls at or below the central estimate with {} probability.",
+        if data.inputs.scenario_weights.is_weighted() {
+            "the supplied"
+        } else {
+            "equal"
+        },
+        format_percent(likelihood.central_probability * 100.0, 1),
+        format_percent(likelihood.at_or_below_central * 100.0, 1)
+    )
+}
+
+/// Most decimals shown for a per-token value before a rounding residual is noted
+const MAX_PER_TOKEN_DECIMALS: usize = 10;
+
+/// A per-token value rounded for display so that it multiplies back to the total
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PerTokenDisplay {
+    /// Per-token value rounded to `decimals`
+    value: f64,
+    /// Decimal places shown
+    decimals: usize,
+    /// Total minus displayed per-token value times supply
+    residual: f64,
+}
+
+/// Round `total / supply` to the fewest decimals (at least two) at which the
+/// displayed per-token value times the supply reconstructs `total` within one
+/// cent. Past `MAX_PER_TOKEN_DECIMALS` the remaining residual is reported.
+fn reconcile_per_token(total: f64, supply: f64) -> PerTokenDisplay {
+    let per_token = total / supply;
+    let display = |decimals| {
+        let value = round_half_away(per_token, decimals);
+        PerTokenDisplay {
+            value,
+            decimals,
+            residual: total - value * supply,
+        }
+    };
+    (2..=MAX_PER_TOKEN_DECIMALS)
+        .map(display)
+        .find(|d| d.residual.abs() <= 0.01)
+        .unwrap_or_else(|| display(MAX_PER_TOKEN_DECIMALS))
+}
+
+/// Executive summary line with the central estimate per token, in the
+/// currency whose amounts `symbol` prefixes
+fn format_per_token_line(total: f64, supply: f64, symbol: &str) -> String {
+    let per_token = reconcile_per_token(total, supply);
+    let tokens = if supply.fract() == 0.0 {
+        (supply as i64).to_formatted_string(&Locale::en)
+    } else {
+        supply.to_string()
+    };
+    let reconciliation = if per_token.residual.abs() <= 0.01 {
+        "multiplied by the supply, this reconstructs the central estimate to the cent".to_string()
+    } else {
+        format!(
+            "multiplied by the supply, this leaves a {}{} rounding residual against the central estimate",
+            symbol,
+            format_decimal(per_token.residual, 2)
+        )
+    };
+    format!(
+        "* Per-token fair value: {}{} across {} tokens ({}).\n",
+        symbol,
+        format_decimal(per_token.value, per_token.decimals),
+        tokens,
+        reconciliation
+    )
+}
+
+fn generate_warnings_section(ctx: &ReportContext) -> String {
+    if ctx.data.warnings.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from("Model Warnings\n--------------\n");
+    for warning in &ctx.data.warnings {
+        section.push_str(&format!("* {}\n", format_warning(ctx, warning)));
+    }
+    section.push('\n');
+    section
+}
+
+/// A model warning as the report shows it, with any amounts in the display
+/// currency, or as index points when redacting
+fn format_warning(ctx: &ReportContext, warning: &ModelWarning) -> String {
+    match warning {
+        ModelWarning::Note(message) => message.clone(),
+        ModelWarning::MarketPriceOutOfRange {
+            market_price,
+            low,
+            high,
+        } => format!(
+            "Market price {} lies outside the {} to {} that stressing the volatility scenarios can rationalize",
+            ctx.currency(*market_price),
+            ctx.currency(*low),
+            ctx.currency(*high)
+        ),
+        ModelWarning::LiftDominance {
+            period,
+            threshold,
+            adjusted_baseline,
+            lifts,
+        } => format!(
+            "{} lift exceeds {} the adjusted baseline ({}) in {}; check that lift inputs are quarterly, not monthly",
+            period,
+            format_multiple(Some(*threshold)),
+            ctx.currency(*adjusted_baseline),
+            lifts
+                .iter()
+                .map(|(lift, amount)| format!("{} ({})", lift, ctx.currency(*amount)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Every model warning rendered as the report renders it, for printing
+/// alongside the report
+pub fn format_warnings(data: &ReportData, options: &ReportOptions) -> Vec<String> {
+    let ctx = ReportContext { data, options };
+    data.warnings
+        .iter()
+        .map(|warning| format_warning(&ctx, warning))
+        .collect()
+}
+
+fn generate_secondary_locale_footnote(ctx: &ReportContext, locale: &Locale, report: &mut Report) {
+    let summary = &ctx.data.summary;
+    report.prose(format!("\n---\nKey Figures ({} locale):\n", locale.name()));
+    report.key_values([
+        (
+            "Central Estimate",
+            ctx.currency_in(summary.central_estimate, locale),
+        ),
+        (
+            "Minimum Valuation",
+            ctx.currency_in(summary.min_valuation, locale),
+        ),
+        (
+            "Maximum Valuation",
+            ctx.currency_in(summary.max_valuation, locale),
+        ),
+    ]);
+}
+
 fn generate_methodology_section() -> &'static str {
     r#"Section 1: Methodology Overview
 ================================
@@ -85,53 +701,391 @@ This valuation employs industry-standard discounted cash flow (DCF) analysis ada
 "#
 }
 
-fn generate_assumptions_section(data: &ReportData) -> String {
-    format!(
+fn generate_assumptions_section(ctx: &ReportContext, report: &mut Report) {
+    let data = ctx.data;
+    report.prose(
         r#"Section 2: Model Assumptions and Inputs
 ========================================
 
 Core Financial Inputs:
-* Publisher's Raw Quarterly Revenue Forecast: {}
-* Platform Adjustment Factor: {:.1}%
-* Adjusted Baseline Revenue: {}
-* Risk-Free Rate: {:.1}%
-* Platform/Publisher Risk Premium: {:.1}%
+"#,
+    );
+    let forecast_label = format!("Publisher's Raw {} Revenue Forecast", data.inputs.period);
+    report.key_values([
+        (
+            forecast_label.as_str(),
+            ctx.currency(data.inputs.raw_forecast),
+        ),
+        (
+            "Platform Adjustment Factor",
+            format_percent(data.inputs.platform_adjustment_factor * 100.0, 1),
+        ),
+        (
+            "Adjusted Baseline Revenue",
+            ctx.currency(data.summary.adjusted_baseline),
+        ),
+        (
+            "Risk-Free Rate",
+            format!(
+                "{}{}",
+                format_percent(data.inputs.risk_free_rate.as_percent(), 1),
+                ctx.options
+                    .risk_free_rate_source
+                    .as_ref()
+                    .map(|source| format!(" (source: {})", source))
+                    .unwrap_or_default()
+            ),
+        ),
+        (
+            "Platform/Publisher Risk Premium",
+            format_percent(data.inputs.platform_risk_premium.as_percent(), 1),
+        ),
+    ]);
+    report.prose("\nScenario Parameters:\n");
+    report.key_values([
+        (
+            "Volatility Scenarios",
+            VolatilityScenario::all()
+                .iter()
+                .map(|v| {
+                    ctx.labels()
+                        .volatility_with_premium(*v, ctx.volatility_premium(*v))
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        (
+            "Payout Timing",
+            match &data.inputs.payout_ladder {
+                Some(ladder) => format!("Laddered ({})", ladder),
+                None if data.inputs.monthly_breakdown => format!(
+                    "{} (each quarter paid in three monthly installments, the last on the payout date)",
+                    PayoutScenario::all()
+                        .iter()
+                        .map(|p| ctx.labels().payout(*p))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                None => PayoutScenario::all()
+                    .iter()
+                    .map(|p| ctx.labels().payout(*p))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            },
+        ),
+        (
+            "Lift Payout Timing",
+            match data.inputs.lift_payout_days {
+                Some(days) => format!("{} days", days),
+                None => "Same as payout timing".to_string(),
+            },
+        ),
+        ("Day-Count Convention", data.inputs.day_count.to_string()),
+        ("Compounding", data.inputs.compounding.to_string()),
+        (
+            "Valuation Horizon",
+            if let Some(stages) = data.inputs.two_stage_growth.filter(|_| data.inputs.quarters > 1)
+            {
+                format!(
+                    "{} {}s growing {}, {} of lift revenue reinvested",
+                    data.inputs.quarters,
+                    data.inputs.period.noun(),
+                    format_two_stage_growth(&stages, data.inputs.period),
+                    format_percent(data.inputs.reinvestment_rate * 100.0, 1)
+                )
+            } else if data.inputs.quarters > 1 {
+                format!(
+                    "{} {}s at {} {} growth, {} of lift revenue reinvested",
+                    data.inputs.quarters,
+                    data.inputs.period.noun(),
+                    format_percent(data.inputs.quarterly_growth_rate * 100.0, 1),
+                    data.inputs.period.to_string().to_lowercase(),
+                    format_percent(data.inputs.reinvestment_rate * 100.0, 1)
+                )
+            } else {
+                format!("Single {}", data.inputs.period.noun())
+            },
+        ),
+        ("Tax Treatment", format_tax_treatment(&data.inputs)),
+        (
+            "Investor Lift Scenarios",
+            if data.inputs.baseline_only {
+                "None (baseline only)".to_string()
+            } else {
+                ctx.lift_scenarios()
+                    .map(|l| ctx.labels().lift(l))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            },
+        ),
+        ("Lift Ramp", data.inputs.lift_ramp.to_string()),
+    ]);
+    report.prose("\nModeling Assumptions:\n");
+    let statements = format_assumptions(data, ctx.options);
+    report.key_values(
+        data.assumptions
+            .iter()
+            .zip(&statements)
+            .map(|(assumption, statement)| (assumption.topic, statement.as_str())),
+    );
+    report.prose("\n");
 
-Scenario Parameters:
-* Volatility Scenarios: Low (2%), Typical (4%), High (6%), Extreme (10%)
-* Payout Timing: 60 days, 90 days, 120 days
-* Investor Lift Scenarios: Low, Medium, High
+    if let Some(floor) = data.inputs.revenue_floor {
+        report.prose(format_revenue_floor_note(ctx, floor));
+    }
+    if let Some(sources) = &ctx.options.input_sources {
+        report.table(build_input_sources_table(ctx, sources));
+        report.prose("\n\n");
+    }
+}
 
-"#,
-        format_currency(data.inputs.raw_forecast),
-        data.inputs.platform_adjustment_factor * 100.0,
-        format_currency(data.summary.adjusted_baseline),
-        data.inputs.risk_free_rate * 100.0,
-        data.inputs.platform_risk_premium * 100.0,
+/// A modeling assumption's statement as the report shows it, with amounts
+/// in the display currency
+fn format_assumption(ctx: &ReportContext, statement: &AssumptionStatement) -> String {
+    match statement {
+        AssumptionStatement::Text(text) => text.clone(),
+        AssumptionStatement::FlatRpm { baseline, lift } => format!(
+            "Flat {} on the baseline audience and {} on lift audience, independent of audience size",
+            ctx.price(*baseline),
+            ctx.price(*lift)
+        ),
+        AssumptionStatement::ForecastConfidence(confidence) => format!(
+            "{} confidence spreads the volatility premiums {} around the typical premium: {}",
+            confidence,
+            format_multiple(Some(confidence.spread())),
+            VolatilityScenario::all()
+                .iter()
+                .map(|&v| format!(
+                    "{} {}",
+                    v,
+                    format_percent(confidence.premium(v).as_percent(), 1)
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        AssumptionStatement::FixedLift { amount, period } => format!(
+            "Fixed {} per {} at medium activation, scaled by each scenario's activation factor",
+            ctx.currency(*amount),
+            period.noun()
+        ),
+        AssumptionStatement::LinearAudience { lift_per_investor } => format!(
+            "Linear: lift audience = investors x {} visitors per investor x activation factor, with no saturation",
+            format_decimal(*lift_per_investor, 1)
+        ),
+        AssumptionStatement::Horizon {
+            quarters,
+            period,
+            growth,
+            two_stage,
+        } => format!(
+            "{} {}s, growing {}",
+            quarters,
+            period.noun(),
+            match two_stage {
+                Some(stages) => format_two_stage_growth(stages, *period),
+                None => format!(
+                    "{} per {}",
+                    format_percent(growth * 100.0, 1),
+                    period.noun()
+                ),
+            }
+        ),
+        AssumptionStatement::TaxedAt { rate } => format!(
+            "Cash flows taxed at {} before discounting",
+            format_percent(rate * 100.0, 1)
+        ),
+    }
+}
+
+/// Every modeling assumption's statement rendered as the report renders it,
+/// in the order of `data.assumptions`
+pub fn format_assumptions(data: &ReportData, options: &ReportOptions) -> Vec<String> {
+    let ctx = ReportContext { data, options };
+    data.assumptions
+        .iter()
+        .map(|assumption| format_assumption(&ctx, &assumption.statement))
+        .collect()
+}
+
+/// Two-stage growth in words, e.g. "3.0% per quarter for 4 quarters, then 1.0%"
+fn format_two_stage_growth(stages: &TwoStageGrowth, period: RevenuePeriod) -> String {
+    format!(
+        "{} per {} for {} {}s, then {}",
+        format_percent(stages.stage1_growth * 100.0, 1),
+        period.noun(),
+        stages.stage1_quarters,
+        period.noun(),
+        format_percent(stages.stage2_growth * 100.0, 1)
+    )
+}
+
+/// Describe whether headline figures are pre- or after-tax
+fn format_tax_treatment(inputs: &ValuationInputs) -> String {
+    let rate = format_percent(inputs.tax_rate * 100.0, 1);
+    match (inputs.after_tax, inputs.tax_rate > 0.0) {
+        (true, _) => format!(
+            "After-tax at {} (cash flows taxed before discounting)",
+            rate
+        ),
+        (false, true) => format!("Pre-tax (after-tax values at {} also computed)", rate),
+        (false, false) => "Pre-tax (no tax rate applied)".to_string(),
+    }
+}
+
+/// Note stating the revenue floor and the scenarios in which it binds.
+///
+/// Whether the floor binds depends only on the lift scenario, so each lift
+/// level is listed once with the quarterly top-up it received.
+fn format_revenue_floor_note(ctx: &ReportContext, floor: f64) -> String {
+    let lifts = std::iter::once(None).chain(ctx.lift_scenarios().map(Some));
+    let floored: Vec<String> = lifts
+        .filter_map(|lift| {
+            let top_up = ctx
+                .data
+                .all_valuations
+                .iter()
+                .find(|v| v.lift_scenario == lift)?
+                .floor_top_up;
+            let label = lift.map_or_else(|| "Baseline".to_string(), |l| ctx.labels().lift(l));
+            (top_up > 0.0).then(|| format!("{} (+{})", label, ctx.currency(top_up)))
+        })
+        .collect();
+    let binding = if floored.is_empty() {
+        "It does not bind in any scenario.".to_string()
+    } else {
+        format!(
+            "It binds, raising {} revenue, in: {}.",
+            ctx.data.inputs.period.to_string().to_lowercase(),
+            floored.join(", ")
+        )
+    };
+    format!(
+        "Revenue Floor: {} per {} (contractual minimum). {}\n\n",
+        ctx.currency(floor),
+        ctx.data.inputs.period.noun(),
+        binding
     )
 }
 
-fn generate_discount_rates_section(data: &ReportData) -> String {
+/// Table marking each model input as user-supplied or left at its default
+fn build_input_sources_table(ctx: &ReportContext, sources: &InputSources) -> TableData {
+    let inputs = &ctx.data.inputs;
+    let forecast_label = format!("Raw {} Revenue Forecast", inputs.period);
+    let rows = [
+        (
+            "forecast",
+            forecast_label.as_str(),
+            ctx.currency(inputs.raw_forecast),
+        ),
+        ("period", "Revenue Period", inputs.period.to_string()),
+        (
+            "risk_free_rate",
+            "Risk-Free Rate",
+            format_percent(inputs.risk_free_rate.as_percent(), 1),
+        ),
+        (
+            "platform_risk_premium",
+            "Platform/Publisher Risk Premium",
+            format_percent(inputs.platform_risk_premium.as_percent(), 1),
+        ),
+        (
+            "platform_adjustment",
+            "Platform Adjustment Factor",
+            format_percent(inputs.platform_adjustment_factor * 100.0, 1),
+        ),
+        (
+            "baseline_audience",
+            "Baseline Monthly Audience",
+            ctx.audience(inputs.baseline_audience),
+        ),
+        ("rpm", "RPM", ctx.price(inputs.rpm)),
+        (
+            "incremental_rpm",
+            "Incremental RPM",
+            ctx.price(inputs.incremental_rpm),
+        ),
+        (
+            "investor_count",
+            "Investor Count",
+            format_investor_count(inputs.investor_count),
+        ),
+        (
+            "lift_per_investor",
+            "Lift per Investor",
+            format_decimal(inputs.lift_per_investor, 1),
+        ),
+        (
+            "day_count",
+            "Day-Count Convention",
+            inputs.day_count.to_string(),
+        ),
+        ("compounding", "Compounding", inputs.compounding.to_string()),
+        ("quarters", "Periods", inputs.quarters.to_string()),
+        (
+            "quarterly_growth_rate",
+            "Growth Rate per Period",
+            format_percent(inputs.quarterly_growth_rate * 100.0, 1),
+        ),
+    ];
+
+    let mut table = TableData::new("Table 0: Input Sources", ["Input", "Value", "Source"]);
+    for (id, label, value) in rows {
+        let source = match id {
+            // Incremental RPM falls back to RPM rather than a fixed default
+            "incremental_rpm" if !sources.is_explicit(id) => sources.source("rpm"),
+            _ => sources.source(id),
+        };
+        table.add_row(vec![label.to_string(), value, source.to_string()]);
+    }
+    table
+}
+
+fn generate_discount_rates_section(ctx: &ReportContext, report: &mut Report) {
     let mut section = String::from(
         r#"Section 3: Risk-Adjusted Discount Rates
 ========================================
 
-The discount rate calculation follows standard financial theory, incorporating three components:
+"#,
+    );
+    if ctx.data.inputs.rate_matrix.is_some() {
+        section.push_str(
+            "Total discount rates were supplied per payout and volatility scenario \
+from a rate matrix and are used verbatim; the component build-up is not applied.\n",
+        );
+    } else if ctx.data.inputs.discount_rate_overrides.is_some() {
+        section.push_str(
+            "Total discount rates were supplied directly and are used verbatim; \
+the component build-up is not applied.\n",
+        );
+    } else if let Some(formula) = &ctx.data.inputs.rate_formula {
+        section.push_str(&format!(
+            "Total discount rates follow the rate formula {} in place of the component \
+sum, evaluated per volatility scenario with rf, vol_premium and platform_premium \
+set to the components below.\n",
+            formula
+        ));
+    } else {
+        section.push_str(
+            r#"The discount rate calculation follows standard financial theory, incorporating three components:
 1. Risk-Free Rate (baseline return for risk-free investments)
 2. Volatility Premium (compensation for market uncertainty)
 3. Platform/Publisher Risk Premium (specific operational and credit risks)
-
----
 "#,
-    );
+        );
+    }
+    section.push_str("\n---\n");
+    report.prose(section);
 
-    section.push_str(&build_discount_rate_table(data));
-    section.push_str("\n---\n\n");
-    section
+    report.table(build_discount_rate_table(ctx));
+    report.prose("\n---\n\n");
+    if let Some(waterfall) = build_waterfall(ctx) {
+        report.table(waterfall);
+        report.prose("\n---\n\n");
+    }
 }
 
-fn generate_baseline_valuation_section(data: &ReportData) -> String {
-    let mut section = String::from(
+fn generate_baseline_valuation_section(ctx: &ReportContext, report: &mut Report) {
+    report.prose(
         r#"Section 4: Baseline Valuation Analysis (No Investor Lift)
 ==========================================================
 
@@ -141,7 +1095,14 @@ The baseline valuation represents the present value of the adjusted revenue stre
 "#,
     );
 
-    section.push_str(&build_baseline_valuation_table(data));
+    match ctx.options.heatmap {
+        Some(style) => report.prose(build_valuation_heatmap(ctx, None, style)),
+        None => report.table(build_baseline_valuation_table(ctx)),
+    }
+    report.prose("\n---\n\n");
+    let (retention, weakest_note) = build_retention_table(ctx);
+    report.table(retention);
+    let mut section = weakest_note.unwrap_or_default();
     section.push_str("\n---\n\n");
     section.push_str("Key Observations:\n");
     section
@@ -152,50 +1113,131 @@ The baseline valuation represents the present value of the adjusted revenue stre
     section.push_str(
         "* The impact of timing becomes more pronounced in high-volatility environments.\n\n",
     );
-
-    section
+    report.prose(section);
 }
 
-fn generate_lift_model_section(data: &ReportData) -> String {
+fn generate_lift_model_section(ctx: &ReportContext, report: &mut Report) {
+    let data = ctx.data;
     let assumptions = &data.lift_assumptions;
-    let mut section = format!(
+    report.prose(
         r#"Section 5: Investor Lift Model and Analysis
 ============================================
 
 The investor lift model quantifies potential revenue enhancement driven by the token holder community. Rather than assuming a fixed lift, this model is based on tangible drivers, providing a more robust framework for analysis.
 
 Core Lift Model Assumptions:
-* Total Investor Count: {}
-* Audience Lift per Investor: {:.1} new monthly visitors
-* Baseline Monthly Audience: {} unique visitors
-* Revenue per Thousand Impressions (RPM): ${:.2}
-
+"#,
+    );
+    report.key_values([
+        (
+            "Total Investor Count",
+            format_investor_count(assumptions.investor_count),
+        ),
+        (
+            "Audience Lift per Investor",
+            format!(
+                "{} new monthly visitors",
+                format_decimal(assumptions.lift_per_investor, 1)
+            ),
+        ),
+        (
+            "Baseline Monthly Audience",
+            format!(
+                "{} unique visitors",
+                ctx.audience(assumptions.baseline_audience)
+            ),
+        ),
+        (
+            "Revenue per Thousand Impressions (RPM)",
+            ctx.price(assumptions.rpm),
+        ),
+        (
+            "Incremental Audience RPM",
+            ctx.price(assumptions.incremental_rpm),
+        ),
+    ]);
+    if let Some(fixed) = assumptions.fixed_lift {
+        report.key_values([(
+            format!(
+                "Fixed {} Lift (replaces the drivers above)",
+                data.inputs.period
+            ),
+            format!("{} at medium activation", ctx.currency(fixed)),
+        )]);
+    }
+    report.prose(
+        r#"
 The model calculates the total potential monthly audience lift and then applies an "Activation Factor" to simulate different levels of community engagement and effectiveness.
 
 ---
 "#,
-        assumptions.investor_count.to_formatted_string(&Locale::en),
-        assumptions.lift_per_investor,
-        (assumptions.baseline_audience.round() as i64).to_formatted_string(&Locale::en),
-        assumptions.rpm
     );
 
-    // Table 3: Lift Scenarios
-    section.push_str(&build_lift_scenarios_table(data));
-    section.push_str("\n---\n\n");
+    if let Some(note) = ctx.no_lift_note() {
+        let payback = data
+            .inputs
+            .lift_acquisition_cost
+            .map_or_else(String::new, |cost| {
+                format!(
+                    " The {} lift acquisition cost is never paid back.",
+                    ctx.currency(cost)
+                )
+            });
+        report.prose(format!(
+            "{}; every lift scenario equals the baseline valuation in Table 2.{}\n\n---\n\n",
+            note, payback
+        ));
+        return;
+    }
 
-    // Table 4: Audience Growth
-    section.push_str(&build_audience_growth_table(data));
-    section.push_str("\n---\n\n");
+    for table in [
+        build_lift_scenarios_table(ctx),
+        build_audience_growth_table(ctx),
+        build_revenue_impact_table(ctx),
+    ] {
+        report.table(table);
+        report.prose("\n---\n\n");
+    }
 
-    // Table 5: Revenue Impact
-    section.push_str(&build_revenue_impact_table(data));
-    section.push_str("\n---\n\n");
+    if let Some(comparison) = &data.lift_model_comparison {
+        report.table(build_lift_model_comparison_table(ctx, comparison));
+        report.prose("\n---\n\n");
+    }
+}
 
-    section
+/// Central estimate under driver-based and fixed lift, with their difference
+fn build_lift_model_comparison_table(
+    ctx: &ReportContext,
+    comparison: &LiftModelComparison,
+) -> TableData {
+    let mut table = TableData::new(
+        "Table 5a: Central Estimate by Lift Model",
+        ["Lift Model", "Central PV"],
+    );
+    table.add_row(vec![
+        "Driver-Based".to_string(),
+        ctx.currency(comparison.driver_based),
+    ]);
+    table.add_row(vec![
+        format!(
+            "Fixed ({} per {})",
+            ctx.currency(comparison.fixed_lift),
+            ctx.data.inputs.period.noun()
+        ),
+        ctx.currency(comparison.fixed),
+    ]);
+    table.add_row(vec![
+        "Difference (Fixed - Driver-Based)".to_string(),
+        format!(
+            "{} ({})",
+            ctx.currency(comparison.difference()),
+            format_signed_percent(comparison.gap_percentage(), 2)
+        ),
+    ]);
+    table
 }
 
-fn generate_full_valuation_section(data: &ReportData) -> String {
+fn generate_full_valuation_section(ctx: &ReportContext, report: &mut Report) {
     let mut section = String::from(
         r#"Section 6: Full Valuation Analysis with Investor Lift
 ======================================================
@@ -205,28 +1247,125 @@ The following matrices present the complete valuation analysis incorporating inv
 "#,
     );
 
-    for lift_scenario in LiftScenario::all() {
-        section.push_str(&format!(
-            "\n### Valuation Matrix: {} Scenario\n\n",
-            lift_scenario
+    let lift_scenarios: Vec<LiftScenario> = match ctx.no_lift_note() {
+        Some(note) => {
+            section.push_str(&format!(
+                "{}; see Table 2 for the valuation matrix.\n\n---\n\n",
+                note
+            ));
+            Vec::new()
+        }
+        None => ctx.lift_scenarios().collect(),
+    };
+    report.prose(section);
+    for lift_scenario in lift_scenarios {
+        report.prose(format!(
+            "\n### Valuation Matrix: {} Scenario\n\n---\n",
+            ctx.labels().lift(lift_scenario)
         ));
-        section.push_str("---\n");
-        section.push_str(&build_valuation_table(data, *lift_scenario));
-        section.push_str("\n---\n\n");
+        match ctx.options.heatmap {
+            Some(style) => report.prose(build_valuation_heatmap(ctx, Some(lift_scenario), style)),
+            None => report.table(build_valuation_table(ctx, lift_scenario)),
+        }
+        if ctx.options.show_step_deltas {
+            report.prose(format_step_deltas(ctx, lift_scenario));
+        }
+        report.prose("\n---\n\n");
     }
 
-    section
+    report.table(build_range_bars(ctx));
+    report.prose("\n---\n\n");
+
+    if let Some(values) = &ctx.data.lift_option
+        && ctx.no_lift_note().is_none()
+    {
+        report.table(build_lift_option_table(ctx, values));
+        report.prose("\n---\n\n");
+    }
+
+    if let Some(rows) = ctx.options.ranked {
+        report.table(build_ranked_table(ctx, rows));
+        report.prose("\n---\n\n");
+    }
 }
 
-fn generate_insights_section(data: &ReportData) -> String {
-    format!(
+/// Every scenario, highest present value first, cut to the top `rows`.
+/// Ties keep grid order.
+fn build_ranked_table(ctx: &ReportContext, rows: usize) -> TableData {
+    let mut ranked = ctx.data.all_valuations.clone();
+    ranked.sort_by_key(|v| v.scenario());
+    ranked.sort_by(|a, b| b.present_value.total_cmp(&a.present_value));
+    ranked.truncate(rows);
+
+    let caption = if ranked.len() < ctx.data.all_valuations.len() {
+        format!(
+            "Table 9b: Top {} of {} Scenarios by Present Value{}",
+            ranked.len(),
+            ctx.data.all_valuations.len(),
+            ctx.position_caption()
+        )
+    } else {
+        format!(
+            "Table 9b: Scenarios Ranked by Present Value{}",
+            ctx.position_caption()
+        )
+    };
+    let mut table = TableData::new(caption, ["Rank", "Scenario", "Present Value", "vs Central"]);
+    let central = ctx.data.summary.central_estimate;
+    for (index, result) in ranked.iter().enumerate() {
+        table.add_row(vec![
+            (index + 1).to_string(),
+            ctx.labels().scenario(result.scenario()),
+            ctx.currency(ctx.matrix_value(result)),
+            format_change_from_central(central, result.present_value),
+        ]);
+    }
+    table
+}
+
+/// Present value of the lift cash flow alone, one row per lift scenario and
+/// payout timing
+fn build_lift_option_table(ctx: &ReportContext, values: &[LiftOptionValue]) -> TableData {
+    let mut headers = vec!["Lift Scenario".to_string()];
+    headers.extend(ctx.volatility_headers());
+    let mut table = TableData::new(
+        "Table 9a: Growth Option Value (Lift Cash Flow Only)",
+        headers,
+    );
+
+    for lift in ctx.lift_scenarios() {
+        for &payout in ctx.data.inputs.payout_scenarios() {
+            let mut row = vec![ctx.labels().lift(lift), ctx.labels().payout(payout)];
+            row.extend(VolatilityScenario::all().iter().map(|&volatility| {
+                let scenario = ScenarioId {
+                    payout,
+                    volatility,
+                    lift: Some(lift),
+                };
+                values
+                    .iter()
+                    .find(|value| value.scenario == scenario)
+                    .map_or_else(
+                        || "N/A".to_string(),
+                        |value| ctx.currency(value.present_value),
+                    )
+            }));
+            table.add_row(row);
+        }
+    }
+    table
+}
+
+fn generate_insights_section(ctx: &ReportContext, report: &mut Report) {
+    let data = ctx.data;
+    report.prose(format!(
         r#"Section 7: Key Insights and Risk Factors
 =========================================
 
 Valuation Sensitivities:
-1. Time Value Impact: Each 30-day delay in payout reduces valuation by approximately {:.1}%.
-2. Volatility Premium: Moving from low to extreme volatility reduces value by {:.1}%.
-3. Investor Lift Potential: Active investor participation can enhance value by up to {:.1}%.
+1. Time Value Impact: {}
+2. Volatility Premium: Moving from low to extreme volatility reduces value by {}.
+3. Investor Lift Potential: {}
 
 Risk Considerations:
 * Platform Risk: Operational challenges could impact revenue realization.
@@ -235,96 +1374,532 @@ Risk Considerations:
 * Participation Risk: Investor lift depends on community engagement.
 
 Investment Implications:
+* Discounting reduces the {} face value by {} to {}.
+* 5% VaR: {}.
+* Scenario agreement: {} of the weighted scenario distribution falls within {} of the central estimate.
+* Implied {} revenue multiple: {} (annualized: {}).{}
 * The wide valuation range ({} to {}) reflects the nascent nature of tokenized revenue assets.
 * The central estimate of {} assumes moderate market conditions and medium investor participation.
 * Investors should consider their risk tolerance and market outlook when evaluating entry points.
 
 "#,
-        data.summary.payout_impact / 2.0,
-        data.summary.volatility_impact,
-        data.summary.lift_impact,
-        format_currency(data.summary.min_valuation),
-        format_currency(data.summary.max_valuation),
-        format_currency(data.summary.central_estimate),
-    )
-}
+        format_time_value_impact(data),
+        format_optional_percentage(data.summary.volatility_impact),
+        format_lift_potential(data.summary.lift_impact),
+        ctx.currency(data.summary.central_face_value),
+        format_optional_percentage(discounting_haircut(&data.summary)),
+        ctx.currency(data.summary.central_estimate),
+        format_value_at_risk(ctx),
+        format_percent(data.summary.scenario_agreement * 100.0, 0),
+        format_percent(AGREEMENT_BAND * 100.0, 0),
+        data.inputs.period.to_string().to_lowercase(),
+        format_multiple(data.summary.quarterly_revenue_multiple),
+        format_multiple(data.summary.annual_revenue_multiple),
+        format_value_ratio_line(ctx),
+        ctx.currency(data.summary.min_valuation),
+        ctx.currency(data.summary.max_valuation),
+        ctx.currency(data.summary.central_estimate),
+    ));
+    report.prose(format_haircut_decomposition(ctx));
 
-fn generate_conclusion_section(data: &ReportData) -> String {
-    // Basic timestamp generation
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_secs())
-        .unwrap_or(0);
+    if !ctx.options.percentiles.is_empty() {
+        report.table(build_percentile_table(ctx));
+        report.prose("\n---\n\n");
+    }
+}
 
-    format!(
-        r#"Section 8: Conclusion
-=====================
+/// Lift sensitivity line, worded by the sign of the low-to-high lift impact
+fn format_lift_potential(impact: Option<f64>) -> String {
+    match impact {
+        Some(impact) if impact < 0.0 => format!(
+            "Moving from low to high investor participation changes value by {}.",
+            format_percent(impact, 1)
+        ),
+        impact => format!(
+            "Active investor participation can enhance value by up to {}.",
+            format_optional_percentage(impact)
+        ),
+    }
+}
 
-This comprehensive analysis establishes a valuation framework for tokenized advertising revenue, a novel asset class at the intersection of digital media and decentralized finance. The model demonstrates that:
+/// 5% VaR worded by sign: under skewed scenario weights the tail percentile
+/// can sit above the central estimate
+fn format_value_at_risk(ctx: &ReportContext) -> String {
+    let var = value_at_risk(ctx.data, 0.95);
+    if var > 0.0 {
+        format!("{} below central", ctx.currency(var))
+    } else if var < 0.0 {
+        format!("{} above central", ctx.currency(-var))
+    } else {
+        "at the central estimate".to_string()
+    }
+}
 
-1. Fair market value is highly sensitive to market conditions and structural parameters.
-2. The central valuation estimate of {} represents a balanced scenario.
-3. Investor participation through the lift mechanism provides significant upside potential.
-4. Risk-adjusted returns must account for volatility, timing, and platform-specific factors.
+/// Time value sensitivity line: the value lost to each 30-day payout delay
+fn format_time_value_impact(data: &ReportData) -> String {
+    let summary = &data.summary;
+    match (summary.payout_impact_60_90, summary.payout_impact_90_120) {
+        (Some(first), Some(second)) => format!(
+            "Delaying payout from 60 to 90 days reduces valuation by {}, and from 90 to 120 days by a further {}.",
+            format_percent(first, 1),
+            format_percent(second, 1)
+        ),
+        _ if data.inputs.payout_ladder.is_some() => LADDER_NOTE.to_string(),
+        _ => ZERO_BASE_NOTE.to_string(),
+    }
+}
 
-The tokenization of revenue streams represents an innovative approach to media financing, offering publishers immediate liquidity while providing investors exposure to digital advertising growth. As this market matures, we expect valuation methodologies to evolve and risk premiums to compress, potentially enhancing asset values over time.
+/// Share of the central face value lost to discounting (as percentage), `None`
+/// when there is no face value to discount
+fn discounting_haircut(summary: &SummaryStatistics) -> Option<f64> {
+    pct_change(summary.central_face_value, summary.central_estimate).map(|change| -change)
+}
+
+/// Insight lines splitting each payout's discount haircut in the central
+/// volatility scenario into time value and risk
+fn format_haircut_decomposition(ctx: &ReportContext) -> String {
+    let lines: Vec<String> = ctx
+        .data
+        .haircut_splits
+        .iter()
+        .map(|&HaircutSplit { payout, time, risk }| {
+            let haircut = time + risk;
+            let share = |part: f64| {
+                if haircut == 0.0 {
+                    "n/a".to_string()
+                } else {
+                    format_percent(part / haircut * 100.0, 1)
+                }
+            };
+            format!(
+                "* {}: {} haircut; time value {} ({}), risk {} ({}).\n",
+                ctx.labels().payout(payout),
+                ctx.currency(haircut),
+                ctx.currency(time),
+                share(time),
+                ctx.currency(risk),
+                share(risk)
+            )
+        })
+        .collect();
+    if lines.is_empty() {
+        return String::new();
+    }
+    format!(
+        "Discount Haircut by Payout ({} volatility, risk-free rate vs full rate):\n{}\n",
+        ctx.labels()
+            .volatility(ctx.data.summary.central_scenario.volatility),
+        lines.concat()
+    )
+}
+
+/// Insight bullet comparing fair value to the purchase price, overall and
+/// per lift scenario, or nothing when no price was given
+fn format_value_ratio_line(ctx: &ReportContext) -> String {
+    let (Some(price), Some(ratio)) = (ctx.data.inputs.purchase_price, ctx.data.summary.value_ratio)
+    else {
+        return String::new();
+    };
+    let per_lift: Vec<String> = ctx
+        .lift_scenarios()
+        .filter_map(|lift| {
+            let scenario = ScenarioId {
+                payout: ctx.central_payout(),
+                volatility: VolatilityScenario::Typical,
+                lift: Some(lift),
+            };
+            ctx.data
+                .all_valuations
+                .iter()
+                .find(|v| v.scenario() == scenario)
+                .map(|v| {
+                    format!(
+                        "{} {}",
+                        ctx.labels().lift(lift),
+                        format_multiple(Some(v.present_value / price))
+                    )
+                })
+        })
+        .collect();
+    let breakdown = if per_lift.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", per_lift.join(", "))
+    };
+    format!(
+        "\n* Value ratio at a {} purchase price: {} of fair value per dollar invested{}; above 1.00x means buying below fair value.",
+        ctx.currency(price),
+        format_multiple(Some(ratio)),
+        breakdown
+    )
+}
+
+/// Signed change of `value` from the central estimate, "N/A" when the
+/// central estimate is zero
+fn format_change_from_central(central: f64, value: f64) -> String {
+    pct_change(central, value).map_or_else(
+        || "N/A".to_string(),
+        |change| format_signed_percent(change, 1),
+    )
+}
+
+/// One row per requested percentile of the equally weighted scenario distribution
+fn build_percentile_table(ctx: &ReportContext) -> TableData {
+    let central = ctx.data.summary.central_estimate;
+    let mut table = TableData::new(
+        "Table 10: Scenario Distribution Percentiles",
+        ["Percentile", "Present Value", "vs Central"],
+    );
+
+    for (percentile, value) in scenario_percentiles(ctx.data, &ctx.options.percentiles) {
+        table.add_row(vec![
+            format!("P{}", percentile),
+            ctx.currency(value),
+            format_change_from_central(central, value),
+        ]);
+    }
+    table
+}
+
+fn generate_conclusion_section(ctx: &ReportContext) -> String {
+    let data = ctx.data;
+    // Basic timestamp generation
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    format!(
+        r#"Section 8: Conclusion
+=====================
+
+This comprehensive analysis establishes a valuation framework for tokenized advertising revenue, a novel asset class at the intersection of digital media and decentralized finance. The model demonstrates that:
+
+1. Fair market value is highly sensitive to market conditions and structural parameters.
+2. The central valuation estimate of {} represents a balanced scenario.
+3. Investor participation through the lift mechanism provides significant upside potential.
+4. Risk-adjusted returns must account for volatility, timing, and platform-specific factors.
+
+The tokenization of revenue streams represents an innovative approach to media financing, offering publishers immediate liquidity while providing investors exposure to digital advertising growth. As this market matures, we expect valuation methodologies to evolve and risk premiums to compress, potentially enhancing asset values over time.
 
 ---
 Report Generated Timestamp: {}
-Model Version: 0.2.1
+Model Version: {}
 "#,
-        format_currency(data.summary.central_estimate),
+        ctx.currency(data.summary.central_estimate),
         timestamp,
+        MODEL_VERSION,
     )
 }
 
 // Helper functions to build tables
 
-fn build_discount_rate_table(data: &ReportData) -> String {
-    let mut table = Table::new();
-    table
-        .load_preset(UTF8_FULL)
-        .apply_modifier(UTF8_ROUND_CORNERS)
-        .set_header(vec![
+fn generate_method_comparison_appendix(
+    ctx: &ReportContext,
+    comparison: &[MethodComparison],
+    report: &mut Report,
+) {
+    report.prose(
+        r#"Appendix A: Discrete vs Continuous Discounting
+==============================================
+
+Each scenario is valued twice: with annual compounding, PV = CF / (1 + r)^t, and with
+continuous compounding, PV = CF * e^(-r * t). The gap shows how much the convention alone
+moves the result.
+
+"#,
+    );
+
+    let mut table = TableData::new(
+        "Table A1: Present Value by Compounding Convention",
+        ["Scenario", "Discrete PV", "Continuous PV", "Gap"],
+    );
+
+    for row in comparison {
+        table.add_row(vec![
+            ctx.labels().scenario(row.scenario),
+            ctx.currency(row.discrete),
+            ctx.currency(row.continuous),
+            format_signed_percent(row.gap_percentage(), 2),
+        ]);
+    }
+
+    report.table(table);
+    report.prose("\n");
+}
+
+fn generate_day_count_appendix(
+    ctx: &ReportContext,
+    comparison: &[DayCountComparison],
+    report: &mut Report,
+) {
+    let selected = ctx.data.inputs.day_count;
+    report.prose(format!(
+        r#"Appendix B: Day-Count Convention Comparison
+===========================================
+
+The central scenario is valued under each day-count convention, which sets how
+payout delays convert into years of discounting. The report uses {}.
+
+"#,
+        selected
+    ));
+
+    let selected_value = comparison
+        .iter()
+        .find(|row| row.day_count == selected)
+        .map_or(ctx.data.summary.central_estimate, |row| {
+            row.central_estimate
+        });
+    let mut table = TableData::new(
+        "Table B1: Central Estimate by Day-Count Convention",
+        ["Day-Count Convention", "Central PV", "vs Selected"],
+    );
+    for row in comparison {
+        let label = if row.day_count == selected {
+            format!("{} (selected)", row.day_count)
+        } else {
+            row.day_count.to_string()
+        };
+        let change = if selected_value == 0.0 {
+            0.0
+        } else {
+            (row.central_estimate - selected_value) / selected_value * 100.0
+        };
+        table.add_row(vec![
+            label,
+            ctx.currency(row.central_estimate),
+            format_signed_percent(change, 2),
+        ]);
+    }
+
+    let values = comparison.iter().map(|row| row.central_estimate);
+    let low = values.clone().fold(f64::INFINITY, f64::min);
+    let high = values.fold(f64::NEG_INFINITY, f64::max);
+    report.table(table);
+    report.prose(format!(
+        "\n\nSpread across conventions: {} ({} of the selected central estimate).\n",
+        ctx.currency(high - low),
+        format_percent(
+            if selected_value == 0.0 {
+                0.0
+            } else {
+                (high - low) / selected_value * 100.0
+            },
+            2
+        )
+    ));
+}
+
+fn build_discount_rate_table(ctx: &ReportContext) -> TableData {
+    let data = ctx.data;
+    if let Some(matrix) = &data.inputs.rate_matrix {
+        return build_rate_matrix_table(ctx, matrix);
+    }
+    if data.inputs.discount_rate_overrides.is_some() {
+        return build_supplied_discount_rate_table(ctx);
+    }
+
+    // A formula's total is not the sum of the components, so they have no share of it
+    let by_formula = data.inputs.rate_formula.is_some();
+    let mut table = TableData::new(
+        "Table 1: Risk-Adjusted Discount Rates by Volatility Scenario",
+        [
             "Volatility Scenario",
             "Risk-Free Rate",
             "Volatility Premium",
             "Platform Premium",
-            "Total Discount Rate",
-        ]);
+            if by_formula {
+                "Total Discount Rate (Formula)"
+            } else {
+                "Total Discount Rate"
+            },
+        ],
+    );
 
     for volatility in VolatilityScenario::all() {
         let components = &data.discount_rates[volatility];
+        let total = components.total_rate();
+        let component = |rate: Rate| match ctx.options.rate_components_as {
+            RateComponentUnits::Percent => format_percent(rate.as_percent(), 1),
+            RateComponentUnits::Bps if by_formula || total.as_decimal() == 0.0 => format_bps(rate),
+            RateComponentUnits::Bps => format!(
+                "{} ({} of total)",
+                format_bps(rate),
+                format_percent(rate.as_decimal() / total.as_decimal() * 100.0, 0)
+            ),
+        };
         table.add_row(vec![
-            volatility.to_string(),
-            format!("{:.1}%", components.risk_free_rate * 100.0),
-            format!("{:.1}%", components.volatility_premium * 100.0),
-            format!("{:.1}%", components.platform_risk_premium * 100.0),
-            format!("{:.1}%", components.total_rate() * 100.0),
+            ctx.labels().volatility(*volatility),
+            component(components.risk_free_rate),
+            component(components.volatility_premium),
+            component(components.platform_risk_premium),
+            match ctx.options.rate_components_as {
+                RateComponentUnits::Percent => format_percent(total.as_percent(), 1),
+                RateComponentUnits::Bps => format_bps(total),
+            },
         ]);
     }
+    table
+}
 
-    format!(
-        "Table 1: Risk-Adjusted Discount Rates by Volatility Scenario\n\n{}",
-        table
-    )
+/// A rate in whole basis points, e.g. "1200 bps"
+fn format_bps(rate: Rate) -> String {
+    format!("{} bps", format_decimal(rate.as_decimal() * 10_000.0, 0))
 }
 
-fn build_baseline_valuation_table(data: &ReportData) -> String {
-    let mut table = Table::new();
+/// Discount rate table for user-supplied totals, which have no components to show
+fn build_supplied_discount_rate_table(ctx: &ReportContext) -> TableData {
+    let mut table = TableData::new(
+        "Table 1: Supplied Discount Rates by Volatility Scenario",
+        ["Volatility Scenario", "Total Discount Rate (Supplied)"],
+    );
+
+    for volatility in VolatilityScenario::all() {
+        table.add_row(vec![
+            ctx.labels().volatility(*volatility),
+            format_percent(
+                ctx.data.discount_rates[volatility]
+                    .total_rate()
+                    .as_percent(),
+                1,
+            ),
+        ]);
+    }
     table
-        .load_preset(UTF8_FULL)
-        .apply_modifier(UTF8_ROUND_CORNERS)
-        .set_header(vec![
-            "Payout Timing",
-            "Low Vol (2%)",
-            "Typical (4%)",
-            "High Vol (6%)",
-            "Extreme (10%)",
+}
+
+/// Discount rate table for a supplied rate matrix, one row per payout scenario
+fn build_rate_matrix_table(ctx: &ReportContext, matrix: &RateMatrix) -> TableData {
+    let mut header = vec!["Payout Timing".to_string()];
+    header.extend(
+        VolatilityScenario::all()
+            .iter()
+            .map(|v| ctx.labels().volatility(*v)),
+    );
+    let mut table = TableData::new("Table 1: Supplied Discount Rate Matrix", header);
+
+    for &payout in ctx.data.inputs.payout_scenarios() {
+        let mut row = vec![ctx.labels().payout(payout)];
+        row.extend(VolatilityScenario::all().iter().map(|&volatility| {
+            matrix
+                .rate(payout, volatility)
+                .map_or_else(|| "N/A".to_string(), |rate| format_percent(rate * 100.0, 1))
+        }));
+        table.add_row(row);
+    }
+    table
+}
+
+/// Waterfall from undiscounted cash flow to present value for the central
+/// baseline scenario, attributing the erosion to each rate component.
+///
+/// The discount factor is rebuilt by adding the components one at a time over
+/// the scenario's effective holding period, so the steps multiply out exactly
+/// to the total factor. Returns `None` when rates were supplied directly or
+/// come from a rate formula, since they are not a sum of components to attribute.
+fn build_waterfall(ctx: &ReportContext) -> Option<TableData> {
+    let data = ctx.data;
+    if data.inputs.discount_rate_overrides.is_some()
+        || data.inputs.rate_matrix.is_some()
+        || data.inputs.rate_formula.is_some()
+    {
+        return None;
+    }
+
+    let volatility = VolatilityScenario::Typical;
+    let payout = if data.inputs.payout_ladder.is_some() {
+        PayoutScenario::Laddered
+    } else {
+        PayoutScenario::Day90
+    };
+    let central = data.all_valuations.iter().find(|v| {
+        v.scenario()
+            == ScenarioId {
+                payout,
+                volatility,
+                lift: None,
+            }
+    })?;
+    let cash_flow = horizon_cash_flow(
+        &data.inputs,
+        data.summary.adjusted_baseline,
+        data.growth_rates[&volatility],
+    )
+    .ok()?
+    .to_f64();
+
+    let components = &data.discount_rates[&volatility];
+    let compounding = data.inputs.compounding;
+    let total_factor = central.retention_ratio(cash_flow);
+    let years = compounding.implied_years(components.total_rate().as_decimal(), total_factor);
+
+    let steps = [
+        ("Time Value (Risk-Free Rate)", components.risk_free_rate),
+        ("Volatility Premium", components.volatility_premium),
+        (
+            "Platform/Publisher Premium",
+            components.platform_risk_premium,
+        ),
+    ]
+    .map(|(label, rate)| (label, rate.as_decimal()));
+
+    let mut table = TableData::new(
+        format!(
+            "Table 1a: Discount Waterfall - {}",
+            ctx.labels().scenario(central.scenario())
+        ),
+        ["Step", "Rate", "Effect", "Remaining Value"],
+    );
+    table.add_row(vec![
+        "Undiscounted Cash Flow".to_string(),
+        String::new(),
+        String::new(),
+        format!("{} ({})", ctx.currency(cash_flow), format_percent(100.0, 1)),
+    ]);
+
+    let mut cumulative_rate = 0.0;
+    let mut factor = 1.0;
+    for (label, rate) in steps {
+        cumulative_rate += rate;
+        let next_factor = compounding.discount_factor(cumulative_rate, years);
+        table.add_row(vec![
+            label.to_string(),
+            format_percent(rate * 100.0, 1),
+            format_signed_percent((next_factor - factor) * 100.0, 1),
+            format!(
+                "{} ({})",
+                ctx.currency(cash_flow * next_factor),
+                format_percent(next_factor * 100.0, 1)
+            ),
         ]);
+        factor = next_factor;
+    }
+    table.add_row(vec![
+        "Present Value".to_string(),
+        format_percent(components.total_rate().as_percent(), 1),
+        String::new(),
+        format!(
+            "{} ({})",
+            ctx.currency(central.present_value),
+            format_percent(total_factor * 100.0, 1)
+        ),
+    ]);
+    Some(table)
+}
+
+fn build_baseline_valuation_table(ctx: &ReportContext) -> TableData {
+    let data = ctx.data;
+    let mut table = TableData::new(
+        format!(
+            "Table 2: Baseline Valuation Matrix (No Investor Lift){}",
+            ctx.position_caption()
+        ),
+        ctx.volatility_headers(),
+    );
 
-    for payout in PayoutScenario::all() {
-        let mut row = vec![payout.to_string()];
+    for payout in data.inputs.payout_scenarios() {
+        let mut row = vec![ctx.labels().payout(*payout)];
 
         for volatility in VolatilityScenario::all() {
             // Find logic now searches the unified vector for baseline (lift_scenario: None)
@@ -336,183 +1911,2609 @@ fn build_baseline_valuation_table(data: &ReportData) -> String {
                         && v.volatility_scenario == *volatility
                         && v.lift_scenario.is_none() // Check for baseline
                 })
-                .map(|v| format_currency(v.present_value))
+                .map(|v| ctx.currency(ctx.matrix_value(v)))
                 .unwrap_or_else(|| "N/A".to_string());
             row.push(value);
         }
 
         table.add_row(row);
     }
+    table
+}
 
-    format!(
-        "Table 2: Baseline Valuation Matrix (No Investor Lift)\n\n{}",
-        table
-    )
+/// Retention grid, with a note naming the cell that keeps the least cash flow
+fn build_retention_table(ctx: &ReportContext) -> (TableData, Option<String>) {
+    let data = ctx.data;
+    let mut table = TableData::new(
+        "Table 2a: Cash Flow Retention (Present Value / Cash Flow)",
+        ctx.volatility_headers(),
+    );
+
+    // Retention is independent of the cash flow level, so the baseline grid represents every lift scenario
+    let mut weakest: Option<(&ValuationResult, f64)> = None;
+    for payout in data.inputs.payout_scenarios() {
+        let mut row = vec![ctx.labels().payout(*payout)];
+
+        for volatility in VolatilityScenario::all() {
+            let cash_flow = horizon_cash_flow(
+                &data.inputs,
+                data.summary.adjusted_baseline,
+                data.growth_rates[volatility],
+            )
+            .ok();
+            let value = data
+                .all_valuations
+                .iter()
+                .find(|v| {
+                    v.payout_scenario == *payout
+                        && v.volatility_scenario == *volatility
+                        && v.lift_scenario.is_none()
+                })
+                .zip(cash_flow)
+                .map(|(v, cash_flow)| {
+                    let ratio = v.retention_ratio(cash_flow.to_f64());
+                    if weakest.is_none_or(|(_, lowest)| ratio < lowest) {
+                        weakest = Some((v, ratio));
+                    }
+                    format_percent(ratio * 100.0, 1)
+                })
+                .unwrap_or_else(|| "N/A".to_string());
+            row.push(value);
+        }
+
+        table.add_row(row);
+    }
+
+    let note = weakest.map(|(result, ratio)| {
+        format!(
+            "\n{}/{} retains only {} of cash flow.",
+            ctx.labels().volatility(result.volatility_scenario),
+            ctx.labels().payout(result.payout_scenario),
+            format_percent(ratio * 100.0, 1)
+        )
+    });
+    (table, note)
 }
 
-fn build_lift_scenarios_table(data: &ReportData) -> String {
-    let mut table = Table::new();
-    table
-        .load_preset(UTF8_FULL)
-        .apply_modifier(UTF8_ROUND_CORNERS)
-        .set_header(vec![
+fn build_lift_scenarios_table(ctx: &ReportContext) -> TableData {
+    let data = ctx.data;
+    let mut table = TableData::new(
+        "Table 3: Investor Lift Activation Scenarios",
+        [
             "Lift Scenario",
             "Investor Activation Factor",
             "Resulting Monthly Audience Lift",
-        ]);
+        ],
+    );
 
     let assumptions = &data.lift_assumptions;
-    for lift in LiftScenario::all() {
+    for lift in ctx.lift_scenarios() {
         let audience_lift =
             lift.additional_audience(assumptions.investor_count, assumptions.lift_per_investor);
         table.add_row(vec![
-            lift.to_string(),
-            format!("{:.0}%", lift.activation_factor() * 100.0),
-            (audience_lift.round() as i64).to_formatted_string(&Locale::en),
+            ctx.labels().lift(lift),
+            format_percent(lift.activation_factor() * 100.0, 0),
+            ctx.audience(audience_lift),
         ]);
     }
-
-    format!("Table 3: Investor Lift Activation Scenarios\n\n{}", table)
+    table
 }
 
-fn build_audience_growth_table(data: &ReportData) -> String {
-    let mut table = Table::new();
-    table
-        .load_preset(UTF8_FULL)
-        .apply_modifier(UTF8_ROUND_CORNERS)
-        .set_header(vec![
-            "Metric",
-            "Baseline",
-            "Low Lift",
-            "Medium Lift",
-            "High Lift",
-        ]);
+fn build_audience_growth_table(ctx: &ReportContext) -> TableData {
+    let data = ctx.data;
+    let mut table = TableData::new(
+        "Table 4: Audience Growth Under Lift Scenarios",
+        ctx.lift_headers(&["Metric", "Baseline"]),
+    );
 
     let assumptions = &data.lift_assumptions;
 
     // Monthly Audience row
     let mut audience_row = vec!["Total Monthly Audience".to_string()];
-    audience_row
-        .push((assumptions.baseline_audience.round() as i64).to_formatted_string(&Locale::en));
-    for lift in LiftScenario::all() {
+    audience_row.push(ctx.audience(assumptions.baseline_audience));
+    for lift in ctx.lift_scenarios() {
         let additional_audience =
             lift.additional_audience(assumptions.investor_count, assumptions.lift_per_investor);
         let total = assumptions.baseline_audience + additional_audience;
-        audience_row.push((total.round() as i64).to_formatted_string(&Locale::en));
+        audience_row.push(ctx.audience(total));
     }
     table.add_row(audience_row);
 
     // Growth % row
     let mut growth_row = vec!["Growth vs Baseline".to_string()];
     growth_row.push("0.0%".to_string());
-    for lift in LiftScenario::all() {
+    for lift in ctx.lift_scenarios() {
         let additional_audience =
             lift.additional_audience(assumptions.investor_count, assumptions.lift_per_investor);
         let growth_pct = (additional_audience / assumptions.baseline_audience) * 100.0;
-        growth_row.push(format!("{:.1}%", growth_pct));
+        growth_row.push(format_percent(growth_pct, 1));
     }
     table.add_row(growth_row);
-
-    format!("Table 4: Audience Growth Under Lift Scenarios\n\n{}", table)
+    table
 }
 
-fn build_revenue_impact_table(data: &ReportData) -> String {
-    let mut table = Table::new();
-    table
-        .load_preset(UTF8_FULL)
-        .apply_modifier(UTF8_ROUND_CORNERS)
-        .set_header(vec![
-            "Revenue Component",
-            "Low Lift",
-            "Medium Lift",
-            "High Lift",
-        ]);
+fn build_revenue_impact_table(ctx: &ReportContext) -> TableData {
+    let data = ctx.data;
+    let mut table = TableData::new(
+        "Table 5: Revenue Impact of Investor Lift",
+        ctx.lift_headers(&["Revenue Component"]),
+    );
 
     let assumptions = &data.lift_assumptions;
 
-    // Baseline Quarterly Revenue
-    table.add_row(vec![
-        "Baseline Quarterly Revenue".to_string(),
-        format_currency(data.summary.adjusted_baseline),
-        format_currency(data.summary.adjusted_baseline),
-        format_currency(data.summary.adjusted_baseline),
-    ]);
+    let period = data.inputs.period;
 
-    // Additional Quarterly Revenue
-    let mut lift_row = vec!["Additional Quarterly Revenue from Lift".to_string()];
-    for lift in LiftScenario::all() {
-        let quarterly_lift = lift.quarterly_lift(
-            assumptions.investor_count,
-            assumptions.lift_per_investor,
-            assumptions.rpm,
-        );
-        lift_row.push(format_currency(quarterly_lift));
+    // Baseline revenue per period
+    let mut baseline_row = vec![format!("Baseline {} Revenue", period)];
+    for _ in ctx.lift_scenarios() {
+        baseline_row.push(ctx.currency(data.summary.adjusted_baseline));
+    }
+    table.add_row(baseline_row);
+
+    // Additional revenue per period
+    let mut lift_row = vec![format!("Additional {} Revenue from Lift", period)];
+    for lift in ctx.lift_scenarios() {
+        lift_row.push(ctx.currency(assumptions.period_lift(lift)));
     }
     table.add_row(lift_row);
 
-    // Total Quarterly Revenue
-    let mut total_row = vec!["Total Lifted Quarterly Revenue".to_string()];
-    for lift in LiftScenario::all() {
-        let quarterly_lift = lift.quarterly_lift(
-            assumptions.investor_count,
-            assumptions.lift_per_investor,
-            assumptions.rpm,
-        );
-        let total = data.summary.adjusted_baseline + quarterly_lift;
-        total_row.push(format_currency(total));
+    // Total revenue per period
+    let mut total_row = vec![format!("Total Lifted {} Revenue", period)];
+    for lift in ctx.lift_scenarios() {
+        let total = data.summary.adjusted_baseline + assumptions.period_lift(lift);
+        total_row.push(ctx.currency(total));
     }
     table.add_row(total_row);
 
-    format!("Table 5: Revenue Impact of Investor Lift\n\n{}", table)
+    // Undiscounted horizon cash flow behind each lift scenario's central value
+    let payout = ctx.central_payout();
+    let mut face_row = vec!["Face Value (Undiscounted)".to_string()];
+    for lift in ctx.lift_scenarios() {
+        let scenario = ScenarioId {
+            payout,
+            volatility: VolatilityScenario::Typical,
+            lift: Some(lift),
+        };
+        let face_value = data
+            .all_valuations
+            .iter()
+            .find(|v| v.scenario() == scenario)
+            .map_or_else(|| "N/A".to_string(), |v| ctx.currency(v.face_value));
+        face_row.push(face_value);
+    }
+    table.add_row(face_row);
+
+    // Periods of lift revenue to recover what acquiring the lift cost
+    if let Some(cost) = data.inputs.lift_acquisition_cost {
+        let mut payback_row = vec![format!(
+            "Payback on {} Lift Acquisition Cost",
+            ctx.currency(cost)
+        )];
+        for lift in ctx.lift_scenarios() {
+            payback_row.push(assumptions.payback_periods(lift, cost).map_or_else(
+                || "never".to_string(),
+                |periods| format!("{} {}s", format_decimal(periods, 1), period.noun()),
+            ));
+        }
+        table.add_row(payback_row);
+    }
+    table
+}
+
+/// Width in characters of the range bars
+const RANGE_BAR_WIDTH: usize = 30;
+
+/// Range bar per payout timing spanning its lowest to highest lifted value
+/// across volatility and lift, with `|` marking the Typical-volatility value
+/// of the central lift scenario. All bars share one scale.
+fn build_range_bars(ctx: &ReportContext) -> TableData {
+    let data = ctx.data;
+    let central_lift = ctx.options.only_lift.unwrap_or(LiftScenario::Medium);
+    let rows: Vec<(PayoutScenario, f64, f64, f64)> = data
+        .inputs
+        .payout_scenarios()
+        .iter()
+        .filter_map(|payout| {
+            let values: Vec<&ValuationResult> = data
+                .all_valuations
+                .iter()
+                .filter(|v| {
+                    v.payout_scenario == *payout
+                        && v.lift_scenario
+                            .is_some_and(|l| ctx.lift_scenarios().any(|s| s == l))
+                })
+                .collect();
+            let low = values.iter().map(|v| v.present_value).reduce(f64::min)?;
+            let high = values.iter().map(|v| v.present_value).reduce(f64::max)?;
+            let central = values
+                .iter()
+                .find(|v| {
+                    v.volatility_scenario == VolatilityScenario::Typical
+                        && v.lift_scenario == Some(central_lift)
+                })?
+                .present_value;
+            Some((*payout, low, central, high))
+        })
+        .collect();
+
+    let scale_low = rows.iter().map(|r| r.1).fold(f64::INFINITY, f64::min);
+    let scale_high = rows.iter().map(|r| r.3).fold(f64::NEG_INFINITY, f64::max);
+    let position = |value: f64| {
+        if scale_high > scale_low {
+            ((value - scale_low) / (scale_high - scale_low) * (RANGE_BAR_WIDTH - 1) as f64).round()
+                as usize
+        } else {
+            0
+        }
+    };
+
+    let mut table = TableData::new(
+        format!(
+            "Table 9: Valuation Range by Payout Timing ({} to {})",
+            ctx.currency(scale_low),
+            ctx.currency(scale_high)
+        ),
+        ["Payout Timing", "Low", "Central", "High", "Range"],
+    );
+
+    for (payout, low, central, high) in rows {
+        let mut bar = vec![' '; RANGE_BAR_WIDTH];
+        let (start, end) = (position(low), position(high));
+        for cell in bar.iter_mut().take(end + 1).skip(start) {
+            *cell = '-';
+        }
+        bar[start] = '[';
+        bar[end] = ']';
+        bar[position(central)] = '|';
+
+        table.add_row(vec![
+            ctx.labels().payout(payout),
+            ctx.currency(low),
+            ctx.currency(central),
+            ctx.currency(high),
+            bar.into_iter().collect(),
+        ]);
+    }
+    table
 }
 
-fn build_valuation_table(data: &ReportData, lift_scenario: LiftScenario) -> String {
+fn build_valuation_table(ctx: &ReportContext, lift_scenario: LiftScenario) -> TableData {
+    let data = ctx.data;
     let table_num = match lift_scenario {
         LiftScenario::Low => 6,
         LiftScenario::Medium => 7,
         LiftScenario::High => 8,
     };
 
-    let mut table = Table::new();
-    table
-        .load_preset(UTF8_FULL)
-        .apply_modifier(UTF8_ROUND_CORNERS)
-        .set_header(vec![
-            "Payout Timing",
-            "Low Vol (2%)",
-            "Typical (4%)",
-            "High Vol (6%)",
-            "Extreme (10%)",
-        ]);
+    let mut table = TableData::new(
+        format!(
+            "Table {}: Final Valuation Matrix - {} Scenario{}",
+            table_num,
+            ctx.labels().lift(lift_scenario),
+            ctx.position_caption()
+        ),
+        ctx.volatility_headers(),
+    );
 
-    for payout in PayoutScenario::all() {
-        let mut row = vec![payout.to_string()];
+    for payout in data.inputs.payout_scenarios() {
+        let mut row = vec![ctx.labels().payout(*payout)];
 
         for volatility in VolatilityScenario::all() {
-            let value = data
-                .all_valuations
-                .iter()
-                .find(|v| {
+            let find = |lift| {
+                data.all_valuations.iter().find(|v| {
                     v.payout_scenario == *payout
                         && v.volatility_scenario == *volatility
-                        && v.lift_scenario == Some(lift_scenario)
+                        && v.lift_scenario == lift
                 })
-                .map(|v| format_currency(v.present_value))
-                .unwrap_or_else(|| "N/A".to_string());
+            };
+            let value = match find(Some(lift_scenario)) {
+                Some(cell) if ctx.options.since_baseline => format!(
+                    "{} ({})",
+                    ctx.currency(ctx.matrix_value(cell)),
+                    find(None)
+                        .map(|v| v.present_value)
+                        .filter(|baseline| *baseline != 0.0)
+                        .map(|baseline| {
+                            format_signed_percent((cell.present_value / baseline - 1.0) * 100.0, 1)
+                        })
+                        .unwrap_or_else(|| "N/A".to_string())
+                ),
+                Some(cell) => ctx.currency(ctx.matrix_value(cell)),
+                None => "N/A".to_string(),
+            };
             row.push(value);
         }
 
         table.add_row(row);
     }
 
+    table
+}
+
+/// Block characters for heatmap shading, from the lowest to the highest quarter
+const HEATMAP_BLOCKS: [char; 4] = ['░', '▒', '▓', '█'];
+
+/// The baseline matrix (`lift` of `None`) or a lift matrix with each cell
+/// shaded by its value relative to the matrix minimum and maximum.
+///
+/// Cells keep the plain matrix's text; color shading is applied on top of
+/// it, so column widths match the unshaded table.
+fn build_valuation_heatmap(
+    ctx: &ReportContext,
+    lift: Option<LiftScenario>,
+    style: HeatmapStyle,
+) -> String {
+    let plain = match lift {
+        Some(lift) => build_valuation_table(ctx, lift),
+        None => build_baseline_valuation_table(ctx),
+    };
+    let values: Vec<Vec<Option<f64>>> = ctx
+        .data
+        .inputs
+        .payout_scenarios()
+        .iter()
+        .map(|&payout| {
+            VolatilityScenario::all()
+                .iter()
+                .map(|&volatility| {
+                    let scenario = ScenarioId {
+                        payout,
+                        volatility,
+                        lift,
+                    };
+                    ctx.data
+                        .all_valuations
+                        .iter()
+                        .find(|v| v.scenario() == scenario)
+                        .map(|v| ctx.matrix_value(v))
+                })
+                .collect()
+        })
+        .collect();
+    let present = values.iter().flatten().flatten().copied();
+    let low = present.clone().fold(f64::INFINITY, f64::min);
+    let high = present.fold(f64::NEG_INFINITY, f64::max);
+    let shade = |value: f64| {
+        if high > low {
+            (value - low) / (high - low)
+        } else {
+            1.0
+        }
+    };
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(plain.headers.clone());
+    if style == HeatmapStyle::Color {
+        table.enforce_styling();
+    }
+    for (row, row_values) in plain.rows.iter().zip(&values) {
+        let mut cells = vec![Cell::new(&row[0])];
+        for (text, value) in row[1..].iter().zip(row_values) {
+            cells.push(match (value, style) {
+                (Some(value), HeatmapStyle::Color) => {
+                    let (r, g, b) = heatmap_color(shade(*value));
+                    Cell::new(text).bg(Color::Rgb { r, g, b }).fg(Color::Black)
+                }
+                (Some(value), HeatmapStyle::Blocks) => {
+                    let level = ((shade(*value) * 4.0) as usize).min(HEATMAP_BLOCKS.len() - 1);
+                    Cell::new(format!("{} {}", HEATMAP_BLOCKS[level], text))
+                }
+                (None, _) => Cell::new(text),
+            });
+        }
+        table.add_row(cells);
+    }
+
+    let legend = match style {
+        HeatmapStyle::Color => "red = lowest, yellow = middle, green = highest".to_string(),
+        HeatmapStyle::Blocks => format!(
+            "{} = lowest quarter ... {} = highest quarter",
+            HEATMAP_BLOCKS[0],
+            HEATMAP_BLOCKS[HEATMAP_BLOCKS.len() - 1]
+        ),
+    };
     format!(
-        "Table {}: Final Valuation Matrix - {} Scenario\n\n{}",
-        table_num, lift_scenario, table
+        "{}\n\n{}\nShading spans {} to {} ({}).",
+        plain.caption,
+        table,
+        ctx.currency(low),
+        ctx.currency(high),
+        legend
     )
 }
 
-/// Formats a f64 value as a currency string, e.g., "$1,234,567"
-fn format_currency(value: f64) -> String {
-    let rounded_value = value.round() as i64;
-    format!("${}", rounded_value.to_formatted_string(&Locale::en))
+/// Background color for a heatmap cell at `position` (0.0 lowest, 1.0
+/// highest) on a red-yellow-green scale
+fn heatmap_color(position: f64) -> (u8, u8, u8) {
+    const RED: (f64, f64, f64) = (248.0, 105.0, 107.0);
+    const YELLOW: (f64, f64, f64) = (255.0, 235.0, 132.0);
+    const GREEN: (f64, f64, f64) = (99.0, 190.0, 123.0);
+    let (from, to, t) = if position < 0.5 {
+        (RED, YELLOW, position * 2.0)
+    } else {
+        (YELLOW, GREEN, position * 2.0 - 1.0)
+    };
+    let mix = |a: f64, b: f64| (a + (b - a) * t).round() as u8;
+    (mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+/// Percentage change between adjacent volatility columns on the central payout row
+fn format_step_deltas(ctx: &ReportContext, lift_scenario: LiftScenario) -> String {
+    let payout = ctx.central_payout();
+    let values: Vec<(VolatilityScenario, f64)> = VolatilityScenario::all()
+        .iter()
+        .filter_map(|&volatility| {
+            let scenario = ScenarioId {
+                payout,
+                volatility,
+                lift: Some(lift_scenario),
+            };
+            ctx.data
+                .all_valuations
+                .iter()
+                .find(|v| v.scenario() == scenario)
+                .map(|v| (volatility, v.present_value))
+        })
+        .collect();
+    let steps: Vec<String> = values
+        .windows(2)
+        .map(|pair| {
+            let ((from, from_value), (to, to_value)) = (pair[0], pair[1]);
+            format!(
+                "{} -> {}: {}",
+                ctx.labels().volatility(from),
+                ctx.labels().volatility(to),
+                format_signed_percent((to_value / from_value - 1.0) * 100.0, 1)
+            )
+        })
+        .collect();
+    format!(
+        "\nVolatility steps ({}): {}\n",
+        ctx.labels().payout(payout),
+        steps.join(", ")
+    )
+}
+
+/// Columns of the long-format CSV, in output order
+const LONG_CSV_COLUMNS: [&str; 10] = [
+    "payout",
+    "volatility",
+    "lift",
+    "discount_rate",
+    "growth_rate",
+    "cash_flow",
+    "present_value",
+    "retention_ratio",
+    "per_token",
+    "position_value",
+];
+
+/// Generate long-format CSV: one row per grid cell with every derived metric.
+///
+/// Rows follow the grid order (baseline first, then each lift scenario) and
+/// columns follow `LONG_CSV_COLUMNS`. Rates are decimals and amounts are
+/// unrounded; `per_token` and `position_value` are empty without a token
+/// supply or position.
+pub fn generate_long_csv(data: &ReportData) -> String {
+    let optional = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+    let mut csv = LONG_CSV_COLUMNS.join(",");
+    csv.push('\n');
+    for valuation in &data.all_valuations {
+        let volatility = valuation.volatility_scenario;
+        let row = [
+            valuation.payout_scenario.to_string(),
+            volatility.to_string(),
+            valuation
+                .lift_scenario
+                .map(|lift| lift.to_string())
+                .unwrap_or_else(|| "Baseline".to_string()),
+            data.discount_rate(valuation.scenario()).to_string(),
+            data.growth_rates[&volatility].to_string(),
+            valuation.face_value.to_string(),
+            valuation.present_value.to_string(),
+            valuation.retention_ratio(valuation.face_value).to_string(),
+            optional(
+                data.inputs
+                    .token_supply
+                    .filter(|supply| *supply > 0.0)
+                    .map(|supply| valuation.present_value / supply),
+            ),
+            optional(valuation.position_value),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Generate the discount factor curve of every volatility scenario as CSV,
+/// for `--export-curve`.
+///
+/// One row per point, every `step` days from 0 to the longest payout, with a
+/// `days` column followed by one discount factor column per volatility
+/// scenario. Factors use the inputs' day-count and compounding conventions.
+pub fn generate_discount_curve_csv(data: &ReportData, step: u32) -> Result<String, ModelError> {
+    let inputs = &data.inputs;
+    let curves = VolatilityScenario::all()
+        .iter()
+        .map(|volatility| {
+            discount_curve(
+                data.discount_rates[volatility].total_rate().as_decimal(),
+                longest_payout_days(inputs),
+                step,
+                inputs.day_count,
+                inputs.compounding,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut csv = std::iter::once("days".to_string())
+        .chain(VolatilityScenario::all().iter().map(|v| v.to_string()))
+        .collect::<Vec<_>>()
+        .join(",");
+    csv.push('\n');
+    for (index, (days, _)) in curves[0].iter().enumerate() {
+        let row: Vec<String> = std::iter::once(days.to_string())
+            .chain(curves.iter().map(|curve| curve[index].1.to_string()))
+            .collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    Ok(csv)
+}
+
+/// Generate the step-by-step audience-to-revenue arithmetic behind each lift
+/// scenario's per-period revenue, for `--explain-lift`.
+///
+/// Figures are formatted as in the report: amounts in the display currency
+/// (index points when redacting) and audiences in the audience locale.
+pub fn generate_lift_explanation(data: &ReportData, options: &ReportOptions) -> String {
+    let ctx = ReportContext { data, options };
+    ctx.lift_scenarios()
+        .map(|lift| explain_lift(&ctx, lift))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// The arithmetic behind one lift scenario's per-period revenue, one step per line
+fn explain_lift(ctx: &ReportContext, lift: LiftScenario) -> String {
+    let assumptions = &ctx.data.lift_assumptions;
+    let step = |label: &str, arithmetic: String| format!("  {:<22}{}", label, arithmetic);
+    let mut lines = vec![format!("{}:", ctx.labels().lift(lift))];
+    if let Some(fixed) = assumptions.fixed_lift {
+        lines.push(step(
+            &format!(
+                "Fixed {} lift:",
+                assumptions.period.to_string().to_lowercase()
+            ),
+            format!(
+                "{} × {} activation = {}",
+                ctx.currency(fixed),
+                format_percent(lift.activation_factor() * 100.0, 0),
+                ctx.currency(assumptions.period_lift(lift))
+            ),
+        ));
+        return lines.join("\n");
+    }
+
+    let audience =
+        lift.additional_audience(assumptions.investor_count, assumptions.lift_per_investor);
+    let monthly_revenue = audience * (assumptions.incremental_rpm / 1000.0);
+    let ramp_months: f64 = assumptions.lift_ramp.monthly_weights().iter().sum();
+    lines.extend([
+        step(
+            "Investor count:",
+            format_investor_count(assumptions.investor_count),
+        ),
+        step(
+            "Lift per investor:",
+            format_decimal(assumptions.lift_per_investor, 1),
+        ),
+        step(
+            "Activation factor:",
+            format_percent(lift.activation_factor() * 100.0, 0),
+        ),
+        step(
+            "Monthly audience:",
+            format!(
+                "{} × {} × {} = {}",
+                format_investor_count(assumptions.investor_count),
+                format_decimal(assumptions.lift_per_investor, 1),
+                format_percent(lift.activation_factor() * 100.0, 0),
+                ctx.audience(audience)
+            ),
+        ),
+        step(
+            "Monthly revenue:",
+            format!(
+                "{} × {} RPM / 1000 = {}",
+                ctx.audience(audience),
+                ctx.price(assumptions.incremental_rpm),
+                ctx.currency(monthly_revenue)
+            ),
+        ),
+        step("Lift ramp:", assumptions.lift_ramp.to_string()),
+        step(
+            "Quarterly revenue:",
+            format!(
+                "{} × {} months = {}",
+                ctx.currency(monthly_revenue),
+                format_decimal(ramp_months, 2),
+                ctx.currency(monthly_revenue * ramp_months)
+            ),
+        ),
+    ]);
+    if assumptions.period != RevenuePeriod::Quarterly {
+        lines.push(step(
+            &format!("Revenue per {}:", assumptions.period.noun()),
+            format!(
+                "{} × {} = {}",
+                ctx.currency(monthly_revenue * ramp_months),
+                format_decimal(assumptions.period.months() / 3.0, 4),
+                ctx.currency(assumptions.period_lift(lift))
+            ),
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Generate the headline metrics in the Prometheus text exposition format:
+/// one `tokval_`-prefixed gauge per metric, every sample carrying `labels`.
+///
+/// Amounts are unrounded dollars and impacts are percentages. Impacts that
+/// were not computed (lift in baseline-only mode, payout with a ladder) are
+/// left out rather than reported as zero.
+pub fn generate_prometheus(data: &ReportData, labels: &[(&str, &str)]) -> String {
+    let summary = &data.summary;
+    let label_set = labels
+        .iter()
+        .map(|(name, value)| format!("{}=\"{}\"", name, escape_label_value(value)))
+        .collect::<Vec<_>>()
+        .join(",");
+    let label_set = if label_set.is_empty() {
+        label_set
+    } else {
+        format!("{{{}}}", label_set)
+    };
+    let metrics = [
+        (
+            "central_estimate",
+            "Central (most likely) present value in dollars",
+            Some(summary.central_estimate),
+        ),
+        (
+            "min_valuation",
+            "Lowest present value across the scenario grid in dollars",
+            Some(summary.min_valuation),
+        ),
+        (
+            "max_valuation",
+            "Highest present value across the scenario grid in dollars",
+            Some(summary.max_valuation),
+        ),
+        (
+            "downside",
+            "Investable low corner (longest payout, extreme volatility, low lift) in dollars",
+            Some(summary.downside),
+        ),
+        (
+            "upside",
+            "Investable high corner (shortest payout, low volatility, high lift) in dollars",
+            Some(summary.upside),
+        ),
+        (
+            "volatility_impact_percent",
+            "Value lost moving from low to extreme volatility, as a percentage",
+            summary.volatility_impact,
+        ),
+        (
+            "lift_impact_percent",
+            "Value gained moving from low to high lift, as a percentage",
+            summary.lift_impact,
+        ),
+        (
+            "payout_impact_percent",
+            "Value lost extending payout from 60 to 120 days, as a percentage",
+            summary.payout_impact,
+        ),
+    ];
+
+    let mut text = String::new();
+    for (name, help, value) in metrics {
+        let Some(value) = value else {
+            continue;
+        };
+        let metric = format!("tokval_{}", name);
+        text.push_str(&format!("# HELP {} {}\n", metric, help));
+        text.push_str(&format!("# TYPE {} gauge\n", metric));
+        text.push_str(&format!("{}{} {}\n", metric, label_set, value));
+    }
+    text
+}
+
+/// Escape a Prometheus label value: backslash, double quote and line feed
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Fields of the JSON report's `inputs` section, in document order.
+///
+/// Every input is destructured by name, so a new one does not compile until
+/// it is serialized here or set aside as a run control. The round-trip check
+/// reads the document back against this same table.
+pub fn json_input_fields(inputs: &ValuationInputs) -> Vec<(&'static str, JsonValue)> {
+    let ValuationInputs {
+        raw_forecast,
+        period,
+        risk_free_rate,
+        platform_risk_premium,
+        platform_adjustment_factor,
+        baseline_audience,
+        rpm,
+        incremental_rpm,
+        investor_count,
+        lift_per_investor,
+        lift_ramp,
+        fixed_lift,
+        lift_acquisition_cost,
+        baseline_only,
+        payout_ladder,
+        monthly_breakdown,
+        day_count,
+        lift_payout_days,
+        compounding,
+        forecast_confidence,
+        quarters,
+        quarterly_growth_rate,
+        two_stage_growth,
+        reinvestment_rate,
+        revenue_floor,
+        tax_rate,
+        after_tax,
+        cap_at_face,
+        purchase_price,
+        market_price,
+        ownership_fraction,
+        token_supply,
+        display_currency,
+        discount_rate_overrides,
+        rate_formula,
+        scenario_weights,
+        // Serialized as the discount_rates section
+        rate_matrix: _,
+        // Run controls that change no value
+        strict: _,
+        lift_dominance_threshold: _,
+        max_scenarios: _,
+        max_payout_days: _,
+        threads: _,
+    } = inputs;
+    let numbers = |values: &[f64]| JsonValue::Array(values.iter().map(|v| (*v).into()).collect());
+    vec![
+        ("raw_forecast", (*raw_forecast).into()),
+        ("period", period.to_string().to_lowercase().into()),
+        ("risk_free_rate", risk_free_rate.as_decimal().into()),
+        (
+            "platform_risk_premium",
+            platform_risk_premium.as_decimal().into(),
+        ),
+        (
+            "platform_adjustment_factor",
+            (*platform_adjustment_factor).into(),
+        ),
+        ("quarters", (*quarters).into()),
+        (
+            "forecast_confidence",
+            forecast_confidence.to_string().to_lowercase().into(),
+        ),
+        (
+            "stage1_quarters",
+            two_stage_growth.map(|stages| stages.stage1_quarters).into(),
+        ),
+        (
+            "stage1_growth",
+            two_stage_growth.map(|stages| stages.stage1_growth).into(),
+        ),
+        (
+            "stage2_growth",
+            two_stage_growth.map(|stages| stages.stage2_growth).into(),
+        ),
+        ("tax_rate", (*tax_rate).into()),
+        ("after_tax", (*after_tax).into()),
+        ("purchase_price", (*purchase_price).into()),
+        ("market_price", (*market_price).into()),
+        ("ownership_fraction", (*ownership_fraction).into()),
+        (
+            "rate_formula",
+            rate_formula.as_ref().map(|f| f.to_string()).into(),
+        ),
+        (
+            "display_currency",
+            display_currency
+                .as_ref()
+                .map(|currency| currency.code.clone())
+                .into(),
+        ),
+        (
+            "fx_rate",
+            display_currency
+                .as_ref()
+                .map(|currency| currency.fx_rate)
+                .into(),
+        ),
+        ("baseline_audience", (*baseline_audience).into()),
+        ("rpm", (*rpm).into()),
+        ("incremental_rpm", (*incremental_rpm).into()),
+        ("investor_count", (*investor_count).into()),
+        ("lift_per_investor", (*lift_per_investor).into()),
+        ("lift_ramp", lift_ramp.to_string().into()),
+        ("fixed_lift", (*fixed_lift).into()),
+        ("lift_acquisition_cost", (*lift_acquisition_cost).into()),
+        ("baseline_only", (*baseline_only).into()),
+        (
+            "payout_ladder",
+            payout_ladder
+                .as_ref()
+                .map(|ladder| {
+                    JsonValue::Array(
+                        ladder
+                            .tranches
+                            .iter()
+                            .map(|tranche| {
+                                JsonValue::Object(vec![
+                                    ("days".to_string(), tranche.days.into()),
+                                    ("fraction".to_string(), tranche.fraction.into()),
+                                ])
+                            })
+                            .collect(),
+                    )
+                })
+                .into(),
+        ),
+        ("monthly_breakdown", (*monthly_breakdown).into()),
+        ("lift_payout_days", (*lift_payout_days).into()),
+        ("day_count", day_count.to_string().into()),
+        ("compounding", compounding.to_string().into()),
+        ("quarterly_growth_rate", (*quarterly_growth_rate).into()),
+        ("reinvestment_rate", (*reinvestment_rate).into()),
+        ("revenue_floor", (*revenue_floor).into()),
+        ("cap_at_face", (*cap_at_face).into()),
+        ("token_supply", (*token_supply).into()),
+        (
+            "discount_rate_overrides",
+            discount_rate_overrides
+                .as_ref()
+                .map(|rates| {
+                    JsonValue::Array(rates.iter().map(|r| r.as_decimal().into()).collect())
+                })
+                .into(),
+        ),
+        (
+            "scenario_weights",
+            JsonValue::Object(vec![
+                (
+                    "payout".to_string(),
+                    scenario_weights.payout.as_deref().map(numbers).into(),
+                ),
+                (
+                    "volatility".to_string(),
+                    scenario_weights.volatility.as_deref().map(numbers).into(),
+                ),
+                (
+                    "lift".to_string(),
+                    scenario_weights.lift.as_deref().map(numbers).into(),
+                ),
+            ]),
+        ),
+    ]
+}
+
+/// Fields of the JSON report's `summary` section, in document order
+pub fn json_summary_fields(data: &ReportData) -> Vec<(&'static str, JsonValue)> {
+    let summary = &data.summary;
+    vec![
+        ("adjusted_baseline", summary.adjusted_baseline.into()),
+        ("central_estimate", summary.central_estimate.into()),
+        ("central_face_value", summary.central_face_value.into()),
+        ("downside", summary.downside.into()),
+        ("upside", summary.upside.into()),
+        ("min_valuation", summary.min_valuation.into()),
+        ("max_valuation", summary.max_valuation.into()),
+        ("volatility_impact_pct", summary.volatility_impact.into()),
+        ("lift_impact_pct", summary.lift_impact.into()),
+        ("implied_stress", data.implied_stress.into()),
+        ("payout_impact_pct", summary.payout_impact.into()),
+        (
+            "payout_impact_60_90_pct",
+            summary.payout_impact_60_90.into(),
+        ),
+        (
+            "payout_impact_90_120_pct",
+            summary.payout_impact_90_120.into(),
+        ),
+        ("value_ratio", summary.value_ratio.into()),
+        ("scenario_agreement", summary.scenario_agreement.into()),
+        ("position_value", summary.position_value.into()),
+        (
+            "display_central_estimate",
+            summary.display_central_estimate.into(),
+        ),
+    ]
+}
+
+/// Total discount rates for the JSON report, keyed by volatility, or by
+/// payout and volatility for a rate matrix
+pub fn json_discount_rates(data: &ReportData) -> Vec<(String, JsonValue)> {
+    let inputs = &data.inputs;
+    match &inputs.rate_matrix {
+        Some(matrix) => inputs
+            .payout_scenarios()
+            .iter()
+            .flat_map(|&payout| {
+                VolatilityScenario::all()
+                    .iter()
+                    .filter_map(move |&volatility| {
+                        matrix
+                            .rate(payout, volatility)
+                            .map(|rate| (format!("{} / {}", payout, volatility), rate.into()))
+                    })
+            })
+            .collect(),
+        None => VolatilityScenario::all()
+            .iter()
+            .filter_map(|volatility| {
+                data.discount_rates.get(volatility).map(|components| {
+                    (
+                        volatility.to_string(),
+                        components.total_rate().as_decimal().into(),
+                    )
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Fields of one entry of the JSON report's `valuations` section
+pub fn json_valuation_fields(
+    data: &ReportData,
+    valuation: &ValuationResult,
+) -> Vec<(&'static str, JsonValue)> {
+    let tag = |key: &str, value: JsonValue| (key.to_string(), value);
+    vec![
+        ("payout", valuation.payout_scenario.to_string().into()),
+        (
+            "volatility",
+            valuation.volatility_scenario.to_string().into(),
+        ),
+        (
+            "lift",
+            valuation.lift_scenario.map(|l| l.to_string()).into(),
+        ),
+        (
+            "discount_rate",
+            data.discount_rate(valuation.scenario()).into(),
+        ),
+        ("present_value", valuation.present_value.into()),
+        (
+            "pre_tax_present_value",
+            valuation.pre_tax_present_value.into(),
+        ),
+        (
+            "after_tax_present_value",
+            valuation.after_tax_present_value.into(),
+        ),
+        ("face_value", valuation.face_value.into()),
+        ("position_value", valuation.position_value.into()),
+        ("display_value", valuation.display_value.into()),
+        (
+            "tags",
+            JsonValue::Object(vec![
+                tag("is_baseline", valuation.tags.is_baseline.into()),
+                tag("is_central", valuation.tags.is_central.into()),
+                tag("is_min", valuation.tags.is_min.into()),
+                tag("is_max", valuation.tags.is_max.into()),
+                tag("risk_bucket", valuation.tags.risk_bucket.to_string().into()),
+            ]),
+        ),
+    ]
+}
+
+/// Generate the versioned JSON report.
+///
+/// `schema_version` and `model_version` lead the document so archived
+/// reports can be interpreted after the structure or the model changes.
+/// Values are unrounded; the headline `present_value` follows the tax toggle.
+pub fn generate_json_report(data: &ReportData) -> JsonValue {
+    let object = |entries: Vec<(&str, JsonValue)>| {
+        JsonValue::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    };
+    let valuations = data
+        .all_valuations
+        .iter()
+        .map(|valuation| object(json_valuation_fields(data, valuation)))
+        .collect();
+
+    object(vec![
+        ("schema_version", JSON_SCHEMA_VERSION.into()),
+        ("model_version", MODEL_VERSION.into()),
+        ("inputs", object(json_input_fields(&data.inputs))),
+        ("summary", object(json_summary_fields(data))),
+        (
+            "discount_rates",
+            JsonValue::Object(json_discount_rates(data)),
+        ),
+        ("valuations", JsonValue::Array(valuations)),
+        (
+            "assumptions",
+            JsonValue::Array(
+                data.assumptions
+                    .iter()
+                    .zip(format_assumptions(data, &ReportOptions::default()))
+                    .map(|(assumption, statement)| {
+                        object(vec![
+                            ("topic", assumption.topic.into()),
+                            ("statement", statement.into()),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ),
+        (
+            "warnings",
+            JsonValue::Array(
+                format_warnings(data, &ReportOptions::default())
+                    .into_iter()
+                    .map(JsonValue::from)
+                    .collect(),
+            ),
+        ),
+    ])
+}
+
+/// Generate the machine-readable numeric report.
+///
+/// Emits one `label = value` line per figure with every value at full `f64`
+/// precision and no rounding, separators or locale formatting, so the output
+/// stays stable when the human report's presentation changes.
+pub fn generate_raw_report(data: &ReportData) -> String {
+    let mut lines = Vec::new();
+    let mut push = |label: String, value: f64| lines.push(format!("{} = {:?}", label, value));
+
+    let summary = &data.summary;
+    push("adjusted_baseline".to_string(), summary.adjusted_baseline);
+    push("central_estimate".to_string(), summary.central_estimate);
+    push("central_face_value".to_string(), summary.central_face_value);
+    if let Some(value) = summary.position_value {
+        push("position_value".to_string(), value);
+    }
+    if let Some(value) = summary.display_central_estimate {
+        push("display_central_estimate".to_string(), value);
+    }
+    push("min_valuation".to_string(), summary.min_valuation);
+    push("max_valuation".to_string(), summary.max_valuation);
+    push("downside".to_string(), summary.downside);
+    push("upside".to_string(), summary.upside);
+    if let Some(impact) = summary.volatility_impact {
+        push("volatility_impact_pct".to_string(), impact);
+    }
+    if let Some(impact) = summary.lift_impact {
+        push("lift_impact_pct".to_string(), impact);
+    }
+    if let Some(impact) = summary.payout_impact {
+        push("payout_impact_pct".to_string(), impact);
+    }
+    if let Some(impact) = summary.payout_impact_60_90 {
+        push("payout_impact_60_90_pct".to_string(), impact);
+    }
+    if let Some(impact) = summary.payout_impact_90_120 {
+        push("payout_impact_90_120_pct".to_string(), impact);
+    }
+    if let Some(multiple) = summary.quarterly_revenue_multiple {
+        push("quarterly_revenue_multiple".to_string(), multiple);
+    }
+    if let Some(multiple) = summary.annual_revenue_multiple {
+        push("annual_revenue_multiple".to_string(), multiple);
+    }
+    if let Some(ratio) = summary.value_ratio {
+        push("value_ratio".to_string(), ratio);
+    }
+    push("scenario_agreement".to_string(), summary.scenario_agreement);
+
+    match &data.inputs.rate_matrix {
+        Some(matrix) => {
+            for &payout in data.inputs.payout_scenarios() {
+                for &volatility in VolatilityScenario::all() {
+                    if let Some(rate) = matrix.rate(payout, volatility) {
+                        push(format!("discount_rate[{} / {}]", payout, volatility), rate);
+                    }
+                }
+            }
+        }
+        None => {
+            for volatility in VolatilityScenario::all() {
+                if let Some(components) = data.discount_rates.get(volatility) {
+                    push(
+                        format!("discount_rate[{}]", volatility),
+                        components.total_rate().as_decimal(),
+                    );
+                }
+            }
+        }
+    }
+
+    let mut valuations: Vec<&ValuationResult> = data.all_valuations.iter().collect();
+    valuations.sort_by_key(|v| v.scenario());
+    for valuation in valuations {
+        push(
+            format!("present_value[{}]", valuation.scenario()),
+            valuation.present_value,
+        );
+        push(
+            format!("face_value[{}]", valuation.scenario()),
+            valuation.face_value,
+        );
+        if data.inputs.tax_rate > 0.0 {
+            push(
+                format!("pre_tax_present_value[{}]", valuation.scenario()),
+                valuation.pre_tax_present_value,
+            );
+            push(
+                format!("after_tax_present_value[{}]", valuation.scenario()),
+                valuation.after_tax_present_value,
+            );
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Generate the portfolio valuation report
+pub fn generate_portfolio_report(result: &PortfolioResult) -> String {
+    let mut report = Report::default();
+
+    report.prose(format!(
+        "{}\n  Portfolio Valuation of Tokenized Future Advertising Revenue\n{}\n\n",
+        "=".repeat(82),
+        "=".repeat(82)
+    ));
+
+    report.prose(
+        r#"Portfolio Summary
+-----------------
+Each asset is valued independently and the position's share of the token pool is summed.
+The range adds each asset's own worst and best scenarios, which need not occur together.
+
+"#,
+    );
+    report.key_values([
+        (
+            "Total Portfolio Present Value (Central Estimate)",
+            format_currency(result.total_present_value),
+        ),
+        (
+            "Portfolio Range (Sum of Per-Asset Extremes)",
+            format!(
+                "{} to {}",
+                format_currency(result.min_value),
+                format_currency(result.max_value)
+            ),
+        ),
+        ("Number of Positions", result.positions.len().to_string()),
+    ]);
+    report.prose("\n");
+
+    let mut table = TableData::new(
+        "Table P1: Portfolio Positions",
+        [
+            "Asset",
+            "Tokens Held",
+            "Pool Ownership",
+            "Central Value",
+            "Min Value",
+            "Max Value",
+            "Contribution",
+        ],
+    );
+
+    for position in &result.positions {
+        table.add_row(vec![
+            position.name.clone(),
+            (position.tokens_held.round() as i64).to_formatted_string(&Locale::en),
+            format_percent(position.ownership * 100.0, 2),
+            format_currency(position.central_value),
+            format_currency(position.min_value),
+            format_currency(position.max_value),
+            format_percent(result.contribution(position), 1),
+        ]);
+    }
+
+    report.table(table);
+    report.prose("\n");
+    report.render_text()
+}
+
+/// Rounds to `decimals` places, half away from zero.
+///
+/// The decimal point is shifted on the value's shortest decimal form (1.005
+/// becomes "1.005e2", parsed exactly as 100.5) so midpoints round as written,
+/// even though the nearest `f64` to 1.005 lies just below it and `{:.2}`
+/// would round down. Negative zero is normalized so nothing prints as "-0.0".
+fn round_half_away(value: f64, decimals: usize) -> f64 {
+    let scale = 10f64.powi(decimals as i32);
+    let scaled = format!("{}e{}", value, decimals)
+        .parse::<f64>()
+        .unwrap_or(value * scale);
+    let rounded = scaled.round() / scale;
+    if rounded == 0.0 { 0.0 } else { rounded }
+}
+
+/// Formats a number to a fixed number of decimals, rounding half away from zero
+fn format_decimal(value: f64, decimals: usize) -> String {
+    format!("{:.*}", decimals, round_half_away(value, decimals))
+}
+
+/// Formats an investor count: whole counts with digit grouping, expected
+/// active counts to one decimal, e.g., "1,000" or "333.3"
+fn format_investor_count(count: f64) -> String {
+    if count.fract() == 0.0 {
+        (count as i64).to_formatted_string(&Locale::en)
+    } else {
+        format_decimal(count, 1)
+    }
+}
+
+/// Formats a value already expressed in percent, e.g., "12.5%"
+fn format_percent(value: f64, decimals: usize) -> String {
+    format!("{}%", format_decimal(value, decimals))
+}
+
+/// Formats a volatility premium (as percentage) with no decimals when whole
+/// and one otherwise, e.g., "5%" or "2.5%"
+fn format_premium(premium: f64) -> String {
+    let decimals = if (premium - premium.round()).abs() < 1e-9 {
+        0
+    } else {
+        1
+    };
+    format_percent(premium, decimals)
+}
+
+/// Formats a signed percentage change, e.g., "+3.2%" or "-1.0%"
+fn format_signed_percent(value: f64, decimals: usize) -> String {
+    let sign = if round_half_away(value, decimals) >= 0.0 {
+        "+"
+    } else {
+        ""
+    };
+    format!("{}{}", sign, format_percent(value, decimals))
+}
+
+/// Formats a f64 value as a currency string, e.g., "$1,234,567"
+fn format_currency(value: f64) -> String {
+    format_currency_in(value, &Locale::en)
+}
+
+/// Formats a f64 value as a currency string using a locale's digit grouping,
+/// e.g., "$1.234.567" for `de`
+fn format_currency_in(value: f64, locale: &Locale) -> String {
+    format_money(value, locale, "$")
+}
+
+/// Formats a f64 value as whole currency units after `symbol`, using a
+/// locale's digit grouping, e.g., "€1,234,567"
+fn format_money(value: f64, locale: &Locale, symbol: &str) -> String {
+    // Whole units, half away from zero like the other formatters
+    let rounded_value = round_half_away(value, 0) as i64;
+    format!("{}{}", symbol, rounded_value.to_formatted_string(locale))
+}
+
+/// Formats a value as index points relative to a reference of 100, e.g., "85.8 pts"
+fn format_index(value: f64, reference: f64) -> String {
+    format!("{} pts", format_decimal(value / reference * 100.0, 1))
+}
+
+/// Formats an optional price/revenue multiple, e.g., "0.92x", or "N/A"
+fn format_multiple(value: Option<f64>) -> String {
+    value
+        .map(|v| format!("{}x", format_decimal(v, 2)))
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+/// Formats an optional percentage, e.g., "12.3%", or "N/A" when not computed
+fn format_optional_percentage(value: Option<f64>) -> String {
+    value
+        .map(|v| format_percent(v, 1))
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Args;
+    use crate::valuation::{calculate_full_valuation, value_lift_only};
+    use clap::{CommandFactory, FromArgMatches, Parser};
+
+    fn sample_report_data() -> ReportData {
+        let inputs = Args::try_parse_from(["tokval", "--forecast", "220000"])
+            .unwrap()
+            .valuation_inputs()
+            .unwrap();
+        calculate_full_valuation(&inputs).unwrap()
+    }
+
+    #[test]
+    fn test_redact_replaces_absolutes_with_index_points() {
+        let data = sample_report_data();
+        let plain = generate_full_report(&data, &ReportOptions::default());
+        let redacted = generate_full_report(
+            &data,
+            &ReportOptions {
+                redact: true,
+                ..ReportOptions::default()
+            },
+        );
+
+        // No absolute currency figures survive redaction
+        assert!(plain.contains(&format_currency(data.summary.central_estimate)));
+        for valuation in &data.all_valuations {
+            assert!(!redacted.contains(&format_currency(valuation.present_value)));
+        }
+        assert!(!redacted.contains(&format_currency(data.inputs.raw_forecast)));
+        let central_index = data.summary.central_estimate / data.summary.adjusted_baseline * 100.0;
+        assert!(redacted.contains(&format!("{:.1} pts", central_index)));
+        assert!(redacted.contains("100.0 pts"));
+
+        // Percentages and ratios are unchanged
+        for impact in [
+            data.summary.volatility_impact.unwrap(),
+            data.summary.lift_impact.unwrap(),
+            data.summary.payout_impact.unwrap(),
+        ] {
+            let formatted = format!("{:.1}%", impact);
+            assert!(plain.contains(&formatted));
+            assert!(redacted.contains(&formatted));
+        }
+    }
+
+    #[test]
+    fn test_secondary_locale_footnote() {
+        let data = sample_report_data();
+        let report = generate_full_report(
+            &data,
+            &ReportOptions {
+                secondary_locale: Some(Locale::de),
+                ..ReportOptions::default()
+            },
+        );
+
+        assert!(report.contains("Key Figures (de locale)"));
+        assert!(report.contains(&format_currency(data.summary.central_estimate)));
+        assert!(report.contains(&format_currency_in(
+            data.summary.central_estimate,
+            &Locale::de
+        )));
+        assert_eq!(format_currency_in(1_234_567.0, &Locale::de), "$1.234.567");
+    }
+
+    #[test]
+    fn test_custom_scenario_labels() {
+        let data = sample_report_data();
+        let labels = ScenarioLabels::new(
+            None,
+            Some(
+                ["Base", "Moderate", "Elevated", "Severe"]
+                    .map(String::from)
+                    .to_vec(),
+            ),
+            Some(
+                ["Cautious", "Expected", "Bullish"]
+                    .map(String::from)
+                    .to_vec(),
+            ),
+        )
+        .unwrap();
+        let report = generate_full_report(
+            &data,
+            &ReportOptions {
+                labels,
+                ..ReportOptions::default()
+            },
+        );
+
+        assert!(report.contains("Severe (30%)"));
+        assert!(report.contains("Valuation Matrix: Bullish Scenario"));
+        assert!(report.contains("90 Days"));
+        assert!(!report.contains("Extreme"));
+
+        let mismatch = ScenarioLabels::new(Some(vec!["Fast".to_string()]), None, None);
+        assert!(matches!(mismatch, Err(ModelError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_raw_report_is_full_precision() {
+        let data = sample_report_data();
+        let raw = generate_raw_report(&data);
+
+        let value_of = |label: &str| {
+            raw.lines()
+                .find_map(|line| line.strip_prefix(&format!("{} = ", label)))
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap()
+        };
+        assert_eq!(value_of("central_estimate"), data.summary.central_estimate);
+        for valuation in &data.all_valuations {
+            assert_eq!(
+                value_of(&format!("present_value[{}]", valuation.scenario())),
+                valuation.present_value
+            );
+        }
+        assert!(!raw.contains('$'));
+        assert!(!raw.contains(','));
+    }
+
+    #[test]
+    fn test_only_lift_shows_single_matrix() {
+        let data = sample_report_data();
+        let report = generate_full_report(
+            &data,
+            &ReportOptions {
+                only_lift: Some(LiftScenario::Medium),
+                ..ReportOptions::default()
+            },
+        );
+
+        assert!(report.contains("Valuation Matrix: Medium Lift Scenario"));
+        assert!(!report.contains("Valuation Matrix: Low Lift Scenario"));
+        assert!(!report.contains("Valuation Matrix: High Lift Scenario"));
+        assert!(!report.contains("Table 8: Final Valuation Matrix"));
+        assert!("extreme".parse::<LiftScenario>().is_err());
+    }
+
+    #[test]
+    fn test_rate_components_in_basis_points() {
+        let data = sample_report_data();
+        let options = ReportOptions {
+            rate_components_as: RateComponentUnits::Bps,
+            ..ReportOptions::default()
+        };
+        let ctx = ReportContext {
+            data: &data,
+            options: &options,
+        };
+        let table = build_discount_rate_table(&ctx).to_string();
+        let typical = table.lines().find(|line| line.contains("Typical")).unwrap();
+        assert!(typical.contains("450 bps (17% of total)"));
+        assert!(typical.contains("1200 bps (45% of total)"));
+        assert!(typical.contains("2650 bps"));
+        assert!(!table.contains("26.5%"));
+        assert!("basis-points".parse::<RateComponentUnits>().is_err());
+        // Half basis points round away from zero, like every other figure
+        assert_eq!(format_bps(Rate::constant(0.00125)), "13 bps");
+        assert_eq!(format_bps(Rate::constant(-0.00125)), "-13 bps");
+    }
+
+    #[test]
+    fn test_waterfall_lands_on_present_value() {
+        let data = sample_report_data();
+        let options = ReportOptions::default();
+        let ctx = ReportContext {
+            data: &data,
+            options: &options,
+        };
+        let waterfall = build_waterfall(&ctx).unwrap().to_string();
+
+        let central = data
+            .all_valuations
+            .iter()
+            .find(|v| {
+                v.scenario()
+                    == ScenarioId {
+                        payout: PayoutScenario::Day90,
+                        volatility: VolatilityScenario::Typical,
+                        lift: None,
+                    }
+            })
+            .unwrap();
+        let platform_row = waterfall
+            .lines()
+            .find(|line| line.contains("Platform/Publisher Premium"))
+            .unwrap();
+        assert!(platform_row.contains(&format_currency(central.present_value)));
+        assert!(waterfall.contains("100.0%"));
+    }
+
+    #[test]
+    fn test_range_bars_mark_each_payout() {
+        let data = sample_report_data();
+        let options = ReportOptions::default();
+        let ctx = ReportContext {
+            data: &data,
+            options: &options,
+        };
+        let bars = build_range_bars(&ctx).to_string();
+
+        for payout in PayoutScenario::all() {
+            let row = bars
+                .lines()
+                .find(|line| line.contains(&payout.to_string()))
+                .unwrap();
+            assert!(row.contains('|'));
+        }
+        // The shared scale runs from the overall lifted minimum to maximum
+        let lifted = data
+            .all_valuations
+            .iter()
+            .filter(|v| v.lift_scenario.is_some());
+        let max = lifted.map(|v| v.present_value).fold(f64::MIN, f64::max);
+        assert!(bars.contains(&format!("to {})", format_currency(max))));
+    }
+
+    #[test]
+    fn test_input_sources_mark_user_and_default() {
+        let matches = Args::command()
+            .try_get_matches_from(["tokval", "--forecast", "220000", "--rpm", "12"])
+            .unwrap();
+        let args = Args::from_arg_matches(&matches).unwrap();
+        let data = calculate_full_valuation(&args.valuation_inputs().unwrap()).unwrap();
+        let report = generate_full_report(
+            &data,
+            &ReportOptions {
+                input_sources: Some(crate::cli::input_sources(&matches)),
+                ..ReportOptions::default()
+            },
+        );
+
+        let row = |label: &str| {
+            report
+                .lines()
+                .find(|line| line.contains(label))
+                .unwrap()
+                .to_string()
+        };
+        assert!(row("│ RPM").contains("user"));
+        assert!(row("Incremental RPM").contains("user"));
+        assert!(row("Investor Count").contains("default"));
+        assert!(row("│ Raw Quarterly Revenue Forecast").contains("user"));
+    }
+
+    #[test]
+    fn test_zero_investors_collapse_lift_sections() {
+        let inputs = Args::try_parse_from(["tokval", "-f", "220000", "--investor-count", "0"])
+            .unwrap()
+            .valuation_inputs()
+            .unwrap();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let report = generate_full_report(&data, &ReportOptions::default());
+
+        assert!(report.contains("No investor lift modeled (investor_count = 0)"));
+        assert!(!report.contains("Valuation Matrix:"));
+        assert!(!report.contains("Table 3:"));
+        assert!(!report.contains("NaN") && !report.contains("inf"));
+        assert!(report.contains("Section 8: Conclusion"));
+
+        // The lift tables themselves stay finite when rendered directly
+        let ctx = ReportContext {
+            data: &data,
+            options: &ReportOptions::default(),
+        };
+        for table in [
+            build_lift_scenarios_table(&ctx),
+            build_audience_growth_table(&ctx),
+            build_revenue_impact_table(&ctx),
+        ] {
+            let table = table.to_string();
+            assert!(!table.contains("NaN") && !table.contains("inf"));
+        }
+    }
+
+    #[test]
+    fn test_percentile_table_has_row_per_percentile() {
+        let data = sample_report_data();
+        let report = generate_full_report(
+            &data,
+            &ReportOptions {
+                percentiles: vec![1.0, 5.0, 25.0, 50.0, 75.0, 95.0, 99.0],
+                ..ReportOptions::default()
+            },
+        );
+        assert!(report.contains("Table 10: Scenario Distribution Percentiles"));
+        for label in [
+            "│ P1 ", "│ P5 ", "│ P25 ", "│ P50 ", "│ P75 ", "│ P95 ", "│ P99 ",
+        ] {
+            assert!(report.contains(label), "missing row {}", label);
+        }
+
+        let report = generate_full_report(&data, &ReportOptions::default());
+        assert!(!report.contains("Table 10"));
+
+        // Table 10 is opt-in on the command line too
+        let args = Args::try_parse_from(["tokval", "--forecast", "220000"]).unwrap();
+        assert!(args.percentiles.is_empty());
+
+        // A zero central estimate has no percentage change to show
+        assert_eq!(format_change_from_central(0.0, 150_000.0), "N/A");
+        assert_eq!(format_change_from_central(200_000.0, 150_000.0), "-25.0%");
+    }
+
+    #[test]
+    fn test_display_renders_full_report() {
+        let data = sample_report_data();
+        let displayed = data.to_string();
+        let report = generate_full_report(&data, &ReportOptions::default());
+
+        // Compare up to the conclusion, whose timestamp may tick between calls
+        let cut = |text: &str| {
+            text.split("Section 8: Conclusion")
+                .next()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(cut(&displayed), cut(&report));
+        assert!(displayed.contains("Section 8: Conclusion"));
+        assert!(format!("{:?}", data).starts_with("ReportData"));
+    }
+
+    #[test]
+    fn test_step_deltas_follow_each_matrix() {
+        let data = sample_report_data();
+        let report = generate_full_report(&data, &ReportOptions::default());
+        assert!(!report.contains("Volatility steps"));
+
+        let report = generate_full_report(
+            &data,
+            &ReportOptions {
+                show_step_deltas: true,
+                ..ReportOptions::default()
+            },
+        );
+        assert_eq!(report.matches("Volatility steps (90 Days):").count(), 3);
+        assert!(report.contains("Low Vol -> Typical: -"));
+        assert!(report.contains("High Vol -> Extreme: -"));
+    }
+
+    #[test]
+    fn test_discounting_haircut_in_insights() {
+        let mut data = sample_report_data();
+        let summary = &data.summary;
+        let haircut = discounting_haircut(summary).unwrap();
+        assert!(
+            (summary.central_face_value * (1.0 - haircut / 100.0) - summary.central_estimate).abs()
+                < 1e-6
+        );
+        let report = generate_full_report(&data, &ReportOptions::default());
+        assert!(report.contains(&format!(
+            "* Discounting reduces the {} face value by {} to {}.",
+            format_currency(summary.central_face_value),
+            format_percent(haircut, 1),
+            format_currency(summary.central_estimate)
+        )));
+
+        data.summary.central_face_value = 0.0;
+        assert_eq!(discounting_haircut(&data.summary), None);
+    }
+
+    #[test]
+    fn test_negative_lift_impact_is_not_called_an_increase() {
+        let mut data = sample_report_data();
+        data.summary.lift_impact = Some(-3.0);
+        let report = generate_full_report(&data, &ReportOptions::default());
+        assert!(
+            report.contains(
+                "Moving from a Low to a High Lift scenario changes the valuation by -3.0%."
+            )
+        );
+        assert!(
+            report
+                .contains("Moving from low to high investor participation changes value by -3.0%.")
+        );
+        assert!(!report.contains("increases the valuation by -"));
+        assert!(!report.contains("enhance value by up to -"));
+    }
+
+    #[test]
+    fn test_value_ratio_in_insights() {
+        let data = sample_report_data();
+        assert!(!generate_full_report(&data, &ReportOptions::default()).contains("Value ratio"));
+
+        let inputs = Args::try_parse_from(["tokval", "-f", "220000", "--purchase-price", "150000"])
+            .unwrap()
+            .valuation_inputs()
+            .unwrap();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let report = generate_full_report(&data, &ReportOptions::default());
+        let expected = format!(
+            "Value ratio at a $150,000 purchase price: {}",
+            format_multiple(data.summary.value_ratio)
+        );
+        assert!(report.contains(&expected));
+        assert!(report.contains("Low Lift ") && report.contains("High Lift "));
+        assert!(generate_raw_report(&data).contains("value_ratio = "));
+    }
+
+    #[test]
+    fn test_since_baseline_annotates_lift_cells() {
+        let mut data = sample_report_data();
+        let options = ReportOptions {
+            since_baseline: true,
+            ..ReportOptions::default()
+        };
+        let ctx = ReportContext {
+            data: &data,
+            options: &options,
+        };
+        let table = build_valuation_table(&ctx, LiftScenario::High).to_string();
+        let cell = |lift| {
+            data.all_valuations
+                .iter()
+                .find(|v| {
+                    v.scenario()
+                        == ScenarioId {
+                            payout: PayoutScenario::Day60,
+                            volatility: VolatilityScenario::Low,
+                            lift,
+                        }
+                })
+                .unwrap()
+                .present_value
+        };
+        let lifted = cell(Some(LiftScenario::High));
+        let expected = format!(
+            "{} ({:+.1}%)",
+            format_currency(lifted),
+            (lifted / cell(None) - 1.0) * 100.0
+        );
+        assert!(table.contains(&expected), "missing {}", expected);
+
+        // A zero baseline cell yields N/A rather than dividing by zero
+        for v in data.all_valuations.iter_mut() {
+            if v.lift_scenario.is_none() {
+                v.present_value = 0.0;
+            }
+        }
+        let ctx = ReportContext {
+            data: &data,
+            options: &options,
+        };
+        let table = build_valuation_table(&ctx, LiftScenario::High).to_string();
+        assert!(table.contains("(N/A)"));
+        assert!(!table.contains("inf"));
+    }
+
+    #[test]
+    fn test_formatting_rounds_half_away_from_zero() {
+        assert_eq!(format_percent(12.45, 1), "12.5%");
+        assert_eq!(format_percent(12.55, 1), "12.6%");
+        assert_eq!(format_percent(-12.45, 1), "-12.5%");
+        assert_eq!(format_percent(12.44, 1), "12.4%");
+        assert_eq!(format_percent(2.5, 0), "3%");
+        assert_eq!(format_decimal(1.005, 2), "1.01");
+        assert_eq!(format_signed_percent(3.25, 1), "+3.3%");
+        assert_eq!(format_signed_percent(-0.04, 1), "+0.0%");
+        assert_eq!(format_signed_percent(-0.05, 1), "-0.1%");
+        assert_eq!(format_percent(-0.01, 1), "0.0%");
+        assert_eq!(format_currency(1_234.5), "$1,235");
+        assert_eq!(format_currency(-1_234.5), "$-1,235");
+        assert_eq!(format_multiple(Some(0.125)), "0.13x");
+    }
+
+    #[test]
+    fn test_display_currency_converts_monetary_figures() {
+        let inputs = Args::try_parse_from([
+            "tokval",
+            "-f",
+            "220000",
+            "--display-currency",
+            "eur",
+            "--fx-rate",
+            "0.92",
+        ])
+        .unwrap()
+        .valuation_inputs()
+        .unwrap();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let base = sample_report_data();
+        assert_eq!(data.summary.central_estimate, base.summary.central_estimate);
+        assert_eq!(
+            data.summary.display_central_estimate,
+            Some(base.summary.central_estimate * 0.92)
+        );
+
+        let report = generate_full_report(&data, &ReportOptions::default());
+        assert!(report.contains(&format!(
+            "estimated to be {}.",
+            format_money(base.summary.central_estimate * 0.92, &Locale::en, "€")
+        )));
+        assert!(report.contains("* Revenue per Thousand Impressions (RPM): €13.80"));
+        assert!(
+            report.contains(
+                "* RPM: Flat €13.80 on the baseline audience and €13.80 on lift audience"
+            )
+        );
+        assert!(report.contains(
+            "* Currency: Valued in USD; monetary figures converted to EUR at 0.92 EUR per USD"
+        ));
+        assert!(!report.contains(&format_currency(base.summary.central_estimate)));
+
+        let json = JsonValue::parse(&generate_json_report(&data).to_string()).unwrap();
+        let inputs = json.get("inputs").unwrap();
+        assert_eq!(
+            inputs.get("display_currency").and_then(JsonValue::as_str),
+            Some("EUR")
+        );
+        assert_eq!(
+            inputs.get("fx_rate").and_then(JsonValue::as_f64),
+            Some(0.92)
+        );
+    }
+
+    #[test]
+    fn test_display_currency_leaves_no_dollar_amounts() {
+        let inputs = Args::try_parse_from([
+            "tokval",
+            "-f",
+            "40000",
+            "--investor-count",
+            "100000",
+            "--quarters",
+            "4",
+            "--market-price",
+            "9999999",
+            "--display-currency",
+            "eur",
+            "--fx-rate",
+            "0.92",
+        ])
+        .unwrap()
+        .valuation_inputs()
+        .unwrap();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert_eq!(data.warnings.len(), 2);
+
+        let options = ReportOptions::default();
+        let outputs = [
+            generate_full_report(&data, &options),
+            generate_lift_explanation(&data, &options),
+            format_warnings(&data, &options).join("\n"),
+        ];
+        for output in outputs {
+            assert!(output.contains('€'));
+            assert!(!output.contains('$'), "{}", output);
+        }
+    }
+
+    #[test]
+    fn test_position_value_leads_summary_and_scales_matrices() {
+        let inputs =
+            Args::try_parse_from(["tokval", "-f", "220000", "--ownership-fraction", "0.025"])
+                .unwrap()
+                .valuation_inputs()
+                .unwrap();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let report = generate_full_report(&data, &ReportOptions::default());
+
+        let line = format!(
+            "* Your position's fair value: {} (2.50% of the pool's central estimate).",
+            format_currency(data.summary.central_estimate * 0.025)
+        );
+        assert!(report.contains(&line));
+        assert!(report.contains(
+            "Table 7: Final Valuation Matrix - Medium Lift Scenario - Your Position (2.50% of Pool)"
+        ));
+        let central_cell = data
+            .all_valuations
+            .iter()
+            .find(|v| {
+                v.scenario()
+                    == ScenarioId {
+                        payout: PayoutScenario::Day90,
+                        volatility: VolatilityScenario::Typical,
+                        lift: Some(LiftScenario::Medium),
+                    }
+            })
+            .unwrap();
+        assert!(report.contains(&format_currency(central_cell.position_value.unwrap())));
+
+        assert!(
+            !generate_full_report(&sample_report_data(), &ReportOptions::default())
+                .contains("Your position")
+        );
+    }
+
+    #[test]
+    fn test_long_csv_has_a_row_per_cell() {
+        let inputs = Args::try_parse_from([
+            "tokval",
+            "-f",
+            "220000",
+            "--tokens-held",
+            "100",
+            "--token-supply",
+            "1000",
+        ])
+        .unwrap()
+        .valuation_inputs()
+        .unwrap();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let csv = generate_long_csv(&data);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "payout,volatility,lift,discount_rate,growth_rate,cash_flow,present_value,retention_ratio,per_token,position_value"
+        );
+        assert_eq!(lines.len(), 1 + data.all_valuations.len());
+        assert!(lines[1].starts_with("60 Days,Low Vol,Baseline,"));
+
+        let first = &data.all_valuations[0];
+        let fields: Vec<&str> = lines[1].split(',').collect();
+        assert_eq!(fields.len(), 10);
+        assert_eq!(fields[6].parse::<f64>().unwrap(), first.present_value);
+        assert_eq!(
+            fields[8].parse::<f64>().unwrap(),
+            first.present_value / 1000.0
+        );
+        assert_eq!(fields[9].parse::<f64>().unwrap(), first.present_value * 0.1);
+    }
+
+    #[test]
+    fn test_discount_curve_csv_has_a_column_per_volatility() {
+        let data = sample_report_data();
+        let csv = generate_discount_curve_csv(&data, 30).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "days,Low Vol,Typical,High Vol,Extreme");
+        assert_eq!(lines[1], "0,1,1,1,1");
+        assert_eq!(lines.len(), 1 + 5);
+        let last: Vec<f64> = lines[5].split(',').map(|f| f.parse().unwrap()).collect();
+        assert_eq!(last[0], 120.0);
+        // Riskier scenarios discount harder
+        assert!(last[1..].windows(2).all(|pair| pair[1] < pair[0]));
+    }
+
+    #[test]
+    fn test_sections_reorder_and_filter() {
+        let data = sample_report_data();
+        let full = generate_full_report(&data, &ReportOptions::default());
+        let explicit = generate_full_report(
+            &data,
+            &ReportOptions {
+                sections: Some(ReportSection::all().to_vec()),
+                ..ReportOptions::default()
+            },
+        );
+        let cut = |text: &str| {
+            text.split("Section 8: Conclusion")
+                .next()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(cut(&full), cut(&explicit));
+
+        let sections: Vec<ReportSection> = "insights,summary"
+            .split(',')
+            .map(|s| s.parse().unwrap())
+            .collect();
+        let report = generate_full_report(
+            &data,
+            &ReportOptions {
+                sections: Some(sections),
+                ..ReportOptions::default()
+            },
+        );
+        let insights = report.find("Section 7: Key Insights").unwrap();
+        let summary = report.find("Executive Summary").unwrap();
+        assert!(insights < summary);
+        assert!(!report.contains("Section 1: Methodology"));
+        assert!(!report.contains("Table 2:"));
+        assert!("charts".parse::<ReportSection>().is_err());
+    }
+
+    #[test]
+    fn test_prometheus_exposes_headline_gauges() {
+        let data = sample_report_data();
+        let text = generate_prometheus(&data, &[("asset", "pub \"A\"\\1")]);
+        assert!(text.contains("# TYPE tokval_central_estimate gauge\n"));
+        assert!(text.contains(&format!(
+            "tokval_central_estimate{{asset=\"pub \\\"A\\\"\\\\1\"}} {}\n",
+            data.summary.central_estimate
+        )));
+        let samples: Vec<&str> = text.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(samples.len(), 8);
+
+        let mut baseline = sample_report_data();
+        baseline.summary.lift_impact = None;
+        let text = generate_prometheus(&baseline, &[]);
+        assert!(!text.contains("lift_impact"));
+        assert!(text.contains(&format!(
+            "\ntokval_min_valuation {}\n",
+            data.summary.min_valuation
+        )));
+    }
+
+    #[test]
+    fn test_json_report_leads_with_versions() {
+        let data = sample_report_data();
+        let json = JsonValue::parse(&generate_json_report(&data).to_string()).unwrap();
+        let entries = json.as_object().unwrap();
+        assert_eq!(entries[0].0, "schema_version");
+        assert_eq!(entries[1].0, "model_version");
+        assert_eq!(
+            json.get("schema_version").and_then(JsonValue::as_f64),
+            Some(JSON_SCHEMA_VERSION as f64)
+        );
+        assert_eq!(
+            json.get("model_version").and_then(JsonValue::as_str),
+            Some(MODEL_VERSION)
+        );
+        let valuations = json
+            .get("valuations")
+            .and_then(JsonValue::as_array)
+            .unwrap();
+        assert_eq!(valuations.len(), data.all_valuations.len());
+        let assumptions = json
+            .get("assumptions")
+            .and_then(JsonValue::as_array)
+            .unwrap();
+        assert_eq!(assumptions.len(), data.assumptions.len());
+        assert_eq!(
+            assumptions[0].get("topic").and_then(JsonValue::as_str),
+            Some("Discounting")
+        );
+
+        let report = generate_full_report(&data, &ReportOptions::default());
+        assert!(report.contains(&format!("Model Version: {}", MODEL_VERSION)));
+    }
+
+    #[test]
+    fn test_per_token_display_reconstructs_total() {
+        for (total, supply) in [
+            (141_857.37, 3_000_000.0),
+            (189_001.004, 7.0),
+            (12_345_678.9, 123_456_789.0),
+        ] {
+            let per_token = reconcile_per_token(total, supply);
+            let displayed: f64 = format_decimal(per_token.value, per_token.decimals)
+                .parse()
+                .unwrap();
+            assert!((displayed * supply - total).abs() <= 0.01);
+            assert!(per_token.decimals >= 2);
+        }
+        // Cents suffice for an even split; a third decimal is needed for sevenths
+        assert_eq!(reconcile_per_token(1_000.0, 8.0).decimals, 2);
+        assert_eq!(reconcile_per_token(1_000.0, 7.0).decimals, 3);
+
+        // Beyond the decimal cap the residual is noted instead
+        let line = format_per_token_line(1_000_000.0, 3e14, "$");
+        assert!(line.contains("rounding residual"));
+
+        let mut data = sample_report_data();
+        data.inputs.token_supply = Some(3_000_000.0);
+        let report = generate_full_report(&data, &ReportOptions::default());
+        assert!(report.contains("* Per-token fair value: $"));
+        assert!(report.contains("reconstructs the central estimate to the cent"));
+    }
+
+    #[test]
+    fn test_markdown_report_renders_the_same_layout() {
+        let data = sample_report_data();
+        let options = ReportOptions::default();
+        let markdown = generate_markdown_report(&data, &options);
+        let text = generate_full_report(&data, &options);
+
+        assert!(markdown.contains(
+            "**Table 2: Baseline Valuation Matrix (No Investor Lift)**\n\n| Payout Timing |"
+        ));
+        assert!(markdown.contains("* **Adjusted Baseline Revenue:** "));
+        assert!(!markdown.contains('╭'));
+        // Both formats carry every table, in the same order
+        let captions = |report: &str| -> Vec<String> {
+            report
+                .lines()
+                .map(|line| line.trim_matches('*').to_string())
+                .filter(|line| line.starts_with("Table "))
+                .collect()
+        };
+        assert_eq!(captions(&markdown), captions(&text));
+        assert!(!captions(&text).is_empty());
+    }
+
+    #[test]
+    fn test_heatmap_shades_each_matrix() {
+        let data = sample_report_data();
+        let blocks = generate_full_report(
+            &data,
+            &ReportOptions {
+                heatmap: Some(HeatmapStyle::Blocks),
+                ..ReportOptions::default()
+            },
+        );
+        // Baseline plus three lift matrices, each with its own scale
+        assert_eq!(blocks.matches("Shading spans").count(), 4);
+        assert!(blocks.contains(&format!(
+            "│ 60 Days       ┆ █ {}",
+            format_currency(data.summary.max_valuation)
+        )));
+        assert!(blocks.contains("┆ ░ $"));
+        assert!(!blocks.contains('\x1b'));
+
+        let options = ReportOptions::default();
+        let ctx = ReportContext {
+            data: &data,
+            options: &options,
+        };
+        let colored = build_valuation_heatmap(&ctx, Some(LiftScenario::High), HeatmapStyle::Color);
+        assert!(colored.starts_with("Table 8: Final Valuation Matrix - High Lift Scenario"));
+        assert!(colored.contains("\x1b[48;2;99;190;123m"));
+        assert_eq!(heatmap_color(0.0), (248, 105, 107));
+        assert_eq!(heatmap_color(0.5), (255, 235, 132));
+    }
+
+    #[test]
+    fn test_ranked_table_lists_highest_values_first() {
+        let data = sample_report_data();
+        let options = ReportOptions {
+            ranked: Some(3),
+            ..ReportOptions::default()
+        };
+        let table = build_ranked_table(
+            &ReportContext {
+                data: &data,
+                options: &options,
+            },
+            3,
+        );
+        assert_eq!(
+            table.caption,
+            "Table 9b: Top 3 of 48 Scenarios by Present Value"
+        );
+        assert_eq!(table.rows.len(), 3);
+        assert_eq!(table.rows[0][1], data.summary.max_scenario.to_string());
+        assert_eq!(
+            table.rows[0][2],
+            format_currency(data.summary.max_valuation)
+        );
+
+        let values: Vec<f64> = data
+            .all_valuations
+            .iter()
+            .map(|v| v.present_value)
+            .collect();
+        let all = build_ranked_table(
+            &ReportContext {
+                data: &data,
+                options: &options,
+            },
+            usize::MAX,
+        );
+        assert_eq!(all.caption, "Table 9b: Scenarios Ranked by Present Value");
+        assert_eq!(all.rows.len(), values.len());
+        assert_eq!(
+            all.rows.last().unwrap()[2],
+            format_currency(data.summary.min_valuation)
+        );
+
+        let report = generate_full_report(&data, &options);
+        assert!(
+            report.find("Table 9: Valuation Range").unwrap() < report.find("Table 9b").unwrap()
+        );
+        assert!(!generate_full_report(&data, &ReportOptions::default()).contains("Table 9b"));
+    }
+
+    #[test]
+    fn test_lift_option_matrix_follows_range_bars() {
+        let mut data = sample_report_data();
+        let report = generate_full_report(&data, &ReportOptions::default());
+        assert!(!report.contains("Table 9a"));
+
+        data.lift_option = Some(value_lift_only(&data).unwrap());
+        let report = generate_full_report(&data, &ReportOptions::default());
+        let table = report.find("Table 9a: Growth Option Value").unwrap();
+        assert!(report.find("Table 9: Valuation Range").unwrap() < table);
+        assert!(table < report.find("Section 7").unwrap());
+        let medium_90 = report[table..]
+            .lines()
+            .find(|line| line.contains("Medium Lift") && line.contains("90 Days"))
+            .unwrap();
+        let option = data.lift_option.as_ref().unwrap().iter().find(|v| {
+            v.scenario
+                == ScenarioId {
+                    payout: PayoutScenario::Day90,
+                    volatility: VolatilityScenario::Typical,
+                    lift: Some(LiftScenario::Medium),
+                }
+        });
+        assert!(medium_90.contains(&format_currency(option.unwrap().present_value)));
+    }
+
+    #[test]
+    fn test_lift_model_comparison_closes_lift_section() {
+        let inputs =
+            Args::try_parse_from(["tokval", "--forecast", "220000", "--fixed-lift", "5000"])
+                .unwrap()
+                .valuation_inputs()
+                .unwrap();
+        let mut data = calculate_full_valuation(&inputs).unwrap();
+        let report = generate_full_report(&data, &ReportOptions::default());
+        assert!(report.contains("* Fixed Quarterly Lift (replaces the drivers above): $5,000"));
+        assert!(!report.contains("Table 5a"));
+
+        let comparison = crate::valuation::compare_lift_models(&inputs).unwrap();
+        data.lift_model_comparison = Some(comparison.clone());
+        let report = generate_full_report(&data, &ReportOptions::default());
+        let table = report
+            .find("Table 5a: Central Estimate by Lift Model")
+            .unwrap();
+        assert!(report.find("Table 5: Revenue Impact").unwrap() < table);
+        assert!(table < report.find("Section 6:").unwrap());
+        assert!(report.contains(&format!(
+            "│ Driver-Based                      ┆ {}",
+            format_currency(comparison.driver_based)
+        )));
+        assert!(report.contains(&format_signed_percent(comparison.gap_percentage(), 2)));
+    }
+
+    #[test]
+    fn test_market_price_reports_implied_stress() {
+        let mut data = sample_report_data();
+        let report = generate_full_report(&data, &ReportOptions::default());
+        assert!(!report.contains("Market-Implied Stress"));
+
+        data.implied_stress = Some(0.4567);
+        let report = generate_full_report(&data, &ReportOptions::default());
+        assert!(
+            report.contains("* Market-Implied Stress: The market price implies stress-level 0.46 ")
+        );
+        let json = JsonValue::parse(&generate_json_report(&data).to_string()).unwrap();
+        assert_eq!(
+            json.get("summary")
+                .and_then(|summary| summary.get("implied_stress"))
+                .and_then(JsonValue::as_f64),
+            Some(0.4567)
+        );
+    }
+
+    #[test]
+    fn test_market_price_warning_follows_currency_and_redaction() {
+        let mut data = sample_report_data();
+        data.warnings = vec![ModelWarning::MarketPriceOutOfRange {
+            market_price: 999_999.0,
+            low: 150_000.0,
+            high: 250_000.0,
+        }];
+        let plain = format_warnings(&data, &ReportOptions::default());
+        assert_eq!(
+            plain,
+            [
+                "Market price $999,999 lies outside the $150,000 to $250,000 that stressing the volatility scenarios can rationalize"
+            ]
+        );
+
+        let redacted = ReportOptions {
+            redact: true,
+            ..ReportOptions::default()
+        };
+        let report = generate_full_report(&data, &redacted);
+        assert!(!report.contains("999,999"));
+        assert!(
+            format_warnings(&data, &redacted)[0]
+                .contains(&format_index(999_999.0, data.summary.adjusted_baseline))
+        );
+
+        data.inputs.display_currency = Some(DisplayCurrency {
+            code: "EUR".to_string(),
+            fx_rate: 0.92,
+        });
+        let converted = format_warnings(&data, &ReportOptions::default());
+        assert!(converted[0].starts_with("Market price €"));
+        assert!(!converted[0].contains('$'));
+    }
+
+    #[test]
+    fn test_lift_dominance_warning_follows_currency_and_redaction() {
+        let mut data = sample_report_data();
+        data.warnings = vec![ModelWarning::LiftDominance {
+            period: RevenuePeriod::Quarterly,
+            threshold: 1.0,
+            adjusted_baseline: 40_000.0,
+            lifts: vec![(LiftScenario::Medium, 45_000.0)],
+        }];
+        assert_eq!(
+            format_warnings(&data, &ReportOptions::default()),
+            [
+                "Quarterly lift exceeds 1.00x the adjusted baseline ($40,000) in Medium Lift ($45,000); check that lift inputs are quarterly, not monthly"
+            ]
+        );
+
+        let redacted = ReportOptions {
+            redact: true,
+            ..ReportOptions::default()
+        };
+        let warning = &format_warnings(&data, &redacted)[0];
+        assert!(!warning.contains("45,000"));
+        assert!(warning.contains(&format_index(45_000.0, data.summary.adjusted_baseline)));
+    }
+
+    #[test]
+    fn test_lift_explanation_walks_through_period_lift() {
+        let inputs = Args::try_parse_from([
+            "tokval",
+            "-f",
+            "220000",
+            "--lift-ramp",
+            "linear",
+            "--period",
+            "monthly",
+        ])
+        .unwrap()
+        .valuation_inputs()
+        .unwrap();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let walkthrough = generate_lift_explanation(&data, &ReportOptions::default());
+        let ctx = ReportContext {
+            data: &data,
+            options: &ReportOptions::default(),
+        };
+        let lifts: Vec<&str> = walkthrough.split("\n\n").collect();
+        assert_eq!(lifts.len(), LiftScenario::all().len());
+        for (text, &lift) in lifts.iter().zip(LiftScenario::all()) {
+            assert!(text.starts_with(&format!("{}:", lift)));
+            assert!(text.contains(&format!("Lift ramp:            {}", LiftRamp::Linear)));
+            let last = text.lines().last().unwrap();
+            assert!(last.trim_start().starts_with("Revenue per month:"));
+            assert!(last.ends_with(&format!(
+                "= {}",
+                ctx.currency(data.lift_assumptions.period_lift(lift))
+            )));
+        }
+
+        // Amounts are redacted like the report's
+        let redacted = generate_lift_explanation(
+            &data,
+            &ReportOptions {
+                redact: true,
+                ..ReportOptions::default()
+            },
+        );
+        let high = data.lift_assumptions.period_lift(LiftScenario::High);
+        assert!(walkthrough.contains(&format_currency(high)));
+        assert!(!redacted.contains(&format_currency(high)));
+        assert!(redacted.contains(" pts"));
+
+        let mut data = data;
+        data.lift_assumptions.fixed_lift = Some(5000.0);
+        let ctx = ReportContext {
+            data: &data,
+            options: &ReportOptions::default(),
+        };
+        assert_eq!(
+            explain_lift(&ctx, LiftScenario::High),
+            "High Lift:\n  Fixed monthly lift:   $5,000 × 150% activation = $7,500"
+        );
+    }
+
+    #[test]
+    fn test_summary_keeps_fractional_volatility_premiums() {
+        let inputs =
+            Args::try_parse_from(["tokval", "-f", "220000", "--forecast-confidence", "high"])
+                .unwrap()
+                .valuation_inputs()
+                .unwrap();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let report = generate_full_report(&data, &ReportOptions::default());
+        assert!(report.contains("* Market Volatility: Moving from 7.5% to 20% volatility"));
+        assert_eq!(format_premium(5.0), "5%");
+        assert_eq!(format_premium(2.5), "2.5%");
+    }
+
+    #[test]
+    fn test_show_formula_footnotes_tables() {
+        let data = sample_report_data();
+        let plain = generate_full_report(&data, &ReportOptions::default());
+        assert!(!plain.contains("Formula:"));
+
+        let options = ReportOptions {
+            show_formula: true,
+            ..ReportOptions::default()
+        };
+        let report = generate_full_report(&data, &options);
+        let after = |caption: &str| {
+            let rest = &report[report.find(caption).unwrap()..];
+            rest[rest.find('╯').unwrap()..]
+                .lines()
+                .nth(2)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(
+            after("Table 1: Risk-Adjusted"),
+            "Formula: total = rf + vol_premium + platform_premium"
+        );
+        assert_eq!(
+            after("Table 3: Investor Lift"),
+            "Formula: audience = investors × lift_per_investor × activation"
+        );
+        assert_eq!(
+            after("Table 2: Baseline"),
+            "Formula: PV = CF / (1+r)^(days/365)"
+        );
+        assert_eq!(
+            after("Table 8: Final"),
+            "Formula: PV = CF / (1+r)^(days/365)"
+        );
+        assert_eq!(report.matches("Formula: PV").count(), 4);
+
+        let markdown = generate_markdown_report(&data, &options);
+        assert!(markdown.contains("Formula: total = rf + vol_premium + platform_premium"));
+
+        // The footnotes follow the inputs that change the computation
+        let report = |argv: &[&str]| {
+            let mut full = vec!["tokval", "--forecast", "220000"];
+            full.extend_from_slice(argv);
+            let inputs = Args::try_parse_from(full)
+                .unwrap()
+                .valuation_inputs()
+                .unwrap();
+            let data = calculate_full_valuation(&inputs).unwrap();
+            generate_full_report(&data, &options)
+        };
+        let conventions = report(&["--day-count", "actual360", "--compounding", "continuous"]);
+        assert!(conventions.contains("Formula: PV = CF × e^(-r × days/360)"));
+        assert!(!conventions.contains("days/365"));
+        let formula = report(&["--rate-formula", "rf + 2 * platform_premium"]);
+        assert!(formula.contains("Formula: total = rf + 2 * platform_premium"));
+        assert!(!formula.contains("vol_premium + platform_premium"));
+        let overridden = report(&["--discount-rates", "0.1,0.2,0.3,0.4"]);
+        assert!(!overridden.contains("Formula: total"));
+    }
+
+    #[test]
+    fn test_value_at_risk_is_worded_by_sign() {
+        let report = |argv: &[&str]| {
+            let mut full = vec!["tokval", "--forecast", "220000"];
+            full.extend_from_slice(argv);
+            let inputs = Args::try_parse_from(full)
+                .unwrap()
+                .valuation_inputs()
+                .unwrap();
+            let data = calculate_full_valuation(&inputs).unwrap();
+            (
+                value_at_risk(&data, 0.95),
+                generate_full_report(&data, &ReportOptions::default()),
+            )
+        };
+        let (var, plain) = report(&[]);
+        assert!(var > 0.0);
+        assert!(plain.contains(&format!(
+            "* 5% VaR: {} below central.",
+            format_currency(var)
+        )));
+
+        // All weight on the best volatility and payout puts the tail above central
+        let (var, skewed) = report(&[
+            "--volatility-weights",
+            "1,0,0,0",
+            "--payout-weights",
+            "1,0,0",
+        ]);
+        assert!(var < 0.0);
+        assert!(skewed.contains(&format!(
+            "* 5% VaR: {} above central.",
+            format_currency(-var)
+        )));
+        assert!(!skewed.contains("$-"));
+    }
+
+    #[test]
+    fn test_portfolio_range_is_labelled_as_per_asset_extremes() {
+        let position = |name: &str, central_value: f64| crate::portfolio::PositionResult {
+            name: name.to_string(),
+            tokens_held: 100.0,
+            ownership: 0.1,
+            central_value,
+            min_value: central_value / 2.0,
+            max_value: central_value * 2.0,
+        };
+        let result = PortfolioResult {
+            positions: vec![position("Alpha", 1_000.0), position("Beta", 3_000.0)],
+            total_present_value: 4_000.0,
+            min_value: 2_000.0,
+            max_value: 8_000.0,
+        };
+        let report = generate_portfolio_report(&result);
+        assert!(report.contains("Portfolio Range (Sum of Per-Asset Extremes)"));
+        assert!(report.contains("$2,000 to $8,000"));
+        assert!(report.contains("which need not occur together"));
+    }
+
+    #[test]
+    fn test_zero_base_changes_render_as_not_available() {
+        let inputs = Args::try_parse_from([
+            "tokval",
+            "--forecast",
+            "220000",
+            "--platform-adjustment",
+            "-100",
+            "--baseline-only",
+        ])
+        .unwrap()
+        .valuation_inputs()
+        .unwrap();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert_eq!(data.summary.volatility_impact, None);
+        assert_eq!(data.summary.payout_impact, None);
+
+        let report = generate_full_report(&data, &ReportOptions::default());
+        assert!(!report.contains("NaN") && !report.contains("inf%"));
+        assert!(report.contains("decreases the asset's valuation by N/A."));
+        assert!(report.contains("to $0 (N/A; longest payout"));
+        assert!(report.contains("Payout Cycle: N/A (no percentage change from a zero valuation)."));
+        assert!(!report.contains("tranche ladder"));
+
+        let json = JsonValue::parse(&generate_json_report(&data).to_string()).unwrap();
+        let summary = json.get("summary").unwrap();
+        for key in ["volatility_impact_pct", "payout_impact_pct"] {
+            assert_eq!(summary.get(key), Some(&JsonValue::Null), "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_lift_payback_in_periods() {
+        let report = |argv: &[&str]| {
+            let mut full = vec!["tokval", "--forecast", "220000"];
+            full.extend_from_slice(argv);
+            let inputs = Args::try_parse_from(full)
+                .unwrap()
+                .valuation_inputs()
+                .unwrap();
+            let data = calculate_full_valuation(&inputs).unwrap();
+            generate_full_report(&data, &ReportOptions::default())
+        };
+
+        // Medium lift earns 10,000 visitors x $15 RPM x 3 months = $450 a quarter
+        let text = report(&["--lift-acquisition-cost", "900"]);
+        let row = text
+            .lines()
+            .find(|line| line.contains("Payback on $900 Lift Acquisition Cost"))
+            .unwrap();
+        assert!(row.contains("4.0 quarters ┆ 2.0 quarters ┆ 1.3 quarters"));
+
+        let text = report(&["--lift-acquisition-cost", "900", "--fixed-lift", "0"]);
+        assert!(text.contains("The $900 lift acquisition cost is never paid back."));
+        assert!(!report(&[]).contains("Payback"));
+    }
+
+    #[test]
+    fn test_audience_locale_groups_audience_counts() {
+        let data = sample_report_data();
+        let default = generate_full_report(&data, &ReportOptions::default());
+        assert!(default.contains("│ Total Monthly Audience ┆ 1,000,000"));
+
+        let report = generate_full_report(
+            &data,
+            &ReportOptions {
+                audience_locale: Some(Locale::de),
+                ..ReportOptions::default()
+            },
+        );
+        assert!(report.contains("│ Total Monthly Audience ┆ 1.000.000"));
+        assert!(report.contains("1.000.000 unique visitors"));
+        let lift_row = report
+            .lines()
+            .find(|line| line.starts_with("│ High Lift") && line.contains("150%"))
+            .unwrap();
+        assert!(lift_row.contains("15.000"));
+        // Currency keeps its own grouping
+        assert!(report.contains(&format_currency(data.summary.central_estimate)));
+    }
 }
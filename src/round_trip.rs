@@ -0,0 +1,188 @@
+//! JSON round-trip check for `--round-trip-check`.
+//!
+//! The JSON report is the serialized form of `ReportData` that archived and
+//! compared runs depend on. This check writes the report, parses it back and
+//! compares every written field with the value it was built from, so a
+//! figure written lossily (e.g. a non-finite value becoming `null`) is caught
+//! before anyone relies on the file. The report's field tables name every
+//! input, so an input cannot be left out of the file unnoticed either.
+
+use crate::json::JsonValue;
+use crate::model::ReportData;
+use crate::report_generator::generate_json_report;
+
+/// Relative tolerance for numbers read back, allowing for formatting only
+const ROUND_TRIP_EPSILON: f64 = 1e-12;
+
+/// Outcome of reading the JSON report back against its `ReportData`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RoundTrip {
+    /// Number of values compared
+    pub checked: usize,
+    /// One description per value that did not survive the round trip
+    pub mismatches: Vec<String>,
+}
+
+impl RoundTrip {
+    /// Compare a number (or its absence, written as `null`) at `path`
+    fn number(&mut self, path: &str, read: Option<&JsonValue>, expected: Option<f64>) {
+        self.checked += 1;
+        let actual = read.and_then(JsonValue::as_f64);
+        let survived = match (actual, expected) {
+            (Some(actual), Some(expected)) => {
+                (actual - expected).abs()
+                    <= ROUND_TRIP_EPSILON * actual.abs().max(expected.abs()).max(1.0)
+            }
+            (None, None) => read == Some(&JsonValue::Null),
+            _ => false,
+        };
+        if !survived {
+            self.mismatches.push(format!(
+                "{}: wrote {}, read back {}",
+                path,
+                describe(expected),
+                describe(actual)
+            ));
+        }
+    }
+
+    /// Compare any other value exactly
+    fn value(&mut self, path: &str, read: Option<&JsonValue>, expected: JsonValue) {
+        self.checked += 1;
+        if read != Some(&expected) {
+            self.mismatches.push(format!(
+                "{}: wrote {}, read back {}",
+                path,
+                expected,
+                read.map_or_else(|| "nothing".to_string(), JsonValue::to_string)
+            ));
+        }
+    }
+
+    /// Compare everything written at `path` with what was read back: numbers
+    /// within tolerance, objects key by key and arrays entry by entry
+    fn compare(&mut self, path: &str, read: Option<&JsonValue>, written: &JsonValue) {
+        match written {
+            JsonValue::Number(number) => self.number(path, read, Some(*number)),
+            JsonValue::Object(entries) => {
+                for (key, value) in entries {
+                    let path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", path, key)
+                    };
+                    self.compare(&path, read.and_then(|r| r.get(key)), value);
+                }
+            }
+            JsonValue::Array(items) => {
+                let read = read.and_then(JsonValue::as_array).unwrap_or_default();
+                self.checked += 1;
+                if read.len() != items.len() {
+                    self.mismatches.push(format!(
+                        "{}: wrote {} entries, read back {}",
+                        path,
+                        items.len(),
+                        read.len()
+                    ));
+                }
+                for (index, item) in items.iter().enumerate() {
+                    self.compare(&format!("{}[{}]", path, index), read.get(index), item);
+                }
+            }
+            _ => self.value(path, read, written.clone()),
+        }
+    }
+}
+
+fn describe(value: Option<f64>) -> String {
+    value.map_or_else(|| "null".to_string(), |v| format!("{:?}", v))
+}
+
+/// Write `data` as the JSON report, parse it back and compare every
+/// serialized figure with the value it was written from
+pub fn check_json_round_trip(data: &ReportData) -> RoundTrip {
+    let mut result = RoundTrip::default();
+    let written = generate_json_report(data);
+    let document = match JsonValue::parse(&written.to_string()) {
+        Ok(document) => document,
+        Err(e) => {
+            result
+                .mismatches
+                .push(format!("JSON report does not parse: {}", e));
+            return result;
+        }
+    };
+
+    result.compare("", Some(&document), &written);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Args;
+    use crate::valuation::calculate_full_valuation;
+    use clap::Parser;
+
+    fn report_data(argv: &[&str]) -> ReportData {
+        let mut full = vec!["tokval", "--forecast", "220000"];
+        full.extend_from_slice(argv);
+        let inputs = Args::try_parse_from(full)
+            .unwrap()
+            .valuation_inputs()
+            .unwrap();
+        calculate_full_valuation(&inputs).unwrap()
+    }
+
+    #[test]
+    fn test_json_report_round_trips() {
+        for argv in [
+            &[][..],
+            &["--purchase-price", "150000", "--ownership-fraction", "0.25"],
+            &["--baseline-only", "--payout-ladder", "30:0.5,90:0.5"],
+            &["--lift-per-investor", "5000"],
+            &[
+                "--quarters",
+                "8",
+                "--stage1-quarters",
+                "4",
+                "--stage1-growth",
+                "3",
+                "--stage2-growth",
+                "1",
+                "--day-count",
+                "actual360",
+                "--compounding",
+                "continuous",
+                "--display-currency",
+                "eur",
+                "--fx-rate",
+                "0.92",
+            ],
+            &["--rate-formula", "rf + 0.5*vol_premium + platform_premium"],
+            &["--lift-weights", "1,2,1"],
+        ] {
+            let result = check_json_round_trip(&report_data(argv));
+            assert!(result.mismatches.is_empty(), "{:?}", result.mismatches);
+            assert!(result.checked > 50);
+        }
+    }
+
+    #[test]
+    fn test_lossy_values_are_reported() {
+        let mut data = report_data(&[]);
+        data.all_valuations[3].face_value = f64::INFINITY;
+        data.summary.value_ratio = Some(f64::NAN);
+        data.inputs.rpm = f64::NAN;
+
+        let result = check_json_round_trip(&data);
+        assert_eq!(
+            result.mismatches,
+            [
+                "inputs.rpm: wrote NaN, read back null",
+                "summary.value_ratio: wrote NaN, read back null",
+                "valuations[3].face_value: wrote inf, read back null"
+            ]
+        );
+    }
+}
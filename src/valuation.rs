@@ -1,7 +1,9 @@
 //! Core valuation logic and calculations.
 
+use crate::bounds::bound;
 use crate::error::ModelError;
 use crate::model::*;
+use crate::money::Money;
 use std::collections::HashMap;
 
 /// Calculate the adjusted baseline revenue after platform adjustment
@@ -9,16 +11,56 @@ fn calculate_adjusted_baseline(raw_forecast: f64, adjustment_factor: f64) -> f64
     raw_forecast * (1.0 + adjustment_factor)
 }
 
+/// Convert a cash flow to fixed-point [`Money`], refusing amounts that are
+/// not finite or too large to hold
+fn to_money(amount: f64) -> Result<Money, ModelError> {
+    Money::from_f64(amount).ok_or_else(|| unrepresentable_cash_flow(amount))
+}
+
+/// Scale a cash flow by `factor`, refusing a result that [`to_money`] would
+fn scale_money(amount: Money, factor: f64) -> Result<Money, ModelError> {
+    amount
+        .scale(factor)
+        .ok_or_else(|| unrepresentable_cash_flow(amount.to_f64() * factor))
+}
+
+/// Sum cash flows, refusing a total too large to hold
+fn sum_money(amounts: impl IntoIterator<Item = Money>) -> Result<Money, ModelError> {
+    Money::checked_sum(amounts)
+        .ok_or_else(|| ModelError::CalculationError("Cash flows are too large to sum".to_string()))
+}
+
+fn unrepresentable_cash_flow(amount: f64) -> ModelError {
+    ModelError::CalculationError(if amount.is_finite() {
+        format!("Cash flow {} is too large to value", amount)
+    } else {
+        format!("Cash flow {} is not a finite amount", amount)
+    })
+}
+
 /// Calculate the discount rate components for a given volatility scenario
 fn calculate_discount_rate(
     inputs: &ValuationInputs,
     volatility_scenario: VolatilityScenario,
 ) -> DiscountRateComponents {
-    DiscountRateComponents {
+    let override_rate = inputs.discount_rate_overrides.as_ref().and_then(|rates| {
+        VolatilityScenario::all()
+            .iter()
+            .position(|v| *v == volatility_scenario)
+            .and_then(|index| rates.get(index).copied())
+    });
+
+    let mut components = DiscountRateComponents {
         risk_free_rate: inputs.risk_free_rate,
-        volatility_premium: volatility_scenario.premium(),
+        volatility_premium: inputs.forecast_confidence.premium(volatility_scenario),
         platform_risk_premium: inputs.platform_risk_premium,
+        override_rate,
+    };
+    // Validation has already checked the formula gives a usable rate
+    if let Some(formula) = &inputs.rate_formula {
+        components.override_rate = formula.rate(&components).ok();
     }
+    components
 }
 
 /// Calculate present value using discounted cash flow formula
@@ -44,22 +86,682 @@ fn calculate_present_value(
     Ok(cash_flow / denominator)
 }
 
-/// Calculate valuations for all scenario combinations and return comprehensive report data
-pub fn calculate_full_valuation(inputs: &ValuationInputs) -> Result<ReportData, ModelError> {
-    // Validate inputs
-    if inputs.raw_forecast <= 0.0 {
+/// Calculate present value under a compounding convention; continuous
+/// compounding uses PV = CashFlow * e^(-Rate * Time)
+fn calculate_compounded_present_value(
+    cash_flow: f64,
+    discount_rate: f64,
+    time_years: f64,
+    compounding: Compounding,
+) -> Result<f64, ModelError> {
+    match compounding {
+        Compounding::Discrete => calculate_present_value(cash_flow, discount_rate, time_years),
+        Compounding::Continuous => Ok(cash_flow * (-discount_rate * time_years).exp()),
+    }
+}
+
+/// Calculate present value of a cash flow paid in tranches, summing the
+/// discounted value of each tranche's share
+fn calculate_laddered_present_value(
+    cash_flow: f64,
+    discount_rate: f64,
+    ladder: &PayoutLadder,
+    day_count: DayCount,
+    compounding: Compounding,
+) -> Result<f64, ModelError> {
+    ladder
+        .tranches
+        .iter()
+        .map(|tranche| {
+            calculate_compounded_present_value(
+                cash_flow * tranche.fraction,
+                discount_rate,
+                tranche.years(day_count),
+                compounding,
+            )
+        })
+        .sum()
+}
+
+/// Calculate present value of one quarter's cash flow for a payout scenario,
+/// resolving laddered payouts against the schedule supplied in the inputs
+fn calculate_quarter_present_value(
+    inputs: &ValuationInputs,
+    cash_flow: f64,
+    discount_rate: f64,
+    payout: PayoutScenario,
+) -> Result<f64, ModelError> {
+    match (payout.days(), &inputs.payout_ladder) {
+        (Some(days), _) if inputs.monthly_breakdown => {
+            calculate_monthly_present_value(inputs, cash_flow, discount_rate, days)
+        }
+        (Some(days), _) => {
+            let years = inputs.day_count.year_fraction(days);
+            calculate_compounded_present_value(cash_flow, discount_rate, years, inputs.compounding)
+        }
+        (None, Some(ladder)) => calculate_laddered_present_value(
+            cash_flow,
+            discount_rate,
+            ladder,
+            inputs.day_count,
+            inputs.compounding,
+        ),
+        (None, None) => Err(ModelError::CalculationError(
+            "Laddered payout scenario requires a payout ladder".to_string(),
+        )),
+    }
+}
+
+/// Calculate present value of one quarter's cash flow split into three equal
+/// monthly payments 30 days apart, the last settling on the payout date
+/// (e.g. days 30, 60 and 90 for a 90-day payout)
+fn calculate_monthly_present_value(
+    inputs: &ValuationInputs,
+    cash_flow: f64,
+    discount_rate: f64,
+    payout_days: u32,
+) -> Result<f64, ModelError> {
+    [60, 30, 0]
+        .iter()
+        .map(|months_before_payout| {
+            let days = payout_days.saturating_sub(*months_before_payout);
+            calculate_compounded_present_value(
+                cash_flow / 3.0,
+                discount_rate,
+                inputs.day_count.year_fraction(days),
+                inputs.compounding,
+            )
+        })
+        .sum()
+}
+
+/// Cash flow of each quarter in the valuation horizon.
+///
+/// Quarter `q` (0-based) pays `(baseline + lift_q) * growth_factor(q)` (see
+/// [`growth_factor`]). Each
+/// quarter a `reinvestment_rate` share of the lift revenue buys further
+/// audience that monetizes like the existing lift audience, so the lift
+/// portion snowballs: `lift_{q+1} = lift_q * (1 + reinvestment_rate)`.
+pub fn quarter_cash_flows(
+    inputs: &ValuationInputs,
+    baseline_cash_flow: f64,
+    lift_cash_flow: f64,
+    growth_rate: f64,
+) -> Result<Vec<Money>, ModelError> {
+    let baseline = to_money(baseline_cash_flow)?;
+    let mut lift = to_money(lift_cash_flow)?;
+    let mut cash_flows = Vec::with_capacity(inputs.quarters as usize);
+    for quarter in 0..inputs.quarters {
+        cash_flows.push(scale_money(
+            sum_money([baseline, lift])?,
+            growth_factor(inputs, growth_rate, quarter),
+        )?);
+        lift = sum_money([lift, scale_money(lift, inputs.reinvestment_rate)?])?;
+    }
+    Ok(cash_flows)
+}
+
+/// Calculate present value across the valuation horizon. Each quarter's cash
+/// flow (see [`quarter_cash_flows`]) is discounted a further `q` quarters.
+///
+/// When a separate lift payout is configured, the baseline and lift portions
+/// are discounted independently, the lift portion over its own tenor.
+fn calculate_scenario_present_value(
+    inputs: &ValuationInputs,
+    baseline_cash_flow: f64,
+    lift_cash_flow: f64,
+    discount_rate: f64,
+    growth_rate: f64,
+    payout: PayoutScenario,
+) -> Result<Money, ModelError> {
+    let at_payout =
+        |cash_flow: f64| calculate_quarter_present_value(inputs, cash_flow, discount_rate, payout);
+
+    match inputs.lift_payout_days {
+        Some(days) if lift_cash_flow != 0.0 => {
+            let lift_years = inputs.day_count.year_fraction(days);
+            let baseline = discount_horizon(
+                inputs,
+                quarter_cash_flows(inputs, baseline_cash_flow, 0.0, growth_rate)?,
+                discount_rate,
+                at_payout,
+            )?;
+            let lift = discount_horizon(
+                inputs,
+                quarter_cash_flows(inputs, 0.0, lift_cash_flow, growth_rate)?,
+                discount_rate,
+                |cash_flow| {
+                    calculate_compounded_present_value(
+                        cash_flow,
+                        discount_rate,
+                        lift_years,
+                        inputs.compounding,
+                    )
+                },
+            )?;
+            sum_money([baseline, lift])
+        }
+        _ => discount_horizon(
+            inputs,
+            quarter_cash_flows(inputs, baseline_cash_flow, lift_cash_flow, growth_rate)?,
+            discount_rate,
+            at_payout,
+        ),
+    }
+}
+
+/// Sum per-period cash flows discounted first to their period start by
+/// `quarter_value`, then a further `q` periods to today. Each period's present
+/// value is rounded to a micro-unit before summing, so the total reconciles
+/// exactly with the per-period values.
+fn discount_horizon(
+    inputs: &ValuationInputs,
+    cash_flows: Vec<Money>,
+    discount_rate: f64,
+    quarter_value: impl Fn(f64) -> Result<f64, ModelError>,
+) -> Result<Money, ModelError> {
+    let mut total = Money::ZERO;
+    for (quarter, quarter_cash_flow) in cash_flows.into_iter().enumerate() {
+        let quarter_value = quarter_value(quarter_cash_flow.to_f64())?;
+        let present_value = to_money(calculate_compounded_present_value(
+            quarter_value,
+            discount_rate,
+            quarter as f64 / inputs.period.per_year() as f64,
+            inputs.compounding,
+        )?)?;
+        total = sum_money([total, present_value])?;
+    }
+    Ok(total)
+}
+
+/// Cumulative growth of period `quarter` (0-based): `(1 + growth_rate)^q`, or
+/// the staged factor when two-stage growth is configured, in which case the
+/// resolved `growth_rate` is the stage-1 rate and adds nothing
+fn growth_factor(inputs: &ValuationInputs, growth_rate: f64, quarter: u32) -> f64 {
+    match &inputs.two_stage_growth {
+        Some(stages) => stages.factor(quarter),
+        None => (1.0 + growth_rate).powi(quarter as i32),
+    }
+}
+
+/// Total undiscounted cash flow over the valuation horizon for a first-period
+/// cash flow growing at `growth_rate` per period
+pub fn horizon_cash_flow(
+    inputs: &ValuationInputs,
+    cash_flow: f64,
+    growth_rate: f64,
+) -> Result<Money, ModelError> {
+    sum_money(quarter_cash_flows(inputs, cash_flow, 0.0, growth_rate)?)
+}
+
+/// Discount factor at `rate` every `step` days from 0 to `max_days`, for
+/// plotting how fast a scenario discounts between the payout dates. The last
+/// point is always `max_days`, even when `step` does not divide it. A zero
+/// `step` is refused.
+pub fn discount_curve(
+    rate: f64,
+    max_days: u32,
+    step: u32,
+    day_count: DayCount,
+    compounding: Compounding,
+) -> Result<Vec<(u32, f64)>, ModelError> {
+    if step == 0 {
+        return Err(ModelError::InvalidInput(
+            "Discount curve step must be at least one day".to_string(),
+        ));
+    }
+    Ok((0..=max_days)
+        .step_by(step as usize)
+        .chain((!max_days.is_multiple_of(step)).then_some(max_days))
+        .map(|days| {
+            (
+                days,
+                compounding.discount_factor(rate, day_count.year_fraction(days)),
+            )
+        })
+        .collect())
+}
+
+/// Longest delay until any revenue is paid: the latest payout scenario,
+/// ladder tranche or lift payout
+pub fn longest_payout_days(inputs: &ValuationInputs) -> u32 {
+    inputs
+        .payout_scenarios()
+        .iter()
+        .filter_map(PayoutScenario::days)
+        .chain(supplied_payout_days(inputs))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Per-period equivalent of an annual discount rate
+fn period_discount_rate(annual_rate: f64, period: RevenuePeriod) -> f64 {
+    (1.0 + annual_rate).powf(1.0 / period.per_year() as f64) - 1.0
+}
+
+/// Margin kept between a clamped growth rate and the per-period discount rate
+const GROWTH_CLAMP_MARGIN: f64 = 0.0001;
+
+/// Resolve the per-period growth rate to use for a volatility scenario.
+///
+/// Growth at or above the per-period discount rate makes later periods worth
+/// more than earlier ones and any perpetuity diverge, so it is either clamped
+/// just below the rate (recording a warning) or rejected in strict mode.
+///
+/// Two-stage growth is never clamped, since that would silently reshape the
+/// stages: a rate at or above the discount rate is an error for each stage
+/// that grows a period within the horizon. The stage-1 rate is returned as
+/// the scenario's growth rate.
+fn resolve_growth_rate(
+    inputs: &ValuationInputs,
+    volatility: VolatilityScenario,
+    discount_rate: f64,
+    warnings: &mut Vec<ModelWarning>,
+) -> Result<f64, ModelError> {
+    let period_rate = period_discount_rate(discount_rate, inputs.period);
+    if let Some(stages) = &inputs.two_stage_growth {
+        // Only the stages that grow a period within the horizon can diverge
+        let applied = [
+            (1, stages.stage1_growth, inputs.quarters > 1),
+            (
+                2,
+                stages.stage2_growth,
+                stages.reaches_stage2(inputs.quarters),
+            ),
+        ];
+        for (stage, growth, _) in applied.into_iter().filter(|(_, _, applies)| *applies) {
+            if growth >= period_rate {
+                return Err(ModelError::InvalidInput(format!(
+                    "Stage-{} growth rate {:.2}% must be below the {:.2}% per-{} discount rate \
+in the {} scenario for the valuation to converge",
+                    stage,
+                    growth * 100.0,
+                    period_rate * 100.0,
+                    inputs.period.noun(),
+                    volatility
+                )));
+            }
+        }
+        return Ok(stages.stage1_growth);
+    }
+
+    let growth = inputs.quarterly_growth_rate;
+    if inputs.quarters <= 1 {
+        return Ok(growth);
+    }
+
+    let ceiling = period_rate - GROWTH_CLAMP_MARGIN;
+    if growth <= ceiling {
+        return Ok(growth);
+    }
+
+    let message = format!(
+        "{} growth rate {:.2}% meets or exceeds the {:.2}% per-{} discount rate in the {} scenario",
+        inputs.period,
+        growth * 100.0,
+        period_rate * 100.0,
+        inputs.period.noun(),
+        volatility
+    );
+    if inputs.strict {
+        return Err(ModelError::InvalidInput(message));
+    }
+    warnings.push(ModelWarning::Note(format!(
+        "{}; growth clamped to {:.2}%",
+        message,
+        ceiling * 100.0
+    )));
+    Ok(ceiling)
+}
+
+/// Supplied payout delays beyond this many days draw a warning, since a
+/// quarter's revenue paid more than a year out is no longer quarterly framing
+const LONG_PAYOUT_DAYS: u32 = 365;
+
+/// Every user-supplied payout delay: ladder tranches and the lift payout
+fn supplied_payout_days(inputs: &ValuationInputs) -> impl Iterator<Item = u32> + '_ {
+    inputs
+        .payout_ladder
+        .iter()
+        .flat_map(|ladder| ladder.tranches.iter().map(|t| t.days))
+        .chain(inputs.lift_payout_days)
+}
+
+/// Inputs with any payout ladder tranches that repeat a day combined, and a
+/// warning naming the days, or `None` when no day repeats. The merged ladder
+/// values the same, but would otherwise list a day more than once.
+fn merge_repeated_payout_days(inputs: &ValuationInputs) -> Option<(ValuationInputs, String)> {
+    let (ladder, repeated) = inputs.payout_ladder.as_ref()?.merge_repeated_days();
+    if repeated.is_empty() {
+        return None;
+    }
+    let days: Vec<String> = repeated.iter().map(u32::to_string).collect();
+    let warning = format!(
+        "Payout ladder lists {} days more than once; their tranches were combined",
+        days.join(", ")
+    );
+    Some((
+        ValuationInputs {
+            payout_ladder: Some(ladder),
+            ..inputs.clone()
+        },
+        warning,
+    ))
+}
+
+/// Validate a payout ladder's tranche fractions
+fn validate_payout_ladder(ladder: &PayoutLadder) -> Result<(), ModelError> {
+    if ladder.tranches.is_empty() {
         return Err(ModelError::InvalidInput(
-            "Raw forecast must be positive".to_string(),
+            "Payout ladder must contain at least one tranche".to_string(),
         ));
     }
-    if inputs.baseline_audience <= 0.0 {
+    if ladder.tranches.iter().any(|t| t.fraction <= 0.0) {
         return Err(ModelError::InvalidInput(
-            "Baseline audience must be positive".to_string(),
+            "Payout ladder fractions must be positive".to_string(),
         ));
     }
-    if inputs.rpm <= 0.0 {
-        return Err(ModelError::InvalidInput("RPM must be positive".to_string()));
+    let total = ladder.total_fraction();
+    if (total - 1.0).abs() > 1e-6 {
+        return Err(ModelError::InvalidInput(format!(
+            "Payout ladder fractions must sum to 1.0 (got {:.4})",
+            total
+        )));
+    }
+    Ok(())
+}
+
+impl ValuationInputs {
+    /// Run every input check, returning all violations rather than stopping
+    /// at the first
+    pub fn validate(&self) -> Result<(), Vec<ModelError>> {
+        let mut errors = Vec::new();
+        if !bound("forecast").admits(self.raw_forecast) {
+            errors.push(ModelError::InvalidInput(
+                "Raw forecast must be positive".to_string(),
+            ));
+        }
+        if !bound("baseline_audience").admits(self.baseline_audience) {
+            errors.push(ModelError::InvalidInput(
+                "Baseline audience must be positive".to_string(),
+            ));
+        }
+        if !bound("rpm").admits(self.rpm) {
+            errors.push(ModelError::InvalidInput("RPM must be positive".to_string()));
+        }
+        if !bound("incremental_rpm").admits(self.incremental_rpm) {
+            errors.push(ModelError::InvalidInput(
+                "Incremental RPM cannot be negative".to_string(),
+            ));
+        }
+        if !bound("lift_per_investor").admits(self.lift_per_investor) {
+            errors.push(ModelError::InvalidInput(
+                "Lift per investor cannot be negative".to_string(),
+            ));
+        }
+        if let Some(Err(e)) = self.payout_ladder.as_ref().map(validate_payout_ladder) {
+            errors.push(e);
+        }
+        if let Some(days) =
+            supplied_payout_days(self).find(|days| !(1..=self.max_payout_days).contains(days))
+        {
+            errors.push(ModelError::InvalidInput(format!(
+            "Payout of {} days is outside the supported range of 1 to {} days (raise --max-payout-days to allow longer delays)",
+            days, self.max_payout_days
+        )));
+        }
+        if !bound("quarters").admits(self.quarters as f64) {
+            errors.push(ModelError::InvalidInput(format!(
+                "Valuation horizon must be at least one {}",
+                self.period.noun()
+            )));
+        }
+        if !bound("quarterly_growth_rate").admits(self.quarterly_growth_rate * 100.0) {
+            errors.push(ModelError::InvalidInput(format!(
+                "{} growth rate must be greater than -100%",
+                self.period
+            )));
+        }
+        if let Some(stages) = &self.two_stage_growth {
+            if !bound("stage1_quarters").admits(stages.stage1_quarters as f64) {
+                errors.push(ModelError::InvalidInput(format!(
+                    "Growth stage 1 must last at least one {}",
+                    self.period.noun()
+                )));
+            }
+            for (stage, name, growth) in [
+                (1, "stage1_growth", stages.stage1_growth),
+                (2, "stage2_growth", stages.stage2_growth),
+            ] {
+                if !bound(name).admits(growth * 100.0) {
+                    errors.push(ModelError::InvalidInput(format!(
+                        "Stage-{} growth rate must be greater than -100%",
+                        stage
+                    )));
+                }
+            }
+        }
+        if self.monthly_breakdown && self.period != RevenuePeriod::Quarterly {
+            errors.push(ModelError::InvalidInput(
+                "Monthly breakdown splits a quarter into months and needs the quarterly period"
+                    .to_string(),
+            ));
+        }
+        if self
+            .purchase_price
+            .is_some_and(|price| !bound("purchase_price").admits(price))
+        {
+            errors.push(ModelError::InvalidInput(
+                "Purchase price must be positive".to_string(),
+            ));
+        }
+        if self
+            .market_price
+            .is_some_and(|price| !bound("market_price").admits(price))
+        {
+            errors.push(ModelError::InvalidInput(
+                "Market price must be positive".to_string(),
+            ));
+        }
+        if self
+            .ownership_fraction
+            .is_some_and(|share| !bound("ownership_fraction").admits(share))
+        {
+            errors.push(ModelError::InvalidInput(
+                "Ownership fraction must be greater than 0 and at most 1".to_string(),
+            ));
+        }
+        if self
+            .display_currency
+            .as_ref()
+            .is_some_and(|currency| !bound("fx_rate").admits(currency.fx_rate))
+        {
+            errors.push(ModelError::InvalidInput(
+                "FX rate must be positive".to_string(),
+            ));
+        }
+        if self
+            .threads
+            .is_some_and(|threads| !bound("threads").admits(threads as f64))
+        {
+            errors.push(ModelError::InvalidInput(
+                "Thread count must be at least 1".to_string(),
+            ));
+        }
+        if !bound("tax_rate").admits(self.tax_rate * 100.0) {
+            errors.push(ModelError::InvalidInput(
+                "Tax rate must be at least 0% and below 100%".to_string(),
+            ));
+        }
+        if self
+            .revenue_floor
+            .is_some_and(|floor| !bound("revenue_floor").admits(floor))
+        {
+            errors.push(ModelError::InvalidInput(
+                "Revenue floor cannot be negative".to_string(),
+            ));
+        }
+        if self
+            .fixed_lift
+            .is_some_and(|lift| !bound("fixed_lift").admits(lift))
+        {
+            errors.push(ModelError::InvalidInput(
+                "Fixed lift cannot be negative".to_string(),
+            ));
+        }
+        if self
+            .lift_acquisition_cost
+            .is_some_and(|cost| !bound("lift_acquisition_cost").admits(cost))
+        {
+            errors.push(ModelError::InvalidInput(
+                "Lift acquisition cost cannot be negative".to_string(),
+            ));
+        }
+        if !bound("reinvestment_rate").admits(self.reinvestment_rate * 100.0) {
+            errors.push(ModelError::InvalidInput(
+                "Reinvestment rate cannot be negative".to_string(),
+            ));
+        }
+        if !bound("lift_dominance_threshold").admits(self.lift_dominance_threshold) {
+            errors.push(ModelError::InvalidInput(
+                "Lift dominance threshold must be positive".to_string(),
+            ));
+        }
+        if let Some(rates) = &self.discount_rate_overrides {
+            let expected = VolatilityScenario::all().len();
+            if rates.len() != expected {
+                errors.push(ModelError::InvalidInput(format!(
+                    "Expected {} discount rates (one per volatility scenario), got {}",
+                    expected,
+                    rates.len()
+                )));
+            }
+        }
+
+        for (kind, weights, expected) in [
+            (
+                "payout",
+                &self.scenario_weights.payout,
+                PayoutScenario::all().len(),
+            ),
+            (
+                "volatility",
+                &self.scenario_weights.volatility,
+                VolatilityScenario::all().len(),
+            ),
+            (
+                "lift",
+                &self.scenario_weights.lift,
+                LiftScenario::all().len(),
+            ),
+        ] {
+            let Some(weights) = weights else { continue };
+            if weights.len() != expected {
+                errors.push(ModelError::InvalidInput(format!(
+                    "Expected {} {} weights, got {}",
+                    expected,
+                    kind,
+                    weights.len()
+                )));
+            } else if weights.iter().any(|w| !(w.is_finite() && *w >= 0.0))
+                || weights.iter().sum::<f64>() <= 0.0
+            {
+                errors.push(ModelError::InvalidInput(format!(
+                    "The {} weights must be non-negative with a positive total",
+                    kind
+                )));
+            }
+        }
+
+        if let Some(formula) = &self.rate_formula {
+            for &volatility in VolatilityScenario::all() {
+                if let Err(e) = formula.rate(&calculate_discount_rate(self, volatility)) {
+                    errors.push(ModelError::InvalidInput(format!(
+                        "In the {} volatility scenario, {}",
+                        volatility, e
+                    )));
+                }
+            }
+        }
+
+        if let Some(matrix) = &self.rate_matrix {
+            let payouts = self.payout_scenarios();
+            for &payout in payouts {
+                if matrix.rate(payout, VolatilityScenario::Low).is_none() {
+                    errors.push(ModelError::InvalidInput(format!(
+                        "Rate matrix has no {} row",
+                        payout
+                    )));
+                }
+            }
+            if let Some(extra) = matrix.payouts().iter().find(|p| !payouts.contains(p)) {
+                errors.push(ModelError::InvalidInput(format!(
+                    "Rate matrix has a {} row, which is not a payout scenario of this run",
+                    extra
+                )));
+            }
+            if matrix.rates().any(|rate| rate <= -1.0) {
+                errors.push(ModelError::InvalidInput(
+                    "Discount rates must be greater than -100%".to_string(),
+                ));
+            }
+        }
+
+        // Refuse oversized grids before doing any work
+        let scenario_count = scenario_count(self);
+        if scenario_count > self.max_scenarios {
+            errors.push(ModelError::InvalidInput(format!(
+            "Scenario grid requires {} evaluations, exceeding the limit of {} (raise --max-scenarios to allow it)",
+            scenario_count, self.max_scenarios
+        )));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
+}
+
+/// Validate the user-supplied inputs before any calculation, reporting a
+/// single violation as itself and several together
+pub fn validate_inputs(inputs: &ValuationInputs) -> Result<(), ModelError> {
+    inputs.validate().map_err(|mut errors| {
+        if errors.len() == 1 {
+            errors.remove(0)
+        } else {
+            ModelError::Validation(errors)
+        }
+    })
+}
+
+/// Number of present-value evaluations the grid requires: every payout,
+/// volatility and lift (plus baseline) combination, once per horizon quarter
+pub fn scenario_count(inputs: &ValuationInputs) -> usize {
+    let lift_count = if inputs.baseline_only {
+        0
+    } else {
+        LiftScenario::all().len()
+    };
+    inputs
+        .payout_scenarios()
+        .len()
+        .saturating_mul(VolatilityScenario::all().len())
+        .saturating_mul(1 + lift_count)
+        .saturating_mul(inputs.quarters as usize)
+}
+
+/// Calculate valuations for all scenario combinations and return comprehensive report data
+pub fn calculate_full_valuation(inputs: &ValuationInputs) -> Result<ReportData, ModelError> {
+    validate_inputs(inputs)?;
+    calculate_validated_valuation(inputs)
+}
+
+/// [`calculate_full_valuation`] for inputs that have already passed
+/// [`validate_inputs`], so callers that validate up front do not do it twice
+pub fn calculate_validated_valuation(inputs: &ValuationInputs) -> Result<ReportData, ModelError> {
+    let merged = merge_repeated_payout_days(inputs);
+    let inputs = merged.as_ref().map_or(inputs, |(merged, _)| merged);
 
     // Calculate adjusted baseline revenue
     let adjusted_baseline =
@@ -71,178 +773,1238 @@ pub fn calculate_full_valuation(inputs: &ValuationInputs) -> Result<ReportData,
         discount_rates.insert(volatility, calculate_discount_rate(inputs, volatility));
     }
 
-    let mut all_valuations = Vec::new();
-
-    // Calculate baseline valuations (no lift) and add to the unified vector
-    for &payout in PayoutScenario::all() {
-        for &volatility in VolatilityScenario::all() {
-            let discount_rate = discount_rates[&volatility].total_rate();
-            let present_value =
-                calculate_present_value(adjusted_baseline, discount_rate, payout.years())?;
-
-            all_valuations.push(ValuationResult {
-                present_value,
-                payout_scenario: payout,
-                volatility_scenario: volatility,
-                lift_scenario: None, // `None` for baseline
-            });
-        }
+    // Resolve the growth rate per volatility scenario, guarding against runaway growth
+    let mut warnings: Vec<ModelWarning> = supplied_payout_days(inputs)
+        .filter(|days| *days > LONG_PAYOUT_DAYS)
+        .map(|days| {
+            ModelWarning::Note(format!(
+                "Payout of {} days exceeds one year; treating the revenue as {} may be misleading",
+                days,
+                inputs.period.to_string().to_lowercase()
+            ))
+        })
+        .collect();
+    warnings.extend(
+        merged
+            .as_ref()
+            .map(|(_, warning)| ModelWarning::Note(warning.clone())),
+    );
+    if let Some(stages) = inputs
+        .two_stage_growth
+        .filter(|stages| inputs.quarters > 1 && !stages.reaches_stage2(inputs.quarters))
+    {
+        warnings.push(ModelWarning::Note(format!(
+            "Growth stage 1 lasts {} {}s, covering the whole {}-{} horizon; the stage-2 rate never applies",
+            stages.stage1_quarters,
+            inputs.period.noun(),
+            inputs.quarters,
+            inputs.period.noun()
+        )));
+    }
+    let mut growth_rates = HashMap::new();
+    for &volatility in VolatilityScenario::all() {
+        // Growth must stay below every payout's rate, so guard against the lowest
+        let discount_rate = match &inputs.rate_matrix {
+            Some(matrix) => inputs
+                .payout_scenarios()
+                .iter()
+                .filter_map(|&payout| matrix.rate(payout, volatility))
+                .fold(f64::INFINITY, f64::min),
+            None => discount_rates[&volatility].total_rate().as_decimal(),
+        };
+        let growth = resolve_growth_rate(inputs, volatility, discount_rate, &mut warnings)?;
+        growth_rates.insert(volatility, growth);
     }
 
-    // Calculate valuations for each lift scenario using configurable inputs
-    let mut lift_valuations = HashMap::new();
     let lift_assumptions = LiftAssumptions {
         baseline_audience: inputs.baseline_audience,
         rpm: inputs.rpm,
+        incremental_rpm: inputs.incremental_rpm,
         investor_count: inputs.investor_count,
         lift_per_investor: inputs.lift_per_investor,
+        lift_ramp: inputs.lift_ramp,
+        fixed_lift: inputs.fixed_lift,
+        period: inputs.period,
     };
 
-    for &lift_scenario in LiftScenario::all() {
-        let scenario_results: Vec<ValuationResult> = Vec::new();
-        let lift_amount = lift_scenario.quarterly_lift(
-            lift_assumptions.investor_count,
-            lift_assumptions.lift_per_investor,
-            lift_assumptions.rpm,
-        );
-        let lifted_revenue = adjusted_baseline + lift_amount;
+    // Baseline-only runs skip the lift grid entirely
+    let lift_scenarios: &[LiftScenario] = if inputs.baseline_only {
+        &[]
+    } else {
+        LiftScenario::all()
+    };
 
-        for &payout in PayoutScenario::all() {
-            for &volatility in VolatilityScenario::all() {
-                let discount_rate = discount_rates[&volatility].total_rate();
-                let present_value =
-                    calculate_present_value(lifted_revenue, discount_rate, payout.years())?;
-
-                all_valuations.push(ValuationResult {
-                    present_value,
-                    payout_scenario: payout,
-                    volatility_scenario: volatility,
-                    lift_scenario: Some(lift_scenario), // Set the specific lift scenario
-                });
-            }
-        }
+    // Lift outweighing the revenue it is lifting usually means a data-entry slip
+    let dominant: Vec<(LiftScenario, f64)> = lift_scenarios
+        .iter()
+        .map(|lift| (*lift, lift_assumptions.period_lift(*lift)))
+        .filter(|(_, amount)| *amount > adjusted_baseline * inputs.lift_dominance_threshold)
+        .collect();
+    if !dominant.is_empty() {
+        warnings.push(ModelWarning::LiftDominance {
+            period: inputs.period,
+            threshold: inputs.lift_dominance_threshold,
+            adjusted_baseline,
+            lifts: dominant,
+        });
+    }
+
+    // Every grid cell: the baseline (no lift) first, then each lift scenario
+    let cells: Vec<ScenarioId> = std::iter::once(None)
+        .chain(lift_scenarios.iter().copied().map(Some))
+        .flat_map(|lift| {
+            inputs.payout_scenarios().iter().flat_map(move |&payout| {
+                VolatilityScenario::all()
+                    .iter()
+                    .map(move |&volatility| ScenarioId {
+                        payout,
+                        volatility,
+                        lift,
+                    })
+            })
+        })
+        .collect();
+
+    // Cells are independent, so they are valued across worker threads
+    let mut all_valuations = parallel_map(&cells, worker_threads(inputs), |cell| {
+        let lift_amount = cell
+            .lift
+            .map_or(0.0, |lift| lift_assumptions.period_lift(lift));
+        // A contractual floor tops the quarter's revenue up as guaranteed baseline
+        let floor_top_up = inputs.revenue_floor.map_or(0.0, |floor| {
+            (floor - (adjusted_baseline + lift_amount)).max(0.0)
+        });
+        let baseline_cash_flow = adjusted_baseline + floor_top_up;
+        let growth = growth_rates[&cell.volatility];
+        let discount_rate = inputs
+            .rate_matrix
+            .as_ref()
+            .and_then(|matrix| matrix.rate(cell.payout, cell.volatility))
+            .unwrap_or_else(|| discount_rates[&cell.volatility].total_rate().as_decimal());
+        let pre_tax_present_value = calculate_scenario_present_value(
+            inputs,
+            baseline_cash_flow,
+            lift_amount,
+            discount_rate,
+            growth,
+            cell.payout,
+        )?
+        .to_f64();
+        // Tax scales every cash flow by the same factor, and discounting is
+        // linear, so the after-tax value is the pre-tax value scaled alike
+        let after_tax_present_value = pre_tax_present_value * (1.0 - inputs.tax_rate);
+        let headline_factor = inputs.headline_factor();
+        let present_value = pre_tax_present_value * headline_factor;
+        let face_value = scale_money(
+            sum_money(quarter_cash_flows(
+                inputs,
+                baseline_cash_flow,
+                lift_amount,
+                growth,
+            )?)?,
+            headline_factor,
+        )?
+        .to_f64();
 
-        lift_valuations.insert(lift_scenario, scenario_results);
+        Ok(ValuationResult {
+            present_value,
+            pre_tax_present_value,
+            after_tax_present_value,
+            face_value,
+            position_value: inputs.ownership_fraction.map(|share| present_value * share),
+            display_value: inputs
+                .display_currency
+                .as_ref()
+                .map(|currency| currency.convert(present_value)),
+            floor_top_up,
+            payout_scenario: cell.payout,
+            volatility_scenario: cell.volatility,
+            lift_scenario: cell.lift,
+            tags: ScenarioTags::default(),
+        })
+    })?;
+    // Lookups by scenario assume one result per cell, in grid order
+    debug_assert!(
+        all_valuations
+            .iter()
+            .map(ValuationResult::scenario)
+            .eq(cells.iter().copied()),
+        "valuations do not match the scenario grid"
+    );
+
+    // A negative rate discounts a cash flow up rather than down
+    if inputs.cap_at_face {
+        let capped: Vec<String> = all_valuations
+            .iter_mut()
+            .filter_map(|result| cap_at_face_value(result).then(|| result.scenario().to_string()))
+            .collect();
+        if !capped.is_empty() {
+            warnings.push(ModelWarning::Note(format!(
+                "Present value exceeds face value in {}; capped at face value",
+                capped.join(", ")
+            )));
+        }
     }
 
     // Calculate summary statistics from the unified vector
-    let summary = calculate_summary_statistics(&all_valuations, adjusted_baseline)?;
+    let summary = calculate_summary_statistics(&all_valuations, adjusted_baseline, inputs)?;
+    for valuation in &mut all_valuations {
+        valuation.tags = ScenarioTags::new(valuation.scenario(), &summary);
+    }
 
-    Ok(ReportData {
+    let mut data = ReportData {
         inputs: inputs.clone(),
         all_valuations, // Pass the single unified vector
         discount_rates,
         summary,
         lift_assumptions,
-    })
-}
+        growth_rates,
+        warnings,
+        method_comparison: None,
+        day_count_comparison: None,
+        lift_model_comparison: None,
+        lift_option: None,
+        implied_stress: None,
+        assumptions: modeling_assumptions(inputs),
+        haircut_splits: Vec::new(),
+    };
+    data.haircut_splits = inputs
+        .payout_scenarios()
+        .iter()
+        .map(|&payout| risk_vs_time_decomposition(&data, payout))
+        .collect::<Result<_, _>>()?;
 
-/// Calculate summary statistics for the executive summary
-fn calculate_summary_statistics(
-    all_valuations: &[ValuationResult], // Takes the single unified vector
-    adjusted_baseline: f64,
-) -> Result<SummaryStatistics, ModelError> {
-    let all_values: Vec<f64> = all_valuations.iter().map(|v| v.present_value).collect();
+    // A price the grid cannot reach is reported, not fatal
+    if let Some(price) = inputs.market_price {
+        match implied_stress(&data, price) {
+            Ok(stress) => data.implied_stress = Some(stress),
+            Err(warning) => data.warnings.push(warning),
+        }
+    }
+    Ok(data)
+}
 
-    let min_valuation = all_values.iter().cloned().fold(f64::INFINITY, f64::min);
-    let max_valuation = all_values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+/// Cap a result's present value at its face value, scaling its other values
+/// alike. Returns whether the cap bound.
+fn cap_at_face_value(result: &mut ValuationResult) -> bool {
+    if result.present_value.abs() <= result.face_value.abs() {
+        return false;
+    }
+    let scale = result.face_value / result.present_value;
+    result.present_value = result.face_value;
+    result.pre_tax_present_value *= scale;
+    result.after_tax_present_value *= scale;
+    result.position_value = result.position_value.map(|value| value * scale);
+    result.display_value = result.display_value.map(|value| value * scale);
+    true
+}
 
-    // The find logic now uses the `.lift_scenario` field, fixing the dead code warning!
-    let find_value =
-        |payout: PayoutScenario, vol: VolatilityScenario, lift: Option<LiftScenario>| {
-            all_valuations
-                .iter()
-                .find(|v| {
-                    v.payout_scenario == payout
-                        && v.volatility_scenario == vol
-                        && v.lift_scenario == lift
-                })
-                .map(|v| v.present_value)
-                .ok_or_else(|| {
-                    ModelError::CalculationError(format!("Could not find value for scenario combo"))
-                })
-        };
+/// Every modeling assumption the valuation of `inputs` applies.
+///
+/// Most are fixed by the model's structure (equal discounting of baseline and
+/// lift, flat RPM, linear audience scaling); the rest state the option chosen
+/// for this run. Lift assumptions are omitted in baseline-only runs.
+pub fn modeling_assumptions(inputs: &ValuationInputs) -> Vec<ModelingAssumption> {
+    let mut assumptions = vec![
+        ModelingAssumption::new(
+            "Discounting",
+            "Baseline and lift cash flows share each scenario's discount rate; lift carries no separate risk premium",
+        ),
+        ModelingAssumption::new(
+            "Discount Rates",
+            if inputs.rate_matrix.is_some() {
+                "Supplied per payout and volatility scenario (rate matrix)".to_string()
+            } else if inputs.discount_rate_overrides.is_some() {
+                "Supplied per volatility scenario".to_string()
+            } else if let Some(formula) = &inputs.rate_formula {
+                format!("Rate formula {} per volatility scenario", formula)
+            } else {
+                "Risk-free rate + volatility premium + platform/publisher premium".to_string()
+            },
+        ),
+        ModelingAssumption::new(
+            "RPM",
+            AssumptionStatement::FlatRpm {
+                baseline: inputs.rpm,
+                lift: inputs.incremental_rpm,
+            },
+        ),
+    ];
+    if inputs.forecast_confidence != ForecastConfidence::Medium {
+        assumptions.push(ModelingAssumption::new(
+            "Forecast Confidence",
+            AssumptionStatement::ForecastConfidence(inputs.forecast_confidence),
+        ));
+    }
+    if let Some(fixed) = inputs.fixed_lift.filter(|_| !inputs.baseline_only) {
+        assumptions.push(ModelingAssumption::new(
+            "Lift Model",
+            AssumptionStatement::FixedLift {
+                amount: fixed,
+                period: inputs.period,
+            },
+        ));
+    } else if !inputs.baseline_only {
+        assumptions.push(ModelingAssumption::new(
+            "Audience Scaling",
+            AssumptionStatement::LinearAudience {
+                lift_per_investor: inputs.lift_per_investor,
+            },
+        ));
+        assumptions.push(ModelingAssumption::new(
+            "Lift Ramp",
+            inputs.lift_ramp.to_string(),
+        ));
+    }
 
-    let central_estimate = find_value(
-        PayoutScenario::Day90,
-        VolatilityScenario::Typical,
-        Some(LiftScenario::Medium),
-    )?;
+    let mut payout = match &inputs.payout_ladder {
+        Some(ladder) => format!("Laddered tranches ({})", ladder),
+        None if inputs.monthly_breakdown => {
+            "Three equal monthly installments per quarter, the last at the payout delay".to_string()
+        }
+        None => format!(
+            "Single bullet payment per {} at the payout delay",
+            inputs.period.noun()
+        ),
+    };
+    if let Some(days) = inputs.lift_payout_days.filter(|_| !inputs.baseline_only) {
+        payout.push_str(&format!("; lift revenue paid after {} days", days));
+    }
+    assumptions.push(ModelingAssumption::new("Payout", payout));
 
-    let low_vol_value = find_value(
-        PayoutScenario::Day90,
-        VolatilityScenario::Low,
-        Some(LiftScenario::Medium),
-    )?;
-    let extreme_vol_value = find_value(
-        PayoutScenario::Day90,
-        VolatilityScenario::Extreme,
-        Some(LiftScenario::Medium),
-    )?;
-    let volatility_impact = ((low_vol_value - extreme_vol_value) / low_vol_value) * 100.0;
+    assumptions.push(ModelingAssumption::new(
+        "Day Count",
+        match inputs.day_count {
+            DayCount::Actual365 => "365-day year (Actual/365)",
+            DayCount::Actual360 => "360-day year (Actual/360)",
+            DayCount::Thirty360 => {
+                "30-day months, each 30 days of delay counting as one, in a 360-day year (30/360)"
+            }
+        },
+    ));
+    assumptions.push(ModelingAssumption::new(
+        "Compounding",
+        inputs.compounding.to_string(),
+    ));
+    assumptions.push(ModelingAssumption::new(
+        "Horizon",
+        if inputs.quarters > 1 {
+            AssumptionStatement::Horizon {
+                quarters: inputs.quarters,
+                period: inputs.period,
+                growth: inputs.quarterly_growth_rate,
+                two_stage: inputs.two_stage_growth,
+            }
+        } else {
+            format!("A single {} of revenue", inputs.period.noun()).into()
+        },
+    ));
+    // The floor amount is left to the report, which can redact it
+    if inputs.revenue_floor.is_some() {
+        assumptions.push(ModelingAssumption::new(
+            "Revenue Floor",
+            format!(
+                "{} revenue is topped up to the contractual minimum wherever it falls short",
+                inputs.period
+            ),
+        ));
+    }
+    assumptions.push(ModelingAssumption::new(
+        "Tax",
+        if inputs.after_tax {
+            AssumptionStatement::TaxedAt {
+                rate: inputs.tax_rate,
+            }
+        } else {
+            "Headline values are pre-tax".into()
+        },
+    ));
+    assumptions.push(ModelingAssumption::new(
+        "Scenario Weights",
+        if inputs.scenario_weights.is_weighted() {
+            "Supplied per dimension, with payout, volatility and lift independent"
+        } else {
+            "Equal across scenarios, with payout, volatility and lift independent"
+        },
+    ));
+    if let Some(currency) = &inputs.display_currency {
+        assumptions.push(ModelingAssumption::new(
+            "Currency",
+            format!(
+                "Valued in USD; monetary figures converted to {} at {} {} per USD",
+                currency.code, currency.fx_rate, currency.code
+            ),
+        ));
+    }
+    assumptions
+}
 
-    let low_lift_value = find_value(
-        PayoutScenario::Day90,
-        VolatilityScenario::Typical,
-        Some(LiftScenario::Low),
-    )?;
-    let high_lift_value = find_value(
-        PayoutScenario::Day90,
-        VolatilityScenario::Typical,
-        Some(LiftScenario::High),
-    )?;
-    let lift_impact = ((high_lift_value - low_lift_value) / low_lift_value) * 100.0;
+/// Number of worker threads for the grid: `--threads`, or every available core
+fn worker_threads(inputs: &ValuationInputs) -> usize {
+    inputs.threads.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    })
+}
 
-    let day60_value = find_value(
-        PayoutScenario::Day60,
-        VolatilityScenario::Typical,
-        Some(LiftScenario::Medium),
-    )?;
-    let day120_value = find_value(
-        PayoutScenario::Day120,
-        VolatilityScenario::Typical,
-        Some(LiftScenario::Medium),
-    )?;
-    let payout_impact = ((day60_value - day120_value) / day60_value) * 100.0;
+/// Apply `f` to every item across up to `threads` scoped worker threads.
+///
+/// Items are split into contiguous chunks and the results reassembled in
+/// input order, so the output does not depend on the thread count.
+fn parallel_map<T, R, F>(items: &[T], threads: usize, f: F) -> Result<Vec<R>, ModelError>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Result<R, ModelError> + Sync,
+{
+    let threads = threads.clamp(1, items.len().max(1));
+    if threads == 1 {
+        return items.iter().map(&f).collect();
+    }
 
-    Ok(SummaryStatistics {
-        min_valuation,
-        max_valuation,
-        central_estimate,
-        best_volatility: VolatilityScenario::Low,
-        worst_volatility: VolatilityScenario::Extreme,
-        volatility_impact,
-        lift_impact,
-        payout_impact,
-        adjusted_baseline,
+    let chunk_size = items.len().div_ceil(threads);
+    let f = &f;
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Result<Vec<R>, _>>()))
+            .collect();
+        let mut results = Vec::with_capacity(items.len());
+        for worker in workers {
+            results.extend(worker.join().expect("valuation worker panicked")?);
+        }
+        Ok(results)
     })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use approx::assert_relative_eq;
+/// Select the scenarios with the lowest and highest present values.
+///
+/// Ties resolve deterministically to the smallest `ScenarioId`: the earlier
+/// payout, then the lower volatility, then the lower lift (baseline first).
+fn select_extreme_scenarios(
+    all_valuations: &[ValuationResult],
+) -> Option<(&ValuationResult, &ValuationResult)> {
+    let mut iter = all_valuations.iter();
+    let first = iter.next()?;
+    let (mut min, mut max) = (first, first);
 
-    /// Creates a default, valid set of ValuationInputs for use in tests.
-    /// Includes the new investor-centric lift model parameters.
-    fn get_default_inputs() -> ValuationInputs {
-        ValuationInputs {
-            raw_forecast: 220_000.0,
-            risk_free_rate: 0.045,
-            platform_risk_premium: 0.12,
-            platform_adjustment_factor: -0.091,
-            baseline_audience: 1_000_000.0,
-            rpm: 15.0,
-            investor_count: 1000,
-            lift_per_investor: 10.0,
+    for v in iter {
+        let earlier = v.scenario() < min.scenario();
+        if v.present_value < min.present_value || (v.present_value == min.present_value && earlier)
+        {
+            min = v;
+        }
+        let earlier = v.scenario() < max.scenario();
+        if v.present_value > max.present_value || (v.present_value == max.present_value && earlier)
+        {
+            max = v;
         }
     }
 
-    #[test]
-    fn test_adjusted_baseline_calculation() {
-        let baseline = calculate_adjusted_baseline(100_000.0, -0.091);
-        assert_relative_eq!(baseline, 90_900.0, epsilon = 0.01);
-    }
+    Some((min, max))
+}
+
+/// Value the grid under both discrete and continuous compounding and pair
+/// each scenario's present values
+pub fn compare_compounding_methods(
+    inputs: &ValuationInputs,
+) -> Result<Vec<MethodComparison>, ModelError> {
+    let value_with = |compounding| {
+        let inputs = ValuationInputs {
+            compounding,
+            ..inputs.clone()
+        };
+        calculate_full_valuation(&inputs).map(|data| data.all_valuations)
+    };
+    let discrete = value_with(Compounding::Discrete)?;
+    let continuous: HashMap<ScenarioId, f64> = value_with(Compounding::Continuous)?
+        .iter()
+        .map(|v| (v.scenario(), v.present_value))
+        .collect();
+
+    let mut comparison = discrete
+        .iter()
+        .map(|v| {
+            let scenario = v.scenario();
+            let continuous = continuous
+                .get(&scenario)
+                .copied()
+                .ok_or(ModelError::ScenarioNotFound(scenario))?;
+            Ok(MethodComparison {
+                scenario,
+                discrete: v.present_value,
+                continuous,
+            })
+        })
+        .collect::<Result<Vec<_>, ModelError>>()?;
+    comparison.sort_by_key(|row| row.scenario);
+    Ok(comparison)
+}
+
+/// Value the central scenario under every day-count convention
+pub fn compare_day_counts(inputs: &ValuationInputs) -> Result<Vec<DayCountComparison>, ModelError> {
+    DayCount::all()
+        .iter()
+        .map(|&day_count| {
+            let inputs = ValuationInputs {
+                day_count,
+                ..inputs.clone()
+            };
+            Ok(DayCountComparison {
+                day_count,
+                central_estimate: calculate_full_valuation(&inputs)?.summary.central_estimate,
+            })
+        })
+        .collect()
+}
+
+/// Value the central scenario with lift driven by investors and with the
+/// fixed quarterly lift from `inputs.fixed_lift`
+pub fn compare_lift_models(inputs: &ValuationInputs) -> Result<LiftModelComparison, ModelError> {
+    let fixed_lift = inputs.fixed_lift.ok_or_else(|| {
+        ModelError::InvalidInput("Comparing lift models requires a fixed lift".to_string())
+    })?;
+    let central_estimate = |fixed_lift| {
+        let inputs = ValuationInputs {
+            fixed_lift,
+            ..inputs.clone()
+        };
+        calculate_full_valuation(&inputs).map(|data| data.summary.central_estimate)
+    };
+    Ok(LiftModelComparison {
+        fixed_lift,
+        driver_based: central_estimate(None)?,
+        fixed: central_estimate(Some(fixed_lift))?,
+    })
+}
+
+/// Value each lift cell's lift cash flow on its own, as a growth option on
+/// top of the baseline. The lift is discounted exactly as in the full grid
+/// (same rate, growth and lift payout), with no baseline or floor alongside.
+pub fn value_lift_only(data: &ReportData) -> Result<Vec<LiftOptionValue>, ModelError> {
+    let inputs = &data.inputs;
+    data.all_valuations
+        .iter()
+        .filter_map(|v| v.lift_scenario.map(|lift| (v.scenario(), lift)))
+        .map(|(scenario, lift)| {
+            let present_value = calculate_scenario_present_value(
+                inputs,
+                0.0,
+                data.lift_assumptions.period_lift(lift),
+                data.discount_rate(scenario),
+                data.growth_rates[&scenario.volatility],
+                scenario.payout,
+            )?;
+            Ok(LiftOptionValue {
+                scenario,
+                present_value: present_value.to_f64() * inputs.headline_factor(),
+            })
+        })
+        .collect()
+}
+
+/// Split the discount haircut of the central volatility and lift at `payout`
+/// into the part owed to the time value of money and the part owed to risk:
+/// face value less the present value at the risk-free rate alone, and that
+/// value less the present value at the full rate. The cash flows are the
+/// cell's, growth included, so only the rate differs.
+fn risk_vs_time_decomposition(
+    data: &ReportData,
+    payout: PayoutScenario,
+) -> Result<HaircutSplit, ModelError> {
+    let inputs = &data.inputs;
+    let scenario = ScenarioId {
+        payout,
+        ..data.summary.central_scenario
+    };
+    let central = data
+        .all_valuations
+        .iter()
+        .find(|v| v.scenario() == scenario)
+        .ok_or(ModelError::ScenarioNotFound(scenario))?;
+
+    let lift_cash_flow = scenario
+        .lift
+        .map_or(0.0, |lift| data.lift_assumptions.period_lift(lift));
+    let risk_free_value = calculate_scenario_present_value(
+        inputs,
+        data.summary.adjusted_baseline + central.floor_top_up,
+        lift_cash_flow,
+        inputs.risk_free_rate.as_decimal(),
+        data.growth_rates[&scenario.volatility],
+        payout,
+    )?;
+    let risk_free_value = risk_free_value.to_f64() * inputs.headline_factor();
+    Ok(HaircutSplit {
+        payout,
+        time: central.face_value - risk_free_value,
+        risk: risk_free_value - central.present_value,
+    })
+}
+
+/// Build the derivation tree behind every figure of a calculated report, for
+/// `--explain-json`. Each scenario's root is its present value, or its
+/// position value when an ownership fraction is given.
+pub fn build_derivation_tree(data: &ReportData) -> DerivationTree {
+    let inputs = &data.inputs;
+    let adjusted_baseline = DerivationNode::derived(
+        "adjusted_baseline",
+        data.summary.adjusted_baseline,
+        "raw_forecast * (1 + platform_adjustment_factor)",
+        vec![
+            DerivationNode::leaf("raw_forecast", inputs.raw_forecast),
+            DerivationNode::leaf(
+                "platform_adjustment_factor",
+                inputs.platform_adjustment_factor,
+            ),
+        ],
+    );
+    let discount_rates = VolatilityScenario::all()
+        .iter()
+        .filter_map(|volatility| {
+            let components = data.discount_rates.get(volatility)?;
+            let node = match (&inputs.rate_formula, components.override_rate) {
+                (Some(formula), _) => DerivationNode::derived(
+                    "discount_rate",
+                    components.total_rate().as_decimal(),
+                    &formula.to_string(),
+                    vec![
+                        DerivationNode::leaf("rf", components.risk_free_rate.as_decimal()),
+                        DerivationNode::leaf(
+                            "vol_premium",
+                            components.volatility_premium.as_decimal(),
+                        ),
+                        DerivationNode::leaf(
+                            "platform_premium",
+                            components.platform_risk_premium.as_decimal(),
+                        ),
+                    ],
+                ),
+                (None, Some(rate)) => DerivationNode::leaf("discount_rate", rate.as_decimal()),
+                (None, None) => DerivationNode::derived(
+                    "discount_rate",
+                    components.total_rate().as_decimal(),
+                    "risk_free_rate + volatility_premium + platform_risk_premium",
+                    vec![
+                        DerivationNode::leaf(
+                            "risk_free_rate",
+                            components.risk_free_rate.as_decimal(),
+                        ),
+                        DerivationNode::leaf(
+                            "volatility_premium",
+                            components.volatility_premium.as_decimal(),
+                        ),
+                        DerivationNode::leaf(
+                            "platform_risk_premium",
+                            components.platform_risk_premium.as_decimal(),
+                        ),
+                    ],
+                ),
+            };
+            Some((*volatility, node))
+        })
+        .collect::<Vec<_>>();
+
+    let scenarios = data
+        .all_valuations
+        .iter()
+        .map(|valuation| {
+            let discount_rate = match &inputs.rate_matrix {
+                Some(_) => {
+                    DerivationNode::leaf("discount_rate", data.discount_rate(valuation.scenario()))
+                }
+                None => discount_rates
+                    .iter()
+                    .find(|(volatility, _)| *volatility == valuation.volatility_scenario)
+                    .map(|(_, node)| node.clone())
+                    .unwrap_or_else(|| DerivationNode::leaf("discount_rate", f64::NAN)),
+            };
+            let present_value =
+                derive_present_value(data, &adjusted_baseline, discount_rate, valuation);
+            let root = match (valuation.position_value, inputs.ownership_fraction) {
+                (Some(position_value), Some(share)) => DerivationNode::derived(
+                    "position_value",
+                    position_value,
+                    "present_value * ownership_fraction",
+                    vec![
+                        present_value,
+                        DerivationNode::leaf("ownership_fraction", share),
+                    ],
+                ),
+                _ => present_value,
+            };
+            (valuation.scenario(), root)
+        })
+        .collect();
+
+    DerivationTree {
+        adjusted_baseline,
+        discount_rates,
+        scenarios,
+    }
+}
+
+/// Derive one grid cell's present value as its cash flow times the effective
+/// discount factor, each traced down to the inputs
+fn derive_present_value(
+    data: &ReportData,
+    adjusted_baseline: &DerivationNode,
+    discount_rate: DerivationNode,
+    valuation: &ValuationResult,
+) -> DerivationNode {
+    let inputs = &data.inputs;
+    let assumptions = &data.lift_assumptions;
+
+    let mut cash_flow_inputs = vec![adjusted_baseline.clone()];
+    if valuation.floor_top_up > 0.0 {
+        cash_flow_inputs.push(DerivationNode::derived(
+            "revenue_floor_top_up",
+            valuation.floor_top_up,
+            "revenue_floor - (adjusted_baseline + quarterly_lift)",
+            vec![DerivationNode::leaf(
+                "revenue_floor",
+                inputs.revenue_floor.unwrap_or_default(),
+            )],
+        ));
+    }
+    if let (Some(lift), Some(fixed)) = (valuation.lift_scenario, assumptions.fixed_lift) {
+        cash_flow_inputs.push(DerivationNode::derived(
+            "quarterly_lift",
+            assumptions.period_lift(lift),
+            "fixed_lift * activation_factor",
+            vec![
+                DerivationNode::leaf("fixed_lift", fixed),
+                DerivationNode::leaf("activation_factor", lift.activation_factor()),
+            ],
+        ));
+    } else if let Some(lift) = valuation.lift_scenario {
+        let ramp_weight = assumptions.lift_ramp.monthly_weights().iter().sum::<f64>()
+            * (assumptions.period.months() / 3.0);
+        cash_flow_inputs.push(DerivationNode::derived(
+            "quarterly_lift",
+            assumptions.period_lift(lift),
+            "additional_audience * incremental_rpm / 1000 * ramp_months",
+            vec![
+                DerivationNode::derived(
+                    "additional_audience",
+                    lift.additional_audience(
+                        assumptions.investor_count,
+                        assumptions.lift_per_investor,
+                    ),
+                    "investor_count * lift_per_investor * activation_factor",
+                    vec![
+                        DerivationNode::leaf("investor_count", assumptions.investor_count),
+                        DerivationNode::leaf("lift_per_investor", assumptions.lift_per_investor),
+                        DerivationNode::leaf("activation_factor", lift.activation_factor()),
+                    ],
+                ),
+                DerivationNode::leaf("incremental_rpm", assumptions.incremental_rpm),
+                DerivationNode::leaf("ramp_months", ramp_weight),
+            ],
+        ));
+    }
+    cash_flow_inputs.push(DerivationNode::leaf("quarters", inputs.quarters as f64));
+    let growth_term = match &inputs.two_stage_growth {
+        Some(stages) => {
+            cash_flow_inputs.push(DerivationNode::leaf(
+                "stage1_quarters",
+                stages.stage1_quarters as f64,
+            ));
+            cash_flow_inputs.push(DerivationNode::leaf("stage1_growth", stages.stage1_growth));
+            cash_flow_inputs.push(DerivationNode::leaf("stage2_growth", stages.stage2_growth));
+            "(1 + stage1_growth)^min(q, stage1_quarters) * (1 + stage2_growth)^max(q - stage1_quarters, 0)"
+        }
+        None => {
+            cash_flow_inputs.push(DerivationNode::leaf(
+                "quarterly_growth_rate",
+                data.growth_rates[&valuation.volatility_scenario],
+            ));
+            "(1 + quarterly_growth_rate)^q"
+        }
+    };
+    cash_flow_inputs.push(DerivationNode::leaf(
+        "reinvestment_rate",
+        inputs.reinvestment_rate,
+    ));
+    let mut cash_flow_formula = format!(
+        "sum over quarters q of (adjusted_baseline + revenue_floor_top_up + quarterly_lift * (1 + reinvestment_rate)^q) * {}",
+        growth_term
+    );
+    if inputs.after_tax {
+        cash_flow_inputs.push(DerivationNode::leaf("tax_rate", inputs.tax_rate));
+        cash_flow_formula.push_str(" * (1 - tax_rate)");
+    }
+    let cash_flow = DerivationNode::derived(
+        "cash_flow",
+        valuation.face_value,
+        &cash_flow_formula,
+        cash_flow_inputs,
+    );
+
+    let mut timing = vec![discount_rate];
+    match (
+        valuation.payout_scenario.years(inputs.day_count),
+        &inputs.payout_ladder,
+    ) {
+        (Some(years), _) => timing.push(DerivationNode::leaf("payout_years", years)),
+        (None, Some(ladder)) => {
+            for tranche in &ladder.tranches {
+                timing.push(DerivationNode::leaf(
+                    &format!("tranche_{}d_fraction", tranche.days),
+                    tranche.fraction,
+                ));
+                timing.push(DerivationNode::leaf(
+                    &format!("tranche_{}d_years", tranche.days),
+                    tranche.years(inputs.day_count),
+                ));
+            }
+        }
+        (None, None) => {}
+    }
+    if let (Some(days), Some(_)) = (inputs.lift_payout_days, valuation.lift_scenario) {
+        timing.push(DerivationNode::leaf(
+            "lift_payout_years",
+            inputs.day_count.year_fraction(days),
+        ));
+    }
+    let per_year = inputs.period.per_year();
+    let discount_factor = DerivationNode::derived(
+        "discount_factor",
+        valuation.retention_ratio(valuation.face_value),
+        &match inputs.compounding {
+            Compounding::Discrete => format!(
+                "cash-flow-weighted (1 + discount_rate)^-(payout_years + q / {}) over quarters q",
+                per_year
+            ),
+            Compounding::Continuous => format!(
+                "cash-flow-weighted e^(-discount_rate * (payout_years + q / {})) over quarters q",
+                per_year
+            ),
+        },
+        timing,
+    );
+
+    DerivationNode::derived(
+        "present_value",
+        valuation.present_value,
+        "cash_flow * discount_factor",
+        vec![cash_flow, discount_factor],
+    )
+}
+
+/// Weighted scenario outcomes as `(present value, probability)` pairs.
+///
+/// Each cell is weighted by `inputs.scenario_weights`, which default to
+/// equal. Lifted scenarios are the outcomes whenever they were valued; the
+/// baseline grid is only used for baseline-only runs.
+pub fn scenario_outcomes(data: &ReportData) -> Vec<(f64, f64)> {
+    weighted_cells(data)
+        .into_iter()
+        .map(|(valuation, probability)| (valuation.present_value, probability))
+        .collect()
+}
+
+/// The cells making up the scenario distribution, with probabilities summing to one
+fn weighted_cells(data: &ReportData) -> Vec<(&ValuationResult, f64)> {
+    weighted_valuations(&data.all_valuations, &data.inputs.scenario_weights)
+}
+
+/// The lifted cells of `valuations` (every cell in baseline-only runs), each
+/// with its probability under `weights`, normalized to sum to one
+fn weighted_valuations<'a>(
+    valuations: &'a [ValuationResult],
+    weights: &ScenarioWeights,
+) -> Vec<(&'a ValuationResult, f64)> {
+    let lifted = valuations.iter().any(|v| v.lift_scenario.is_some());
+    let cells: Vec<(&ValuationResult, f64)> = valuations
+        .iter()
+        .filter(|v| v.lift_scenario.is_some() == lifted)
+        .map(|v| (v, weights.probability(v.scenario())))
+        .collect();
+    let total: f64 = cells.iter().map(|(_, weight)| weight).sum();
+    cells
+        .into_iter()
+        .map(|(valuation, weight)| (valuation, weight / total))
+        .collect()
+}
+
+/// Probability of the central scenario itself and of an outcome at or below
+/// the central estimate, under the weighted scenario distribution
+pub fn central_likelihood(data: &ReportData) -> CentralLikelihood {
+    let summary = &data.summary;
+    let cells = weighted_cells(data);
+    let mass = |include: &dyn Fn(&ValuationResult) -> bool| -> f64 {
+        cells
+            .iter()
+            .filter(|(valuation, _)| include(valuation))
+            .map(|(_, probability)| probability)
+            .sum()
+    };
+    CentralLikelihood {
+        central_probability: mass(&|v| v.scenario() == summary.central_scenario),
+        at_or_below_central: mass(&|v| v.present_value <= summary.central_estimate),
+    }
+}
+
+/// Largest stress level searched either side of the supplied weights
+const MAX_STRESS: f64 = 20.0;
+
+/// Stress level at which the weighted expected value equals `market_price`.
+///
+/// Stress `s` tilts the scenario weights exponentially across the volatility
+/// scenarios: each cell's probability is scaled by `exp(s * rank)`, where the
+/// rank runs evenly from -1 at the calmest scenario to +1 at the most
+/// volatile, then renormalized. Zero reproduces the weights as given, and
+/// positive values shift weight toward higher volatility and lower values.
+/// Prices beyond what any tilt within `MAX_STRESS` reaches are rejected with
+/// the warning to record instead.
+pub fn implied_stress(data: &ReportData, market_price: f64) -> Result<f64, ModelWarning> {
+    let cells = weighted_cells(data);
+    let last = (VolatilityScenario::all().len() - 1).max(1) as f64;
+    let rank = |volatility: VolatilityScenario| {
+        let index = VolatilityScenario::all()
+            .iter()
+            .position(|&v| v == volatility)
+            .unwrap_or_default();
+        2.0 * index as f64 / last - 1.0
+    };
+    let expected_value = |stress: f64| {
+        let (value, weight) =
+            cells
+                .iter()
+                .fold((0.0, 0.0), |(value, weight), (valuation, probability)| {
+                    let tilted = probability * (stress * rank(valuation.volatility_scenario)).exp();
+                    (value + tilted * valuation.present_value, weight + tilted)
+                });
+        value / weight
+    };
+
+    // Stress normally lowers the expected value, but a rate matrix may not,
+    // so bisect in whichever direction the bracket runs
+    let (calm, stressed) = (expected_value(-MAX_STRESS), expected_value(MAX_STRESS));
+    if !(calm.min(stressed)..=calm.max(stressed)).contains(&market_price) {
+        return Err(ModelWarning::MarketPriceOutOfRange {
+            market_price,
+            low: calm.min(stressed),
+            high: calm.max(stressed),
+        });
+    }
+    let decreasing = stressed <= calm;
+    let (mut low, mut high) = (-MAX_STRESS, MAX_STRESS);
+    for _ in 0..100 {
+        let mid = (low + high) / 2.0;
+        if (expected_value(mid) > market_price) == decreasing {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok((low + high) / 2.0)
+}
+
+/// Smallest outcome whose cumulative probability reaches `percentile` (0.0-1.0)
+pub fn weighted_percentile(outcomes: &[(f64, f64)], percentile: f64) -> Option<f64> {
+    let mut sorted = outcomes.to_vec();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let total: f64 = sorted.iter().map(|(_, weight)| weight).sum();
+
+    let mut cumulative = 0.0;
+    for (value, weight) in &sorted {
+        cumulative += weight / total;
+        if cumulative >= percentile - f64::EPSILON {
+            return Some(*value);
+        }
+    }
+    sorted.last().map(|(value, _)| *value)
+}
+
+/// Check that requested report percentiles lie strictly between 0 and 100
+/// and are listed in ascending order
+pub fn validate_percentiles(percentiles: &[f64]) -> Result<(), ModelError> {
+    if let Some(p) = percentiles.iter().find(|p| !(**p > 0.0 && **p < 100.0)) {
+        return Err(ModelError::InvalidInput(format!(
+            "Percentile {} must be strictly between 0 and 100",
+            p
+        )));
+    }
+    if percentiles.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(ModelError::InvalidInput(
+            "Percentiles must be listed in ascending order".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Value of the scenario distribution at each requested percentile (0-100)
+pub fn scenario_percentiles(data: &ReportData, percentiles: &[f64]) -> Vec<(f64, f64)> {
+    let outcomes = scenario_outcomes(data);
+    percentiles
+        .iter()
+        .filter_map(|&p| weighted_percentile(&outcomes, p / 100.0).map(|value| (p, value)))
+        .collect()
+}
+
+/// Value at risk: how far the `1 - confidence` percentile of the weighted
+/// present value distribution falls below the central estimate, e.g. a
+/// `confidence` of 0.95 gives the 5% VaR
+pub fn value_at_risk(data: &ReportData, confidence: f64) -> f64 {
+    weighted_percentile(&scenario_outcomes(data), 1.0 - confidence)
+        .map(|tail_value| data.summary.central_estimate - tail_value)
+        .unwrap_or(0.0)
+}
+
+/// Calculate summary statistics for the executive summary
+fn calculate_summary_statistics(
+    all_valuations: &[ValuationResult], // Takes the single unified vector
+    adjusted_baseline: f64,
+    inputs: &ValuationInputs,
+) -> Result<SummaryStatistics, ModelError> {
+    let (min_result, max_result) = select_extreme_scenarios(all_valuations)
+        .ok_or_else(|| ModelError::CalculationError("No valuations to summarize".to_string()))?;
+    let min_valuation = min_result.present_value;
+    let max_valuation = max_result.present_value;
+
+    // Look up a single grid cell, naming it in the error if it is missing
+    let find_result =
+        |payout: PayoutScenario, volatility: VolatilityScenario, lift: Option<LiftScenario>| {
+            let scenario = ScenarioId {
+                payout,
+                volatility,
+                lift,
+            };
+            all_valuations
+                .iter()
+                .find(|v| v.scenario() == scenario)
+                .ok_or(ModelError::ScenarioNotFound(scenario))
+        };
+    let find_value = |payout, volatility, lift| {
+        find_result(payout, volatility, lift).map(|result| result.present_value)
+    };
+
+    // Without lift results, the sensitivities are measured on the baseline grid
+    let reference_lift = if inputs.baseline_only {
+        None
+    } else {
+        Some(LiftScenario::Medium)
+    };
+    // A ladder replaces the 90-day bullet as the reference payout
+    let reference_payout = if inputs.payout_ladder.is_some() {
+        PayoutScenario::Laddered
+    } else {
+        PayoutScenario::Day90
+    };
+
+    let central = find_result(
+        reference_payout,
+        VolatilityScenario::Typical,
+        reference_lift,
+    )?;
+    let central_estimate = central.present_value;
+
+    // The investable corners combine each dimension's plausible worst or best
+    let payouts = inputs.payout_scenarios();
+    let corner_lift = |lift| (!inputs.baseline_only).then_some(lift);
+    let downside = find_value(
+        payouts[payouts.len() - 1],
+        VolatilityScenario::Extreme,
+        corner_lift(LiftScenario::Low),
+    )?;
+    let upside = find_value(
+        payouts[0],
+        VolatilityScenario::Low,
+        corner_lift(LiftScenario::High),
+    )?;
+
+    let low_vol_value = find_value(reference_payout, VolatilityScenario::Low, reference_lift)?;
+    let extreme_vol_value = find_value(
+        reference_payout,
+        VolatilityScenario::Extreme,
+        reference_lift,
+    )?;
+    let volatility_impact = pct_change(low_vol_value, extreme_vol_value).map(|change| -change);
+
+    let lift_impact = if inputs.baseline_only {
+        None
+    } else {
+        let low_lift_value = find_value(
+            reference_payout,
+            VolatilityScenario::Typical,
+            Some(LiftScenario::Low),
+        )?;
+        let high_lift_value = find_value(
+            reference_payout,
+            VolatilityScenario::Typical,
+            Some(LiftScenario::High),
+        )?;
+        pct_change(low_lift_value, high_lift_value)
+    };
+
+    // Discounting is not linear in time, so each 30-day step is measured
+    // from the values either side of it rather than as a share of the total
+    let (payout_impact, payout_impact_60_90, payout_impact_90_120) =
+        if inputs.payout_ladder.is_some() {
+            (None, None, None)
+        } else {
+            let day60_value = find_value(
+                PayoutScenario::Day60,
+                VolatilityScenario::Typical,
+                reference_lift,
+            )?;
+            let day90_value = find_value(
+                PayoutScenario::Day90,
+                VolatilityScenario::Typical,
+                reference_lift,
+            )?;
+            let day120_value = find_value(
+                PayoutScenario::Day120,
+                VolatilityScenario::Typical,
+                reference_lift,
+            )?;
+            let reduction = |from: f64, to: f64| pct_change(from, to).map(|change| -change);
+            (
+                reduction(day60_value, day120_value),
+                reduction(day60_value, day90_value),
+                reduction(day90_value, day120_value),
+            )
+        };
+
+    Ok(SummaryStatistics {
+        min_valuation,
+        max_valuation,
+        min_scenario: min_result.scenario(),
+        max_scenario: max_result.scenario(),
+        central_scenario: central.scenario(),
+        central_estimate,
+        central_face_value: central.face_value,
+        downside,
+        upside,
+        position_value: central.position_value,
+        display_central_estimate: central.display_value,
+        best_volatility: VolatilityScenario::Low,
+        worst_volatility: VolatilityScenario::Extreme,
+        volatility_impact,
+        lift_impact,
+        payout_impact,
+        payout_impact_60_90,
+        payout_impact_90_120,
+        adjusted_baseline,
+        quarterly_revenue_multiple: revenue_multiple(central_estimate, adjusted_baseline),
+        annual_revenue_multiple: revenue_multiple(
+            central_estimate,
+            adjusted_baseline * inputs.period.per_year() as f64,
+        ),
+        value_ratio: inputs.purchase_price.map(|price| central_estimate / price),
+        scenario_agreement: scenario_agreement(
+            all_valuations,
+            &inputs.scenario_weights,
+            central_estimate,
+        ),
+    })
+}
+
+/// Percentage change from `from` to `to`, `(to - from) / |from| * 100`:
+/// positive for a rise and negative for a fall, always relative to the size
+/// of the starting value. `None` when `from` is zero, where no percentage change is
+/// defined, rather than an infinite or NaN figure.
+pub fn pct_change(from: f64, to: f64) -> Option<f64> {
+    (from != 0.0).then(|| (to - from) / from.abs() * 100.0)
+}
+
+/// Relative distance from the central estimate within which a scenario agrees with it
+pub const AGREEMENT_BAND: f64 = 0.10;
+
+/// Probability, under the weighted scenario distribution, of a value within
+/// `AGREEMENT_BAND` of `central_estimate`
+fn scenario_agreement(
+    all_valuations: &[ValuationResult],
+    weights: &ScenarioWeights,
+    central_estimate: f64,
+) -> f64 {
+    let band = AGREEMENT_BAND * central_estimate.abs();
+    weighted_valuations(all_valuations, weights)
+        .into_iter()
+        .filter(|(v, _)| (v.present_value - central_estimate).abs() <= band)
+        .map(|(_, probability)| probability)
+        .sum()
+}
+
+/// Price-to-revenue multiple, `None` when there is no revenue to divide by
+fn revenue_multiple(value: f64, revenue: f64) -> Option<f64> {
+    (revenue != 0.0).then(|| value / revenue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate::Rate;
+    use approx::assert_relative_eq;
+
+    /// The messages of the warnings that quote no amounts
+    fn notes(warnings: &[ModelWarning]) -> Vec<&str> {
+        warnings
+            .iter()
+            .filter_map(|warning| match warning {
+                ModelWarning::Note(message) => Some(message.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Creates a default, valid set of ValuationInputs for use in tests.
+    /// Includes the new investor-centric lift model parameters.
+    fn get_default_inputs() -> ValuationInputs {
+        ValuationInputs {
+            raw_forecast: 220_000.0,
+            period: RevenuePeriod::Quarterly,
+            risk_free_rate: Rate::constant(0.045),
+            platform_risk_premium: Rate::constant(0.12),
+            platform_adjustment_factor: -0.091,
+            baseline_audience: 1_000_000.0,
+            rpm: 15.0,
+            incremental_rpm: 15.0,
+            investor_count: 1000.0,
+            lift_per_investor: 10.0,
+            lift_ramp: LiftRamp::Instant,
+            baseline_only: false,
+            payout_ladder: None,
+            monthly_breakdown: false,
+            day_count: DayCount::Actual365,
+            compounding: Compounding::Discrete,
+            forecast_confidence: ForecastConfidence::Medium,
+            quarters: 1,
+            quarterly_growth_rate: 0.0,
+            two_stage_growth: None,
+            reinvestment_rate: 0.0,
+            revenue_floor: None,
+            tax_rate: 0.0,
+            after_tax: false,
+            lift_payout_days: None,
+            strict: false,
+            cap_at_face: false,
+            lift_dominance_threshold: 1.0,
+            max_scenarios: 10_000,
+            max_payout_days: 3650,
+            purchase_price: None,
+            market_price: None,
+            ownership_fraction: None,
+            token_supply: None,
+            display_currency: None,
+            threads: Some(1),
+            discount_rate_overrides: None,
+            rate_matrix: None,
+            rate_formula: None,
+            scenario_weights: ScenarioWeights::default(),
+            fixed_lift: None,
+            lift_acquisition_cost: None,
+        }
+    }
+
+    #[test]
+    fn test_adjusted_baseline_calculation() {
+        let baseline = calculate_adjusted_baseline(100_000.0, -0.091);
+        assert_relative_eq!(baseline, 90_900.0, epsilon = 0.01);
+    }
 
     #[test]
     fn test_present_value_calculation() {
@@ -261,7 +2023,7 @@ mod tests {
     #[test]
     /// Tests the calculation of additional audience based on the new driver-based model.
     fn test_additional_audience_calculation() {
-        let investor_count = 1000;
+        let investor_count = 1000.0;
         let lift_per_investor = 10.0;
 
         // Medium scenario has an activation factor of 1.0
@@ -279,21 +2041,31 @@ mod tests {
 
     #[test]
     /// Tests the quarterly lift revenue calculation using the new model.
-    fn test_quarterly_lift_revenue_calculation() {
-        let investor_count = 1000;
+    fn test_period_lift_revenue_calculation() {
+        let investor_count = 1000.0;
         let lift_per_investor = 10.0;
         let rpm = 20.0; // Use a custom RPM for the test
 
         // Test Medium Lift (activation factor 1.0)
-        let medium_lift_revenue =
-            LiftScenario::Medium.quarterly_lift(investor_count, lift_per_investor, rpm);
+        let medium_lift_revenue = LiftScenario::Medium.period_lift(
+            investor_count,
+            lift_per_investor,
+            rpm,
+            LiftRamp::Instant,
+            RevenuePeriod::Quarterly,
+        );
         // Expected Audience: 1000 * 10 * 1.0 = 10,000
         // Expected Revenue: (10,000 / 1000) * $20 RPM * 3 months = $600
         assert_relative_eq!(medium_lift_revenue, 600.0);
 
         // Test Low Lift (activation factor 0.5)
-        let low_lift_revenue =
-            LiftScenario::Low.quarterly_lift(investor_count, lift_per_investor, rpm);
+        let low_lift_revenue = LiftScenario::Low.period_lift(
+            investor_count,
+            lift_per_investor,
+            rpm,
+            LiftRamp::Instant,
+            RevenuePeriod::Quarterly,
+        );
         // Expected Audience: 1000 * 10 * 0.5 = 5,000
         // Expected Revenue: (5,000 / 1000) * $20 RPM * 3 months = $300
         assert_relative_eq!(low_lift_revenue, 300.0);
@@ -306,24 +2078,27 @@ mod tests {
         let mut inputs = get_default_inputs();
         // Override default inputs with custom test values
         inputs.rpm = 25.0;
-        inputs.investor_count = 2000;
+        inputs.incremental_rpm = 25.0;
+        inputs.investor_count = 2000.0;
         inputs.lift_per_investor = 15.0;
 
         let report_data = calculate_full_valuation(&inputs).unwrap();
 
         // 1. Check that the report's assumptions reflect the custom inputs.
         assert_relative_eq!(report_data.lift_assumptions.rpm, 25.0);
-        assert_eq!(report_data.lift_assumptions.investor_count, 2000);
+        assert_eq!(report_data.lift_assumptions.investor_count, 2000.0);
         assert_relative_eq!(report_data.lift_assumptions.lift_per_investor, 15.0);
 
         // 2. Manually calculate the expected result for a single, known scenario (the central estimate).
         // Scenario: Medium Lift, Typical Volatility, 90 Day Payout
 
         // Calculate expected lift revenue using the new model
-        let expected_lift = LiftScenario::Medium.quarterly_lift(
+        let expected_lift = LiftScenario::Medium.period_lift(
             inputs.investor_count,
             inputs.lift_per_investor,
-            inputs.rpm,
+            inputs.incremental_rpm,
+            inputs.lift_ramp,
+            RevenuePeriod::Quarterly,
         );
         // Expected Audience: 2000 * 15 * 1.0 = 30,000
         // Expected Revenue: (30,000 / 1000) * $25 RPM * 3 months = $2,250
@@ -336,8 +2111,8 @@ mod tests {
 
         // Calculate the discount rate for this scenario
         let components = calculate_discount_rate(&inputs, VolatilityScenario::Typical);
-        let discount_rate = components.total_rate();
-        let time_years = PayoutScenario::Day90.years();
+        let discount_rate = components.total_rate().as_decimal();
+        let time_years = PayoutScenario::Day90.years(inputs.day_count).unwrap();
 
         // Calculate the final expected Present Value
         let expected_pv =
@@ -347,4 +2122,1803 @@ mod tests {
         let central_estimate = report_data.summary.central_estimate;
         assert_relative_eq!(central_estimate, expected_pv, epsilon = 0.01);
     }
+
+    #[test]
+    fn test_incremental_rpm_scales_lift_revenue() {
+        let inputs = get_default_inputs();
+        let mut discounted = get_default_inputs();
+        discounted.incremental_rpm = inputs.rpm / 2.0;
+
+        let lift_cash_flow = |inputs: &ValuationInputs| {
+            let data = calculate_full_valuation(inputs).unwrap();
+            let lifted = data
+                .all_valuations
+                .iter()
+                .find(|v| v.scenario() == reference_scenario(Some(LiftScenario::Medium)))
+                .unwrap()
+                .present_value;
+            let baseline = data
+                .all_valuations
+                .iter()
+                .find(|v| v.scenario() == reference_scenario(None))
+                .unwrap()
+                .present_value;
+            lifted - baseline
+        };
+
+        assert_relative_eq!(
+            lift_cash_flow(&discounted),
+            lift_cash_flow(&inputs) / 2.0,
+            epsilon = 1e-3
+        );
+    }
+
+    fn reference_scenario(lift: Option<LiftScenario>) -> ScenarioId {
+        ScenarioId {
+            payout: PayoutScenario::Day90,
+            volatility: VolatilityScenario::Typical,
+            lift,
+        }
+    }
+
+    #[test]
+    fn test_grid_has_one_valuation_per_cell() {
+        let mut inputs = get_default_inputs();
+        let ladder: PayoutLadder = "60:0.5,120:0.5".parse().unwrap();
+        for (baseline_only, payout_ladder) in [
+            (false, None),
+            (true, None),
+            (false, Some(ladder.clone())),
+            (true, Some(ladder)),
+        ] {
+            inputs.baseline_only = baseline_only;
+            inputs.payout_ladder = payout_ladder;
+            let data = calculate_full_valuation(&inputs).unwrap();
+
+            let lifts = if baseline_only {
+                0
+            } else {
+                LiftScenario::all().len()
+            };
+            let expected =
+                inputs.payout_scenarios().len() * VolatilityScenario::all().len() * (lifts + 1);
+            assert_eq!(data.all_valuations.len(), expected);
+            let unique: std::collections::HashSet<ScenarioId> =
+                data.all_valuations.iter().map(|v| v.scenario()).collect();
+            assert_eq!(unique.len(), expected);
+            assert_eq!(scenario_count(&inputs), expected * inputs.quarters as usize);
+        }
+    }
+
+    #[test]
+    fn test_repeated_ladder_days_are_merged_with_a_warning() {
+        let mut inputs = get_default_inputs();
+        inputs.payout_ladder = Some("60:0.5,90:0.5".parse().unwrap());
+        let distinct = calculate_full_valuation(&inputs).unwrap();
+        inputs.payout_ladder = Some("60:0.25,90:0.25,60:0.25,90:0.125,90:0.125".parse().unwrap());
+        let repeated = calculate_full_valuation(&inputs).unwrap();
+
+        let ladder = repeated.inputs.payout_ladder.as_ref().unwrap();
+        assert_eq!(
+            ladder.tranches,
+            distinct.inputs.payout_ladder.as_ref().unwrap().tranches
+        );
+        assert_eq!(
+            notes(&repeated.warnings),
+            ["Payout ladder lists 60, 90 days more than once; their tranches were combined"]
+        );
+        assert_eq!(repeated.all_valuations.len(), distinct.all_valuations.len());
+        for (merged, expected) in repeated.all_valuations.iter().zip(&distinct.all_valuations) {
+            assert_relative_eq!(merged.present_value, expected.present_value, epsilon = 1e-9);
+        }
+        assert!(distinct.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_baseline_only_skips_lift_grid() {
+        let mut inputs = get_default_inputs();
+        inputs.baseline_only = true;
+
+        let report_data = calculate_full_valuation(&inputs).unwrap();
+
+        assert!(
+            report_data
+                .all_valuations
+                .iter()
+                .all(|v| v.lift_scenario.is_none())
+        );
+        assert_eq!(
+            report_data.all_valuations.len(),
+            PayoutScenario::all().len() * VolatilityScenario::all().len()
+        );
+        assert!(report_data.summary.lift_impact.is_none());
+
+        // The central estimate falls back to the baseline 90-day typical scenario.
+        let components = calculate_discount_rate(&inputs, VolatilityScenario::Typical);
+        let expected = calculate_present_value(
+            report_data.summary.adjusted_baseline,
+            components.total_rate().as_decimal(),
+            PayoutScenario::Day90.years(DayCount::Actual365).unwrap(),
+        )
+        .unwrap();
+        assert_relative_eq!(
+            report_data.summary.central_estimate,
+            expected,
+            epsilon = 0.01
+        );
+    }
+
+    #[test]
+    fn test_single_tranche_ladder_matches_day90() {
+        let ladder: PayoutLadder = "90:1.0".parse().unwrap();
+        let bullet = calculate_present_value(
+            100_000.0,
+            0.265,
+            PayoutScenario::Day90.years(DayCount::Actual365).unwrap(),
+        )
+        .unwrap();
+        let laddered = calculate_laddered_present_value(
+            100_000.0,
+            0.265,
+            &ladder,
+            DayCount::Actual365,
+            Compounding::Discrete,
+        )
+        .unwrap();
+        assert_eq!(laddered, bullet);
+
+        let mut inputs = get_default_inputs();
+        let bullet_estimate = calculate_full_valuation(&inputs)
+            .unwrap()
+            .summary
+            .central_estimate;
+        inputs.payout_ladder = Some(ladder);
+        let report_data = calculate_full_valuation(&inputs).unwrap();
+        assert_eq!(report_data.summary.central_estimate, bullet_estimate);
+        assert!(report_data.summary.payout_impact.is_none());
+    }
+
+    #[test]
+    fn test_laddered_present_value_sums_tranches() {
+        let ladder: PayoutLadder = "30:0.3,60:0.4,90:0.3".parse().unwrap();
+        let expected = calculate_present_value(30_000.0, 0.2, 30.0 / 365.0).unwrap()
+            + calculate_present_value(40_000.0, 0.2, 60.0 / 365.0).unwrap()
+            + calculate_present_value(30_000.0, 0.2, 90.0 / 365.0).unwrap();
+        let laddered = calculate_laddered_present_value(
+            100_000.0,
+            0.2,
+            &ladder,
+            DayCount::Actual365,
+            Compounding::Discrete,
+        )
+        .unwrap();
+        assert_relative_eq!(laddered, expected, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_payout_ladder_fractions_must_sum_to_one() {
+        let mut inputs = get_default_inputs();
+        inputs.payout_ladder = Some("30:0.5,60:0.4".parse().unwrap());
+        assert!(matches!(
+            calculate_full_valuation(&inputs),
+            Err(ModelError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_day_count_conventions_for_90_day_payout() {
+        let actual_365 = PayoutScenario::Day90.years(DayCount::Actual365).unwrap();
+        let actual_360 = PayoutScenario::Day90.years(DayCount::Actual360).unwrap();
+        assert_relative_eq!(actual_365, 90.0 / 365.0);
+        assert_relative_eq!(actual_360, 0.25);
+
+        // A longer year fraction under Actual/360 discounts the cash flow more heavily.
+        let pv_365 = calculate_present_value(100_000.0, 0.10, actual_365).unwrap();
+        let pv_360 = calculate_present_value(100_000.0, 0.10, actual_360).unwrap();
+        assert_relative_eq!(pv_365, 97_677.29, epsilon = 0.01);
+        assert_relative_eq!(pv_360, 97_645.41, epsilon = 0.01);
+        assert_relative_eq!(pv_365 - pv_360, 31.88, epsilon = 0.01);
+
+        // 30/360 counts 90 days as three 30-day months of a 360-day year
+        let thirty_360 = PayoutScenario::Day90.years(DayCount::Thirty360).unwrap();
+        assert_relative_eq!(thirty_360, 0.25);
+        assert!(thirty_360 > actual_365);
+    }
+
+    #[test]
+    fn test_default_day_count_preserves_output() {
+        let inputs = get_default_inputs();
+        assert_eq!(inputs.day_count, DayCount::default());
+        let mut actual_360 = inputs.clone();
+        actual_360.day_count = DayCount::Actual360;
+        let base = calculate_full_valuation(&inputs)
+            .unwrap()
+            .summary
+            .central_estimate;
+        let shorter_year = calculate_full_valuation(&actual_360)
+            .unwrap()
+            .summary
+            .central_estimate;
+        assert!(shorter_year < base);
+    }
+
+    #[test]
+    fn test_multi_quarter_sums_discounted_growing_quarters() {
+        let mut inputs = get_default_inputs();
+        inputs.baseline_only = true;
+        inputs.quarters = 4;
+        inputs.quarterly_growth_rate = 0.02;
+
+        let report_data = calculate_full_valuation(&inputs).unwrap();
+        let rate = calculate_discount_rate(&inputs, VolatilityScenario::Typical)
+            .total_rate()
+            .as_decimal();
+        let expected: f64 = (0..4)
+            .map(|q| {
+                let cash_flow = report_data.summary.adjusted_baseline * 1.02_f64.powi(q);
+                let years =
+                    PayoutScenario::Day90.years(DayCount::Actual365).unwrap() + q as f64 / 4.0;
+                calculate_present_value(cash_flow, rate, years).unwrap()
+            })
+            .sum();
+        assert_relative_eq!(
+            report_data.summary.central_estimate,
+            expected,
+            epsilon = 1e-6
+        );
+        assert!(report_data.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_exactly_one_cell_is_tagged_central() {
+        let mut inputs = get_default_inputs();
+        for baseline_only in [false, true] {
+            inputs.baseline_only = baseline_only;
+            let data = calculate_full_valuation(&inputs).unwrap();
+            let tagged = |flag: fn(&ScenarioTags) -> bool| {
+                data.all_valuations
+                    .iter()
+                    .filter(|v| flag(&v.tags))
+                    .collect::<Vec<_>>()
+            };
+
+            let central = tagged(|tags| tags.is_central);
+            assert_eq!(central.len(), 1);
+            assert_eq!(central[0].present_value, data.summary.central_estimate);
+            assert_eq!(central[0].tags.risk_bucket, RiskBucket::Base);
+            let min = tagged(|tags| tags.is_min);
+            assert_eq!(min.len(), 1);
+            assert_eq!(min[0].present_value, data.summary.min_valuation);
+            assert_eq!(min[0].tags.risk_bucket, RiskBucket::Conservative);
+            let max = tagged(|tags| tags.is_max);
+            assert_eq!(max.len(), 1);
+            assert_eq!(max[0].present_value, data.summary.max_valuation);
+            assert_eq!(max[0].tags.risk_bucket, RiskBucket::Aggressive);
+            assert_eq!(
+                tagged(|tags| tags.is_baseline).len(),
+                data.all_valuations
+                    .iter()
+                    .filter(|v| v.lift_scenario.is_none())
+                    .count()
+            );
+        }
+    }
+
+    #[test]
+    fn test_discount_curve_spans_zero_to_the_longest_payout() {
+        let curve =
+            discount_curve(0.2, 120, 50, DayCount::Actual365, Compounding::Discrete).unwrap();
+        let days: Vec<u32> = curve.iter().map(|(days, _)| *days).collect();
+        assert_eq!(days, [0, 50, 100, 120]);
+        assert_eq!(curve[0].1, 1.0);
+        assert_relative_eq!(curve[3].1, 1.2_f64.powf(-120.0 / 365.0), epsilon = 1e-12);
+        assert!(curve.windows(2).all(|pair| pair[1].1 < pair[0].1));
+        assert_eq!(
+            discount_curve(0.2, 120, 30, DayCount::Actual365, Compounding::Discrete)
+                .unwrap()
+                .len(),
+            5
+        );
+        assert!(matches!(
+            discount_curve(0.2, 120, 0, DayCount::Actual365, Compounding::Discrete),
+            Err(ModelError::InvalidInput(_))
+        ));
+
+        let mut inputs = get_default_inputs();
+        assert_eq!(longest_payout_days(&inputs), 120);
+        inputs.lift_payout_days = Some(180);
+        assert_eq!(longest_payout_days(&inputs), 180);
+    }
+
+    #[test]
+    fn test_two_stage_growth_with_equal_rates_matches_single_stage() {
+        let mut inputs = get_default_inputs();
+        inputs.quarters = 8;
+        inputs.quarterly_growth_rate = 0.02;
+        let single = calculate_full_valuation(&inputs).unwrap();
+
+        inputs.quarterly_growth_rate = 0.0;
+        inputs.two_stage_growth = Some(TwoStageGrowth {
+            stage1_quarters: 3,
+            stage1_growth: 0.02,
+            stage2_growth: 0.02,
+        });
+        let staged = calculate_full_valuation(&inputs).unwrap();
+        for (single, staged) in single.all_valuations.iter().zip(&staged.all_valuations) {
+            assert_relative_eq!(single.present_value, staged.present_value, epsilon = 1e-6);
+        }
+
+        // Slower stage-2 growth only lowers the periods after stage 1
+        inputs.two_stage_growth = Some(TwoStageGrowth {
+            stage1_quarters: 3,
+            stage1_growth: 0.02,
+            stage2_growth: 0.0,
+        });
+        let slowing = calculate_full_valuation(&inputs).unwrap();
+        assert!(slowing.summary.central_estimate < single.summary.central_estimate);
+        let flows = quarter_cash_flows(&inputs, 100.0, 0.0, 0.02).unwrap();
+        assert_relative_eq!(flows[3].to_f64(), 100.0 * 1.02_f64.powi(3), epsilon = 1e-6);
+        assert_eq!(flows[7], flows[3]);
+
+        // Staged growth must converge, so it is refused rather than clamped
+        inputs.two_stage_growth = Some(TwoStageGrowth {
+            stage1_quarters: 3,
+            stage1_growth: 0.02,
+            stage2_growth: 0.06,
+        });
+        let error = calculate_full_valuation(&inputs).unwrap_err().to_string();
+        assert!(error.contains("Stage-2 growth rate 6.00% must be below"));
+
+        // A stage that grows no period within the horizon is not checked
+        inputs.quarters = 4;
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert!(
+            notes(&data.warnings)
+                .iter()
+                .any(|w| w.contains("the stage-2 rate never applies"))
+        );
+        inputs.quarters = 1;
+        inputs.two_stage_growth = Some(TwoStageGrowth {
+            stage1_quarters: 3,
+            stage1_growth: 0.5,
+            stage2_growth: 0.5,
+        });
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert!(data.warnings.is_empty());
+
+        // The longest possible first stage never reaches stage 2 without overflowing
+        let longest = TwoStageGrowth {
+            stage1_quarters: u32::MAX,
+            stage1_growth: 0.01,
+            stage2_growth: 1.0,
+        };
+        assert!(!longest.reaches_stage2(u32::MAX));
+        assert!(!longest.reaches_stage2(0));
+        inputs.quarters = 4;
+        inputs.two_stage_growth = Some(longest);
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert!(
+            notes(&data.warnings)
+                .iter()
+                .any(|w| w.contains("the stage-2 rate never applies"))
+        );
+    }
+
+    #[test]
+    fn test_growth_above_discount_rate_is_clamped_with_warning() {
+        let mut inputs = get_default_inputs();
+        inputs.quarters = 8;
+        // Above the Low volatility per-quarter rate (~5.0%) but below Extreme (~10.5%)
+        inputs.quarterly_growth_rate = 0.06;
+
+        let report_data = calculate_full_valuation(&inputs).unwrap();
+        assert!(
+            notes(&report_data.warnings)
+                .iter()
+                .any(|w| w.contains(&VolatilityScenario::Low.to_string()))
+        );
+        assert!(
+            !notes(&report_data.warnings)
+                .iter()
+                .any(|w| w.contains(&VolatilityScenario::Extreme.to_string()))
+        );
+
+        // Clamped growth keeps every later quarter worth less than the first
+        let low_rate = calculate_discount_rate(&inputs, VolatilityScenario::Low)
+            .total_rate()
+            .as_decimal();
+        let first_quarter = calculate_present_value(
+            report_data.summary.adjusted_baseline,
+            low_rate,
+            PayoutScenario::Day90.years(DayCount::Actual365).unwrap(),
+        )
+        .unwrap();
+        let low_baseline = report_data
+            .all_valuations
+            .iter()
+            .find(|v| {
+                v.payout_scenario == PayoutScenario::Day90
+                    && v.volatility_scenario == VolatilityScenario::Low
+                    && v.lift_scenario.is_none()
+            })
+            .unwrap();
+        assert!(low_baseline.present_value < first_quarter * 8.0);
+    }
+
+    #[test]
+    fn test_cap_at_face_binds_under_negative_rate() {
+        let mut inputs = get_default_inputs();
+        inputs.discount_rate_overrides = Some(vec![Rate::from_decimal(-0.05).unwrap(); 4]);
+        let uncapped = calculate_full_valuation(&inputs).unwrap();
+        assert!(
+            uncapped
+                .all_valuations
+                .iter()
+                .all(|v| v.present_value > v.face_value)
+        );
+
+        inputs.cap_at_face = true;
+        let data = calculate_full_valuation(&inputs).unwrap();
+        for valuation in &data.all_valuations {
+            assert_eq!(valuation.present_value, valuation.face_value);
+        }
+        let warning = notes(&data.warnings)
+            .into_iter()
+            .find(|w| w.starts_with("Present value exceeds face value"))
+            .unwrap();
+        assert!(warning.contains("90 Days / Typical / Medium"));
+        assert!(warning.ends_with("capped at face value"));
+
+        // Positive rates leave nothing to cap
+        inputs.discount_rate_overrides = None;
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert!(
+            notes(&data.warnings)
+                .iter()
+                .all(|w| !w.contains("face value"))
+        );
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_growth_above_discount_rate() {
+        let mut inputs = get_default_inputs();
+        inputs.quarters = 8;
+        inputs.quarterly_growth_rate = 0.06;
+        inputs.strict = true;
+
+        match calculate_full_valuation(&inputs) {
+            Err(ModelError::InvalidInput(message)) => assert!(message.contains("Low Vol")),
+            other => panic!(
+                "expected growth guard to trigger, got {:?}",
+                other.map(|_| ())
+            ),
+        }
+    }
+
+    #[test]
+    fn test_retention_ratio_matches_discount_factor() {
+        let inputs = get_default_inputs();
+        let report_data = calculate_full_valuation(&inputs).unwrap();
+        let rate = calculate_discount_rate(&inputs, VolatilityScenario::Extreme)
+            .total_rate()
+            .as_decimal();
+        let result = report_data
+            .all_valuations
+            .iter()
+            .find(|v| {
+                v.payout_scenario == PayoutScenario::Day120
+                    && v.volatility_scenario == VolatilityScenario::Extreme
+                    && v.lift_scenario.is_none()
+            })
+            .unwrap();
+
+        let expected = 1.0 / (1.0 + rate).powf(120.0 / 365.0);
+        assert_relative_eq!(
+            result.retention_ratio(report_data.summary.adjusted_baseline),
+            expected,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_extreme_scenario_ties_prefer_earliest_scenario() {
+        let result = |payout, volatility, lift, present_value| ValuationResult {
+            present_value,
+            pre_tax_present_value: present_value,
+            after_tax_present_value: present_value,
+            face_value: 0.0,
+            position_value: None,
+            display_value: None,
+            floor_top_up: 0.0,
+            payout_scenario: payout,
+            volatility_scenario: volatility,
+            lift_scenario: lift,
+            tags: ScenarioTags::default(),
+        };
+        // Listed out of tie-break order so iteration order cannot decide the winner
+        let valuations = vec![
+            result(PayoutScenario::Day120, VolatilityScenario::Low, None, 100.0),
+            result(
+                PayoutScenario::Day60,
+                VolatilityScenario::High,
+                Some(LiftScenario::Low),
+                100.0,
+            ),
+            result(PayoutScenario::Day60, VolatilityScenario::High, None, 100.0),
+            result(PayoutScenario::Day90, VolatilityScenario::Low, None, 50.0),
+            result(
+                PayoutScenario::Day90,
+                VolatilityScenario::Typical,
+                None,
+                50.0,
+            ),
+        ];
+
+        let (min, max) = select_extreme_scenarios(&valuations).unwrap();
+        assert_eq!(
+            min.scenario(),
+            ScenarioId {
+                payout: PayoutScenario::Day90,
+                volatility: VolatilityScenario::Low,
+                lift: None,
+            }
+        );
+        assert_eq!(
+            max.scenario(),
+            ScenarioId {
+                payout: PayoutScenario::Day60,
+                volatility: VolatilityScenario::High,
+                lift: None,
+            }
+        );
+
+        let mut reversed = valuations.clone();
+        reversed.reverse();
+        let (min_rev, max_rev) = select_extreme_scenarios(&reversed).unwrap();
+        assert_eq!(min_rev.scenario(), min.scenario());
+        assert_eq!(max_rev.scenario(), max.scenario());
+    }
+
+    #[test]
+    fn test_max_scenarios_guard() {
+        let mut inputs = get_default_inputs();
+        inputs.quarters = 8;
+        // 3 payouts x 4 volatilities x (baseline + 3 lifts) x 8 quarters
+        assert_eq!(scenario_count(&inputs), 384);
+
+        inputs.max_scenarios = 383;
+        match calculate_full_valuation(&inputs) {
+            Err(ModelError::InvalidInput(message)) => {
+                assert!(message.contains("384"));
+                assert!(message.contains("383"));
+            }
+            other => panic!("expected scenario limit error, got {:?}", other.map(|_| ())),
+        }
+
+        inputs.max_scenarios = 384;
+        assert!(calculate_full_valuation(&inputs).is_ok());
+    }
+
+    #[test]
+    fn test_discount_rate_overrides_used_verbatim() {
+        let mut inputs = get_default_inputs();
+        inputs.discount_rate_overrides = Some(vec![
+            Rate::constant(0.20),
+            Rate::constant(0.26),
+            Rate::constant(0.36),
+            Rate::constant(0.47),
+        ]);
+        let components = calculate_discount_rate(&inputs, VolatilityScenario::High);
+        assert_eq!(components.total_rate().as_decimal(), 0.36);
+
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let extreme = data
+            .all_valuations
+            .iter()
+            .find(|v| {
+                v.payout_scenario == PayoutScenario::Day90
+                    && v.volatility_scenario == VolatilityScenario::Extreme
+                    && v.lift_scenario.is_none()
+            })
+            .unwrap();
+        let expected = calculate_present_value(
+            data.summary.adjusted_baseline,
+            0.47,
+            PayoutScenario::Day90.years(DayCount::Actual365).unwrap(),
+        )
+        .unwrap();
+        assert_relative_eq!(extreme.present_value, expected, epsilon = 1e-6);
+
+        inputs.discount_rate_overrides = Some(vec![Rate::constant(0.20), Rate::constant(0.26)]);
+        assert!(matches!(
+            validate_inputs(&inputs),
+            Err(ModelError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_forecast_confidence_spreads_premiums_around_typical() {
+        let premiums = |confidence: ForecastConfidence| {
+            let mut inputs = get_default_inputs();
+            inputs.forecast_confidence = confidence;
+            let data = calculate_full_valuation(&inputs).unwrap();
+            let premiums: Vec<f64> = VolatilityScenario::all()
+                .iter()
+                .map(|v| data.discount_rates[v].volatility_premium.as_percent())
+                .collect();
+            (
+                premiums,
+                data.summary.max_valuation - data.summary.min_valuation,
+            )
+        };
+        let (medium, medium_range) = premiums(ForecastConfidence::Medium);
+        let (high, high_range) = premiums(ForecastConfidence::High);
+        let (low, low_range) = premiums(ForecastConfidence::Low);
+
+        assert_eq!(medium, [5.0, 10.0, 20.0, 30.0]);
+        for (actual, expected) in high.iter().zip([7.5, 10.0, 15.0, 20.0]) {
+            assert_relative_eq!(*actual, expected, epsilon = 1e-9);
+        }
+        for (actual, expected) in low.iter().zip([2.5, 10.0, 25.0, 40.0]) {
+            assert_relative_eq!(*actual, expected, epsilon = 1e-9);
+        }
+        assert!(high_range < medium_range && medium_range < low_range);
+    }
+
+    #[test]
+    fn test_rate_formula_replaces_component_sum() {
+        let mut inputs = get_default_inputs();
+        inputs.rate_formula = Some("rf + 0.5*vol_premium + platform_premium".parse().unwrap());
+        let data = calculate_full_valuation(&inputs).unwrap();
+        for volatility in VolatilityScenario::all() {
+            let components = &data.discount_rates[volatility];
+            assert_relative_eq!(
+                components.total_rate().as_decimal(),
+                inputs.risk_free_rate.as_decimal()
+                    + 0.5 * volatility.premium().as_decimal()
+                    + inputs.platform_risk_premium.as_decimal(),
+                epsilon = 1e-12
+            );
+        }
+        let tree = build_derivation_tree(&data);
+        let (_, typical) = tree
+            .discount_rates
+            .iter()
+            .find(|(volatility, _)| *volatility == VolatilityScenario::Typical)
+            .unwrap();
+        assert_eq!(
+            typical.formula.as_deref(),
+            Some("rf + 0.5*vol_premium + platform_premium")
+        );
+
+        inputs.rate_formula = Some("rf - 2".parse().unwrap());
+        let message = validate_inputs(&inputs).unwrap_err().to_string();
+        assert!(
+            message.contains("rate formula 'rf - 2' is unusable"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_continuous_compounding_discounts_more() {
+        let discrete =
+            calculate_compounded_present_value(100_000.0, 0.265, 1.0, Compounding::Discrete)
+                .unwrap();
+        let continuous =
+            calculate_compounded_present_value(100_000.0, 0.265, 1.0, Compounding::Continuous)
+                .unwrap();
+        assert_relative_eq!(discrete, 100_000.0 / 1.265, epsilon = 1e-6);
+        assert_relative_eq!(continuous, 100_000.0 * (-0.265f64).exp(), epsilon = 1e-6);
+
+        let comparison = compare_compounding_methods(&get_default_inputs()).unwrap();
+        assert_eq!(comparison.len(), 48);
+        assert!(comparison.iter().all(|row| row.gap_percentage() < 0.0));
+    }
+
+    #[test]
+    fn test_missing_scenario_error_names_the_combination() {
+        let inputs = get_default_inputs();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let central = reference_scenario(Some(LiftScenario::Medium));
+        let valuations: Vec<ValuationResult> = data
+            .all_valuations
+            .into_iter()
+            .filter(|v| v.scenario() != central)
+            .collect();
+
+        let err =
+            calculate_summary_statistics(&valuations, data.summary.adjusted_baseline, &inputs)
+                .unwrap_err();
+        assert!(matches!(err, ModelError::ScenarioNotFound(s) if s == central));
+        assert_eq!(
+            err.to_string(),
+            "Calculation error: no valuation for scenario 90 Days / Typical / Medium Lift"
+        );
+    }
+
+    #[test]
+    fn test_weighted_percentile_and_value_at_risk() {
+        let outcomes = [(10.0, 0.25), (40.0, 0.25), (20.0, 0.25), (30.0, 0.25)];
+        assert_eq!(weighted_percentile(&outcomes, 0.05), Some(10.0));
+        assert_eq!(weighted_percentile(&outcomes, 0.5), Some(20.0));
+        assert_eq!(weighted_percentile(&outcomes, 1.0), Some(40.0));
+        assert_eq!(weighted_percentile(&[], 0.5), None);
+
+        // 36 equally weighted lifted cells: the 5th percentile is the 2nd lowest
+        let data = calculate_full_valuation(&get_default_inputs()).unwrap();
+        let mut lifted: Vec<f64> = data
+            .all_valuations
+            .iter()
+            .filter(|v| v.lift_scenario.is_some())
+            .map(|v| v.present_value)
+            .collect();
+        lifted.sort_by(f64::total_cmp);
+        assert_relative_eq!(
+            value_at_risk(&data, 0.95),
+            data.summary.central_estimate - lifted[1],
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_central_likelihood_follows_scenario_weights() {
+        // Equal weights: the central cell is one of 36 lifted cells
+        let data = calculate_full_valuation(&get_default_inputs()).unwrap();
+        let likelihood = central_likelihood(&data);
+        assert_relative_eq!(likelihood.central_probability, 1.0 / 36.0, epsilon = 1e-12);
+
+        // Independent dimensions multiply: 2/4 * 4/8 * 2/4 for 90 Days / Typical / Medium
+        let mut inputs = get_default_inputs();
+        inputs.scenario_weights = ScenarioWeights {
+            payout: Some(vec![1.0, 2.0, 1.0]),
+            volatility: Some(vec![1.0, 4.0, 2.0, 1.0]),
+            lift: Some(vec![1.0, 2.0, 1.0]),
+        };
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let likelihood = central_likelihood(&data);
+        assert_relative_eq!(likelihood.central_probability, 0.125, epsilon = 1e-12);
+        let outcomes = scenario_outcomes(&data);
+        assert_relative_eq!(
+            outcomes.iter().map(|(_, p)| p).sum::<f64>(),
+            1.0,
+            epsilon = 1e-12
+        );
+        let below: f64 = outcomes
+            .iter()
+            .filter(|(value, _)| *value <= data.summary.central_estimate)
+            .map(|(_, p)| p)
+            .sum();
+        assert_relative_eq!(likelihood.at_or_below_central, below, epsilon = 1e-12);
+
+        inputs.scenario_weights.lift = Some(vec![1.0, 2.0]);
+        assert!(validate_inputs(&inputs).is_err());
+        inputs.scenario_weights.lift = Some(vec![0.0, 0.0, 0.0]);
+        assert!(validate_inputs(&inputs).is_err());
+    }
+
+    #[test]
+    fn test_validate_inputs_rejects_negative_lift_drivers() {
+        let mut inputs = get_default_inputs();
+        inputs.incremental_rpm = -1.0;
+        assert!(matches!(
+            validate_inputs(&inputs),
+            Err(ModelError::InvalidInput(_))
+        ));
+
+        let mut inputs = get_default_inputs();
+        inputs.lift_per_investor = -1.0;
+        assert!(matches!(
+            validate_inputs(&inputs),
+            Err(ModelError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_reinvestment_snowballs_lift_revenue() {
+        let mut inputs = get_default_inputs();
+        inputs.quarters = 4;
+
+        // Zero reinvestment reproduces the linear multi-quarter cash flows
+        let linear = quarter_cash_flows(&inputs, 200_000.0, 5_000.0, 0.0).unwrap();
+        assert!(linear.iter().all(|cf| cf.to_f64() == 205_000.0));
+        let baseline_pv = calculate_full_valuation(&inputs)
+            .unwrap()
+            .summary
+            .central_estimate;
+
+        inputs.reinvestment_rate = 0.5;
+        let snowball = quarter_cash_flows(&inputs, 200_000.0, 5_000.0, 0.0).unwrap();
+        assert_relative_eq!(snowball[0].to_f64(), 205_000.0, epsilon = 1e-6);
+        assert_relative_eq!(
+            snowball[3].to_f64(),
+            200_000.0 + 5_000.0 * 1.5f64.powi(3),
+            epsilon = 1e-6
+        );
+        assert!(snowball.windows(2).all(|pair| pair[1] > pair[0]));
+
+        let reinvested_pv = calculate_full_valuation(&inputs)
+            .unwrap()
+            .summary
+            .central_estimate;
+        assert!(reinvested_pv > baseline_pv);
+    }
+
+    #[test]
+    fn test_later_lift_payout_shrinks_lift_contribution() {
+        let inputs = get_default_inputs();
+        let mut delayed = get_default_inputs();
+        delayed.lift_payout_days = Some(120);
+
+        let lift_contribution = |inputs: &ValuationInputs| {
+            let data = calculate_full_valuation(inputs).unwrap();
+            let value = |lift| {
+                data.all_valuations
+                    .iter()
+                    .find(|v| v.scenario() == reference_scenario(lift))
+                    .unwrap()
+                    .present_value
+            };
+            value(Some(LiftScenario::Medium)) - value(None)
+        };
+
+        // Lift settles 30 days after the 90-day baseline payout
+        let lift = LiftScenario::Medium.period_lift(
+            1000.0,
+            10.0,
+            15.0,
+            LiftRamp::Instant,
+            RevenuePeriod::Quarterly,
+        );
+        let rate: f64 = 0.265;
+        assert_relative_eq!(
+            lift_contribution(&inputs),
+            lift / (1.0 + rate).powf(90.0 / 365.0),
+            epsilon = 1e-3
+        );
+        assert_relative_eq!(
+            lift_contribution(&delayed),
+            lift / (1.0 + rate).powf(120.0 / 365.0),
+            epsilon = 1e-3
+        );
+        assert!(lift_contribution(&delayed) < lift_contribution(&inputs));
+    }
+
+    #[test]
+    fn test_implied_revenue_multiples() {
+        let data = calculate_full_valuation(&get_default_inputs()).unwrap();
+        let summary = &data.summary;
+        assert_relative_eq!(
+            summary.quarterly_revenue_multiple.unwrap(),
+            summary.central_estimate / summary.adjusted_baseline
+        );
+        assert_relative_eq!(
+            summary.annual_revenue_multiple.unwrap(),
+            summary.quarterly_revenue_multiple.unwrap() / 4.0
+        );
+        assert_eq!(revenue_multiple(1.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_face_value_is_undiscounted_cash_flow() {
+        let inputs = get_default_inputs();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let lift = LiftScenario::Medium.period_lift(
+            inputs.investor_count,
+            inputs.lift_per_investor,
+            inputs.incremental_rpm,
+            inputs.lift_ramp,
+            RevenuePeriod::Quarterly,
+        );
+        let central = data
+            .all_valuations
+            .iter()
+            .find(|v| v.scenario() == reference_scenario(Some(LiftScenario::Medium)))
+            .unwrap();
+
+        assert_relative_eq!(
+            central.face_value,
+            data.summary.adjusted_baseline + lift,
+            epsilon = 1e-6
+        );
+        assert!(central.present_value < central.face_value);
+        assert_relative_eq!(data.summary.central_face_value, central.face_value);
+    }
+
+    #[test]
+    fn test_horizon_sums_reconcile_to_the_micro_unit() {
+        // Ten quarters of 0.1 drift when summed as f64 but not as Money
+        let mut inputs = get_default_inputs();
+        inputs.raw_forecast = 0.1;
+        inputs.platform_adjustment_factor = 0.0;
+        inputs.baseline_only = true;
+        inputs.quarters = 10;
+        assert_ne!(std::iter::repeat_n(0.1_f64, 10).sum::<f64>(), 1.0);
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert!(data.all_valuations.iter().all(|v| v.face_value == 1.0));
+        assert_eq!(horizon_cash_flow(&inputs, 0.1, 0.0).unwrap().to_f64(), 1.0);
+
+        // A snowballing lift: the face value is exactly the sum of the flows
+        let mut inputs = get_default_inputs();
+        inputs.quarters = 6;
+        inputs.reinvestment_rate = 0.25;
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let scenario = reference_scenario(Some(LiftScenario::Medium));
+        let central = data
+            .all_valuations
+            .iter()
+            .find(|v| v.scenario() == scenario)
+            .unwrap();
+        let flows = quarter_cash_flows(
+            &inputs,
+            data.summary.adjusted_baseline,
+            data.lift_assumptions.period_lift(LiftScenario::Medium),
+            data.growth_rates[&scenario.volatility],
+        )
+        .unwrap();
+        assert_eq!(
+            central.face_value,
+            Money::checked_sum(flows).unwrap().to_f64()
+        );
+
+        // Amounts that are not finite are refused rather than read as zero
+        assert!(matches!(
+            quarter_cash_flows(&inputs, f64::NAN, 0.0, 0.0),
+            Err(ModelError::CalculationError(message)) if message.contains("not a finite amount")
+        ));
+
+        // Amounts too large to hold are refused rather than overflowing
+        inputs.raw_forecast = 1e32;
+        inputs.quarters = 4;
+        assert!(matches!(
+            calculate_full_valuation(&inputs),
+            Err(ModelError::CalculationError(message)) if message.contains("too large to value")
+        ));
+    }
+
+    #[test]
+    fn test_requested_percentiles() {
+        assert!(validate_percentiles(&[1.0, 5.0, 50.0, 95.0, 99.0]).is_ok());
+        assert!(validate_percentiles(&[0.0, 50.0]).is_err());
+        assert!(validate_percentiles(&[50.0, 100.0]).is_err());
+        assert!(validate_percentiles(&[50.0, 5.0]).is_err());
+        assert!(validate_percentiles(&[5.0, 5.0]).is_err());
+
+        let data = calculate_full_valuation(&get_default_inputs()).unwrap();
+        let rows = scenario_percentiles(&data, &[1.0, 50.0, 99.0]);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[2].1, data.summary.max_valuation);
+        assert!(rows[0].1 <= rows[1].1 && rows[1].1 <= rows[2].1);
+    }
+
+    #[test]
+    fn test_supplied_payout_days_are_range_checked() {
+        let mut inputs = get_default_inputs();
+        inputs.lift_payout_days = Some(100_000);
+        assert!(matches!(
+            validate_inputs(&inputs),
+            Err(ModelError::InvalidInput(msg)) if msg.contains("100000 days")
+        ));
+
+        inputs.lift_payout_days = None;
+        inputs.payout_ladder = Some("0:0.5,90:0.5".parse().unwrap());
+        assert!(validate_inputs(&inputs).is_err());
+
+        inputs.payout_ladder = Some("90:0.5,400:0.5".parse().unwrap());
+        assert!(validate_inputs(&inputs).is_ok());
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert_eq!(data.warnings.len(), 1);
+        assert!(notes(&data.warnings)[0].contains("400 days exceeds one year"));
+    }
+
+    #[test]
+    fn test_validate_reports_every_violation() {
+        let mut inputs = get_default_inputs();
+        assert!(inputs.validate().is_ok());
+
+        inputs.raw_forecast = 0.0;
+        inputs.rpm = -1.0;
+        inputs.quarters = 0;
+        let errors = inputs.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+
+        let error = validate_inputs(&inputs).unwrap_err();
+        assert!(matches!(&error, ModelError::Validation(errors) if errors.len() == 3));
+        let message = error.to_string();
+        assert!(message.contains("Raw forecast must be positive"));
+        assert!(message.contains("RPM must be positive"));
+        assert!(message.contains("at least one quarter"));
+    }
+
+    #[test]
+    fn test_lift_option_is_lifted_value_less_baseline() {
+        let mut inputs = get_default_inputs();
+        inputs.quarters = 4;
+        inputs.quarterly_growth_rate = 0.02;
+        inputs.lift_payout_days = Some(30);
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let options = value_lift_only(&data).unwrap();
+        assert_eq!(options.len(), 3 * 3 * 4);
+
+        let present_value = |scenario| {
+            data.all_valuations
+                .iter()
+                .find(|v| v.scenario() == scenario)
+                .unwrap()
+                .present_value
+        };
+        for option in &options {
+            let baseline = ScenarioId {
+                lift: None,
+                ..option.scenario
+            };
+            assert_relative_eq!(
+                option.present_value,
+                present_value(option.scenario) - present_value(baseline),
+                max_relative = 1e-9
+            );
+        }
+    }
+
+    #[test]
+    fn test_downside_and_upside_are_investable_corners() {
+        let mut inputs = get_default_inputs();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let value = |payout, volatility, lift| {
+            data.all_valuations
+                .iter()
+                .find(|v| {
+                    v.scenario()
+                        == ScenarioId {
+                            payout,
+                            volatility,
+                            lift,
+                        }
+                })
+                .unwrap()
+                .present_value
+        };
+        let summary = &data.summary;
+        assert_eq!(
+            summary.downside,
+            value(
+                PayoutScenario::Day120,
+                VolatilityScenario::Extreme,
+                Some(LiftScenario::Low)
+            )
+        );
+        assert_eq!(
+            summary.upside,
+            value(
+                PayoutScenario::Day60,
+                VolatilityScenario::Low,
+                Some(LiftScenario::High)
+            )
+        );
+        assert!(summary.min_valuation <= summary.downside);
+        assert!(summary.downside < summary.central_estimate);
+        assert!(summary.central_estimate < summary.upside);
+        assert!(summary.upside <= summary.max_valuation);
+
+        inputs.baseline_only = true;
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert_eq!(data.summary.downside, data.summary.min_valuation);
+        assert_eq!(data.summary.upside, data.summary.max_valuation);
+    }
+
+    #[test]
+    fn test_pct_change_is_relative_to_the_starting_value() {
+        assert_eq!(pct_change(200.0, 150.0), Some(-25.0));
+        assert_relative_eq!(
+            pct_change(150.0, 200.0).unwrap(),
+            100.0 / 3.0,
+            epsilon = 1e-12
+        );
+        assert_eq!(pct_change(-100.0, -50.0), Some(50.0));
+        assert_eq!(pct_change(-100.0, -150.0), Some(-50.0));
+        assert_eq!(pct_change(0.0, 0.0), None);
+        assert_eq!(pct_change(0.0, 100.0), None);
+
+        let data = calculate_full_valuation(&get_default_inputs()).unwrap();
+        let value = |payout, volatility, lift| {
+            data.all_valuations
+                .iter()
+                .find(|v| {
+                    v.scenario()
+                        == ScenarioId {
+                            payout,
+                            volatility,
+                            lift,
+                        }
+                })
+                .unwrap()
+                .present_value
+        };
+        let medium = Some(LiftScenario::Medium);
+        let day90 = PayoutScenario::Day90;
+        assert_eq!(
+            data.summary.volatility_impact,
+            pct_change(
+                value(day90, VolatilityScenario::Low, medium),
+                value(day90, VolatilityScenario::Extreme, medium)
+            )
+            .map(|change| -change)
+        );
+        assert_eq!(
+            data.summary.lift_impact,
+            pct_change(
+                value(day90, VolatilityScenario::Typical, Some(LiftScenario::Low)),
+                value(day90, VolatilityScenario::Typical, Some(LiftScenario::High))
+            )
+        );
+    }
+
+    #[test]
+    fn test_activation_factors_increase_with_lift() {
+        // The report describes Low to High lift as an increase in value
+        let factors: Vec<f64> = LiftScenario::all()
+            .iter()
+            .map(LiftScenario::activation_factor)
+            .collect();
+        assert!(factors.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let data = calculate_full_valuation(&get_default_inputs()).unwrap();
+        assert!(data.summary.lift_impact.unwrap() > 0.0);
+        for payout in PayoutScenario::all() {
+            for volatility in VolatilityScenario::all() {
+                let values: Vec<f64> = LiftScenario::all()
+                    .iter()
+                    .map(|lift| {
+                        data.all_valuations
+                            .iter()
+                            .find(|v| {
+                                v.payout_scenario == *payout
+                                    && v.volatility_scenario == *volatility
+                                    && v.lift_scenario == Some(*lift)
+                            })
+                            .unwrap()
+                            .present_value
+                    })
+                    .collect();
+                assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_payout_steps_are_not_half_the_total() {
+        let data = calculate_full_valuation(&get_default_inputs()).unwrap();
+        let summary = &data.summary;
+        let total = summary.payout_impact.unwrap();
+        let first = summary.payout_impact_60_90.unwrap();
+        let second = summary.payout_impact_90_120.unwrap();
+
+        // The steps compound to the 60-to-120 impact rather than adding to it
+        assert_relative_eq!(
+            (1.0 - first / 100.0) * (1.0 - second / 100.0),
+            1.0 - total / 100.0,
+            epsilon = 1e-12
+        );
+        assert!((first - total / 2.0).abs() > 1e-6);
+        assert!((second - total / 2.0).abs() > 1e-6);
+        assert!(first + second > total);
+
+        let mut inputs = get_default_inputs();
+        inputs.payout_ladder = Some("30:0.5,90:0.5".parse().unwrap());
+        let summary = calculate_full_valuation(&inputs).unwrap().summary;
+        assert_eq!(summary.payout_impact_60_90, None);
+        assert_eq!(summary.payout_impact_90_120, None);
+    }
+
+    #[test]
+    fn test_scenario_agreement_counts_scenarios_near_central() {
+        let mut inputs = get_default_inputs();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert_relative_eq!(data.summary.scenario_agreement, 1.0, epsilon = 1e-12);
+
+        // Rates far apart spread the volatility scenarios beyond the band
+        inputs.discount_rate_overrides = Some(
+            [0.1, 0.15, 2.0, 4.0]
+                .into_iter()
+                .map(|rate| Rate::from_decimal(rate).unwrap())
+                .collect(),
+        );
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let central = data.summary.central_estimate;
+        let agrees =
+            |v: &ValuationResult| (v.present_value / central - 1.0).abs() <= AGREEMENT_BAND;
+        // Only the lifted cells make up the distribution
+        let lifted: Vec<&ValuationResult> = data
+            .all_valuations
+            .iter()
+            .filter(|v| v.lift_scenario.is_some())
+            .collect();
+        let agreeing = lifted.iter().filter(|v| agrees(v)).count();
+        assert!(agreeing > 0 && agreeing < lifted.len());
+        assert_relative_eq!(
+            data.summary.scenario_agreement,
+            agreeing as f64 / lifted.len() as f64
+        );
+
+        // Weights shift the agreement toward the likelier cells
+        inputs.scenario_weights.volatility = Some(vec![1.0, 1.0, 0.0, 0.0]);
+        let weighted = calculate_full_valuation(&inputs).unwrap();
+        let expected: f64 = weighted_cells(&weighted)
+            .into_iter()
+            .filter(|(v, _)| agrees(v))
+            .map(|(_, p)| p)
+            .sum();
+        assert_relative_eq!(weighted.summary.scenario_agreement, expected);
+        assert!(weighted.summary.scenario_agreement != data.summary.scenario_agreement);
+    }
+
+    #[test]
+    fn test_value_ratio_against_purchase_price() {
+        let mut inputs = get_default_inputs();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert_eq!(data.summary.value_ratio, None);
+
+        inputs.purchase_price = Some(150_000.0);
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert_relative_eq!(
+            data.summary.value_ratio.unwrap(),
+            data.summary.central_estimate / 150_000.0
+        );
+
+        for price in [0.0, -5.0] {
+            inputs.purchase_price = Some(price);
+            assert!(matches!(
+                validate_inputs(&inputs),
+                Err(ModelError::InvalidInput(msg)) if msg.contains("Purchase price")
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parallel_grid_matches_sequential() {
+        let mut inputs = get_default_inputs();
+        inputs.quarters = 4;
+        let sequential = calculate_full_valuation(&inputs).unwrap();
+
+        for threads in [2, 3, 8, 1000] {
+            inputs.threads = Some(threads);
+            let parallel = calculate_full_valuation(&inputs).unwrap();
+            assert_eq!(
+                parallel.all_valuations.len(),
+                sequential.all_valuations.len()
+            );
+            for (a, b) in parallel
+                .all_valuations
+                .iter()
+                .zip(&sequential.all_valuations)
+            {
+                assert_eq!(a.scenario(), b.scenario());
+                assert_eq!(a.present_value, b.present_value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_position_values_scale_with_ownership() {
+        let mut inputs = get_default_inputs();
+        inputs.ownership_fraction = Some(0.025);
+        let data = calculate_full_valuation(&inputs).unwrap();
+        for v in &data.all_valuations {
+            assert_relative_eq!(v.position_value.unwrap(), v.present_value * 0.025);
+        }
+        assert_relative_eq!(
+            data.summary.position_value.unwrap(),
+            data.summary.central_estimate * 0.025
+        );
+
+        for share in [0.0, 1.5] {
+            inputs.ownership_fraction = Some(share);
+            assert!(validate_inputs(&inputs).is_err());
+        }
+        inputs.ownership_fraction = Some(1.0);
+        assert!(validate_inputs(&inputs).is_ok());
+    }
+
+    #[test]
+    fn test_warns_when_lift_dominates_baseline() {
+        let mut inputs = get_default_inputs();
+        assert!(
+            calculate_full_valuation(&inputs)
+                .unwrap()
+                .warnings
+                .is_empty()
+        );
+
+        // 100,000 investors x 10 x $15 RPM x 3 months = $45,000 medium lift
+        inputs.raw_forecast = 40_000.0;
+        inputs.platform_adjustment_factor = 0.0;
+        inputs.investor_count = 100_000.0;
+        let warnings = calculate_full_valuation(&inputs).unwrap().warnings;
+        assert_eq!(warnings.len(), 1);
+        let ModelWarning::LiftDominance { lifts, .. } = &warnings[0] else {
+            panic!("expected a lift dominance warning, got {:?}", warnings[0]);
+        };
+        assert_eq!(
+            lifts.iter().map(|(lift, _)| *lift).collect::<Vec<_>>(),
+            [LiftScenario::Medium, LiftScenario::High]
+        );
+        assert_relative_eq!(lifts[0].1, 45_000.0, epsilon = 1e-6);
+        assert_relative_eq!(lifts[1].1, 67_500.0, epsilon = 1e-6);
+
+        inputs.lift_dominance_threshold = 2.0;
+        assert!(
+            calculate_full_valuation(&inputs)
+                .unwrap()
+                .warnings
+                .is_empty()
+        );
+
+        // A threshold that would always or never warn is refused
+        for threshold in [0.0, -1.0, f64::NAN] {
+            inputs.lift_dominance_threshold = threshold;
+            assert!(validate_inputs(&inputs).is_err());
+        }
+    }
+
+    #[test]
+    fn test_tax_rate_scales_present_values() {
+        let mut inputs = get_default_inputs();
+        let pre_tax = calculate_full_valuation(&inputs).unwrap();
+        inputs.tax_rate = 0.25;
+        let taxed = calculate_full_valuation(&inputs).unwrap();
+
+        // Pre-tax remains the headline until --after-tax is chosen
+        for (before, after) in pre_tax.all_valuations.iter().zip(&taxed.all_valuations) {
+            assert_eq!(after.present_value, before.present_value);
+            assert_relative_eq!(after.after_tax_present_value, before.present_value * 0.75);
+        }
+
+        inputs.after_tax = true;
+        let after_tax = calculate_full_valuation(&inputs).unwrap();
+        for (before, after) in pre_tax.all_valuations.iter().zip(&after_tax.all_valuations) {
+            assert_relative_eq!(after.present_value, before.present_value * 0.75);
+            assert_relative_eq!(after.pre_tax_present_value, before.present_value);
+        }
+        assert_relative_eq!(
+            after_tax.summary.central_estimate,
+            pre_tax.summary.central_estimate * 0.75
+        );
+
+        inputs.tax_rate = 1.0;
+        assert!(validate_inputs(&inputs).is_err());
+    }
+
+    #[test]
+    fn test_linear_lift_ramp_earns_less_than_instant() {
+        let instant = LiftScenario::Medium.period_lift(
+            1000.0,
+            10.0,
+            15.0,
+            LiftRamp::Instant,
+            RevenuePeriod::Quarterly,
+        );
+        let linear = LiftScenario::Medium.period_lift(
+            1000.0,
+            10.0,
+            15.0,
+            LiftRamp::Linear,
+            RevenuePeriod::Quarterly,
+        );
+        // Same end-state audience, but only two of three months' revenue
+        assert!(linear < instant);
+        assert_relative_eq!(linear, instant * 2.0 / 3.0);
+
+        let mut inputs = get_default_inputs();
+        let instant_data = calculate_full_valuation(&inputs).unwrap();
+        inputs.lift_ramp = LiftRamp::Linear;
+        let linear_data = calculate_full_valuation(&inputs).unwrap();
+        assert!(linear_data.summary.central_estimate < instant_data.summary.central_estimate);
+    }
+
+    #[test]
+    fn test_derivation_tree_traces_every_scenario() {
+        let mut inputs = get_default_inputs();
+        inputs.quarters = 2;
+        inputs.ownership_fraction = Some(0.1);
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let tree = build_derivation_tree(&data);
+
+        assert_eq!(tree.scenarios.len(), data.all_valuations.len());
+        assert_relative_eq!(tree.adjusted_baseline.value, data.summary.adjusted_baseline);
+        for ((scenario, root), valuation) in tree.scenarios.iter().zip(&data.all_valuations) {
+            assert_eq!(*scenario, valuation.scenario());
+            assert_eq!(root.name, "position_value");
+            let present_value = &root.inputs[0];
+            let (cash_flow, discount_factor) = (&present_value.inputs[0], &present_value.inputs[1]);
+            assert_relative_eq!(present_value.value, valuation.present_value);
+            assert_relative_eq!(cash_flow.value, valuation.face_value);
+            assert_relative_eq!(
+                cash_flow.value * discount_factor.value,
+                present_value.value,
+                max_relative = 1e-12
+            );
+            let rate = &discount_factor.inputs[0];
+            assert_relative_eq!(
+                rate.value,
+                data.discount_rates[&valuation.volatility_scenario]
+                    .total_rate()
+                    .as_decimal()
+            );
+            assert_eq!(rate.inputs.len(), 3);
+        }
+
+        let json = crate::json::JsonValue::from(&tree).to_string();
+        assert!(crate::json::JsonValue::parse(&json).is_ok());
+    }
+
+    #[test]
+    fn test_rate_matrix_sets_each_cell_rate() {
+        let mut inputs = get_default_inputs();
+        inputs.rate_matrix = Some(
+            crate::rate_matrix::parse_rate_matrix(
+                "payout,low,typical,high,extreme\n\
+                 60,0.20,0.26,0.36,0.47\n\
+                 90,0.30,0.26,0.36,0.47\n\
+                 120,0.20,0.26,0.36,0.47\n",
+            )
+            .unwrap(),
+        );
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let value = |payout, volatility| {
+            data.all_valuations
+                .iter()
+                .find(|v| {
+                    v.scenario()
+                        == ScenarioId {
+                            payout,
+                            volatility,
+                            lift: None,
+                        }
+                })
+                .unwrap()
+                .present_value
+        };
+        let years = 90.0 / 365.0;
+        assert_relative_eq!(
+            value(PayoutScenario::Day90, VolatilityScenario::Low),
+            data.summary.adjusted_baseline / 1.30_f64.powf(years),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            value(PayoutScenario::Day90, VolatilityScenario::Typical),
+            data.summary.adjusted_baseline / 1.26_f64.powf(years),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            data.discount_rate(ScenarioId {
+                payout: PayoutScenario::Day90,
+                volatility: VolatilityScenario::Low,
+                lift: None,
+            }),
+            0.30
+        );
+
+        // A matrix missing one of the run's payout rows is rejected
+        inputs.rate_matrix = Some(
+            crate::rate_matrix::parse_rate_matrix(
+                "payout,low,typical,high,extreme\n60,0.2,0.26,0.36,0.47\n",
+            )
+            .unwrap(),
+        );
+        let message = validate_inputs(&inputs).unwrap_err().to_string();
+        assert!(message.contains("no 90 Days row"));
+    }
+
+    #[test]
+    fn test_monthly_breakdown_against_bullet() {
+        let mut inputs = get_default_inputs();
+        inputs.baseline_only = true;
+        let bullet = calculate_full_valuation(&inputs).unwrap();
+        inputs.monthly_breakdown = true;
+        let monthly = calculate_full_valuation(&inputs).unwrap();
+
+        for (b, m) in bullet.all_valuations.iter().zip(&monthly.all_valuations) {
+            let days = b.payout_scenario.days().unwrap();
+            let rate = bullet.discount_rates[&b.volatility_scenario]
+                .total_rate()
+                .as_decimal();
+            let expected: f64 = [days - 60, days - 30, days]
+                .iter()
+                .map(|d| {
+                    bullet.summary.adjusted_baseline / 3.0 / (1.0 + rate).powf(*d as f64 / 365.0)
+                })
+                .sum();
+            assert_relative_eq!(m.present_value, expected, epsilon = 1e-6);
+            // Two of the three months arrive before the bullet date
+            assert!(m.present_value > b.present_value);
+        }
+
+        // Without discounting, the split changes nothing
+        inputs.discount_rate_overrides = Some(vec![Rate::constant(0.0); 4]);
+        let undiscounted = calculate_full_valuation(&inputs).unwrap();
+        for v in &undiscounted.all_valuations {
+            assert_relative_eq!(
+                v.present_value,
+                undiscounted.summary.adjusted_baseline,
+                epsilon = 1e-6
+            );
+        }
+    }
+
+    #[test]
+    fn test_revenue_floor_binds_without_lift_only() {
+        let mut inputs = get_default_inputs();
+        let unfloored = calculate_full_valuation(&inputs).unwrap();
+        let adjusted_baseline = unfloored.summary.adjusted_baseline;
+        let high_lift = LiftScenario::High.period_lift(
+            1000.0,
+            10.0,
+            15.0,
+            LiftRamp::Instant,
+            RevenuePeriod::Quarterly,
+        );
+        // Above the baseline alone, below the baseline with High lift
+        inputs.revenue_floor = Some(adjusted_baseline + high_lift / 2.0);
+        let floored = calculate_full_valuation(&inputs).unwrap();
+
+        for (before, after) in unfloored.all_valuations.iter().zip(&floored.all_valuations) {
+            match after.lift_scenario {
+                None => {
+                    assert_relative_eq!(after.floor_top_up, high_lift / 2.0, epsilon = 1e-6);
+                    assert!(after.present_value > before.present_value);
+                    assert_relative_eq!(
+                        after.face_value,
+                        adjusted_baseline + high_lift / 2.0,
+                        epsilon = 1e-6
+                    );
+                }
+                Some(LiftScenario::High) => {
+                    assert_eq!(after.floor_top_up, 0.0);
+                    assert_relative_eq!(after.present_value, before.present_value);
+                }
+                Some(_) => {}
+            }
+        }
+
+        inputs.revenue_floor = Some(-1.0);
+        assert!(validate_inputs(&inputs).is_err());
+    }
+
+    #[test]
+    fn test_compare_day_counts_values_central_scenario() {
+        let inputs = get_default_inputs();
+        let comparison = compare_day_counts(&inputs).unwrap();
+        assert_eq!(comparison.len(), 3);
+
+        let central = |day_count| {
+            comparison
+                .iter()
+                .find(|row| row.day_count == day_count)
+                .unwrap()
+                .central_estimate
+        };
+        let selected = calculate_full_valuation(&inputs).unwrap();
+        assert_relative_eq!(
+            central(DayCount::Actual365),
+            selected.summary.central_estimate
+        );
+        // A 360-day year stretches the same delay over more of a year
+        assert!(central(DayCount::Actual360) < central(DayCount::Actual365));
+    }
+
+    #[test]
+    fn test_risk_vs_time_decomposition_splits_central_haircut() {
+        let mut inputs = get_default_inputs();
+        let split = |inputs: &ValuationInputs| {
+            calculate_full_valuation(inputs)
+                .unwrap()
+                .haircut_splits
+                .into_iter()
+                .find(|split| split.payout == PayoutScenario::Day90)
+                .map(|split| (split.time, split.risk))
+                .unwrap()
+        };
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert_eq!(data.haircut_splits.len(), inputs.payout_scenarios().len());
+        let (time, risk) = split(&inputs);
+        assert!(time > 0.0 && risk > time);
+        assert_relative_eq!(
+            time + risk,
+            data.summary.central_face_value - data.summary.central_estimate,
+            max_relative = 1e-9
+        );
+
+        // Discounting at the risk-free rate alone leaves no haircut for risk
+        inputs.discount_rate_overrides = Some(vec![inputs.risk_free_rate; 4]);
+        let (rf_time, rf_risk) = split(&inputs);
+        assert_relative_eq!(rf_time, time, max_relative = 1e-9);
+        assert!(rf_risk.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_fixed_lift_replaces_driver_lift() {
+        let mut inputs = get_default_inputs();
+        inputs.fixed_lift = Some(5000.0);
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert_relative_eq!(
+            data.lift_assumptions.period_lift(LiftScenario::High),
+            7500.0
+        );
+
+        let comparison = compare_lift_models(&inputs).unwrap();
+        assert_relative_eq!(comparison.fixed, data.summary.central_estimate);
+        inputs.fixed_lift = None;
+        assert_relative_eq!(
+            comparison.driver_based,
+            calculate_full_valuation(&inputs)
+                .unwrap()
+                .summary
+                .central_estimate
+        );
+        assert_relative_eq!(
+            comparison.difference(),
+            comparison.fixed - comparison.driver_based
+        );
+        assert!(compare_lift_models(&inputs).is_err());
+
+        inputs.fixed_lift = Some(-1.0);
+        assert!(validate_inputs(&inputs).is_err());
+    }
+
+    #[test]
+    fn test_implied_stress_matches_market_price() {
+        let mut inputs = get_default_inputs();
+        let data = calculate_full_valuation(&inputs).unwrap();
+        let expected: f64 = scenario_outcomes(&data)
+            .iter()
+            .map(|(value, probability)| value * probability)
+            .sum();
+
+        // The unstressed weights price the pool at its expected value
+        assert_relative_eq!(
+            implied_stress(&data, expected).unwrap(),
+            0.0,
+            epsilon = 1e-9
+        );
+        let discounted = implied_stress(&data, expected * 0.99).unwrap();
+        let premium = implied_stress(&data, expected * 1.01).unwrap();
+        assert!(discounted > 0.0 && premium < 0.0);
+
+        // Beyond the grid's reach the price draws a warning instead
+        assert!(implied_stress(&data, data.summary.max_valuation * 2.0).is_err());
+        inputs.market_price = Some(data.summary.max_valuation * 2.0);
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert!(data.implied_stress.is_none());
+        assert!(
+            data.warnings
+                .iter()
+                .any(|w| matches!(w, ModelWarning::MarketPriceOutOfRange { .. }))
+        );
+
+        inputs.market_price = Some(expected * 0.99);
+        let data = calculate_full_valuation(&inputs).unwrap();
+        assert_relative_eq!(data.implied_stress.unwrap(), discounted);
+    }
+
+    #[test]
+    fn test_modeling_assumptions_reflect_active_options() {
+        let data = calculate_full_valuation(&get_default_inputs()).unwrap();
+        let statement = |assumptions: &[ModelingAssumption], topic: &str| {
+            assumptions
+                .iter()
+                .find(|a| a.topic == topic)
+                .map(|a| match &a.statement {
+                    AssumptionStatement::Text(text) => text.clone(),
+                    structured => format!("{:?}", structured),
+                })
+        };
+        let topics: Vec<&str> = data.assumptions.iter().map(|a| a.topic).collect();
+        assert_eq!(topics[0], "Discounting");
+        assert!(
+            statement(&data.assumptions, "Payout")
+                .unwrap()
+                .contains("Single bullet")
+        );
+        assert!(
+            statement(&data.assumptions, "Day Count")
+                .unwrap()
+                .starts_with("365-day year")
+        );
+        assert!(statement(&data.assumptions, "Audience Scaling").is_some());
+        assert!(statement(&data.assumptions, "Revenue Floor").is_none());
+
+        let mut inputs = get_default_inputs();
+        inputs.baseline_only = true;
+        inputs.monthly_breakdown = true;
+        inputs.day_count = DayCount::Thirty360;
+        inputs.revenue_floor = Some(150_000.0);
+        let assumptions = modeling_assumptions(&inputs);
+        assert!(
+            statement(&assumptions, "Payout")
+                .unwrap()
+                .contains("monthly installments")
+        );
+        assert!(
+            statement(&assumptions, "Day Count")
+                .unwrap()
+                .contains("30/360")
+        );
+        assert!(statement(&assumptions, "Audience Scaling").is_none());
+        assert!(statement(&assumptions, "Revenue Floor").is_some());
+
+        // Figures stay numeric for the report to format
+        inputs.quarters = 4;
+        inputs.after_tax = true;
+        inputs.tax_rate = 0.25;
+        let assumptions = modeling_assumptions(&inputs);
+        let find = |topic: &str| {
+            &assumptions
+                .iter()
+                .find(|a| a.topic == topic)
+                .unwrap()
+                .statement
+        };
+        assert_eq!(
+            find("RPM"),
+            &AssumptionStatement::FlatRpm {
+                baseline: 15.0,
+                lift: 15.0
+            }
+        );
+        assert_eq!(find("Tax"), &AssumptionStatement::TaxedAt { rate: 0.25 });
+        assert!(matches!(
+            find("Horizon"),
+            AssumptionStatement::Horizon { quarters: 4, .. }
+        ));
+    }
+
+    #[test]
+    fn test_weekly_period_reconciles_with_quarterly() {
+        let quarterly = get_default_inputs();
+        let mut weekly = get_default_inputs();
+        weekly.period = RevenuePeriod::Weekly;
+        weekly.raw_forecast = quarterly.raw_forecast / 13.0;
+        weekly.quarters = 13;
+
+        // Thirteen weeks of lift add up to one quarter's
+        let assumptions =
+            |inputs: &ValuationInputs| calculate_full_valuation(inputs).unwrap().lift_assumptions;
+        assert_relative_eq!(
+            assumptions(&weekly).period_lift(LiftScenario::Medium) * 13.0,
+            assumptions(&quarterly).period_lift(LiftScenario::Medium),
+            epsilon = 1e-9
+        );
+
+        let quarterly_data = calculate_full_valuation(&quarterly).unwrap();
+        let weekly_data = calculate_full_valuation(&weekly).unwrap();
+        for (q, w) in quarterly_data
+            .all_valuations
+            .iter()
+            .zip(&weekly_data.all_valuations)
+        {
+            assert_eq!(q.scenario(), w.scenario());
+            assert_relative_eq!(q.face_value, w.face_value, max_relative = 1e-9);
+            // Weekly cash flows arrive through the quarter rather than at its
+            // start, each discounted a further k/52 years
+            let rate = quarterly_data.discount_rate(q.scenario());
+            let spread: f64 = (0..13)
+                .map(|k| (1.0 + rate).powf(-(k as f64) / 52.0))
+                .sum::<f64>()
+                / 13.0;
+            assert_relative_eq!(
+                q.present_value * spread,
+                w.present_value,
+                max_relative = 1e-9
+            );
+        }
+    }
+
+    #[test]
+    fn test_monthly_breakdown_needs_quarterly_period() {
+        let mut inputs = get_default_inputs();
+        inputs.monthly_breakdown = true;
+        inputs.period = RevenuePeriod::Monthly;
+        let message = validate_inputs(&inputs).unwrap_err().to_string();
+        assert!(message.contains("needs the quarterly period"));
+    }
 }